@@ -0,0 +1,30 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use crowdfunding_client::accounts::AccountSource;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::{
+    instruction::Instruction,
+    signature::{Keypair, Signature},
+    signer::Signer,
+    transaction::Transaction,
+};
+
+/// Where the CLI reads accounts from and sends transactions to.
+#[async_trait(?Send)]
+pub trait Connection: AccountSource {
+    async fn send(&mut self, instructions: &[Instruction], signer: &Keypair) -> Result<Signature>;
+}
+
+#[async_trait(?Send)]
+impl Connection for RpcClient {
+    async fn send(&mut self, instructions: &[Instruction], signer: &Keypair) -> Result<Signature> {
+        let blockhash = self.get_latest_blockhash().await?;
+        let transaction = Transaction::new_signed_with_payer(
+            instructions,
+            Some(&signer.pubkey()),
+            &[signer],
+            blockhash,
+        );
+        Ok(self.send_and_confirm_transaction(&transaction).await?)
+    }
+}
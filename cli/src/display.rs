@@ -0,0 +1,315 @@
+use anchor_lang::prelude::Pubkey;
+use core::fmt::Write;
+use crowdfunding_client::{
+    accounts::{active_campaigns, season_top},
+    crowdfunding::state::*,
+};
+
+pub const CATEGORIES: [(&str, CampaignCategory); 9] = [
+    ("other", CampaignCategory::Other),
+    ("charity", CampaignCategory::Charity),
+    ("education", CampaignCategory::Education),
+    ("health", CampaignCategory::Health),
+    ("environment", CampaignCategory::Environment),
+    ("animals", CampaignCategory::Animals),
+    ("community", CampaignCategory::Community),
+    ("technology", CampaignCategory::Technology),
+    ("art", CampaignCategory::Art),
+];
+
+pub const REWARD_DISTRIBUTIONS: [(&str, RewardDistribution); 2] = [
+    ("tiers", RewardDistribution::Tiers),
+    ("proportional", RewardDistribution::Proportional),
+];
+
+pub const CHRT_VAULTS: [(&str, ChrtVault); 2] = [
+    ("fee-exemption", ChrtVault::FeeExemption),
+    ("liquidation", ChrtVault::Liquidation),
+];
+
+fn str_from_bytes(bytes: &[u8]) -> &str {
+    let len = bytes.iter().position(|&b| b == 0).unwrap_or(bytes.len());
+    core::str::from_utf8(&bytes[..len]).unwrap_or("<invalid utf-8>")
+}
+
+fn write_top<T: TopRecord>(out: &mut String, title: &str, top: &[T]) {
+    let top = &top[..top_len(top)];
+    writeln!(out, "{} ({}):", title, top.len()).unwrap();
+    for (i, record) in top.iter().enumerate() {
+        writeln!(
+            out,
+            "  {:>3}. {:<44} {:>20}",
+            i + 1,
+            record.donor(),
+            record.donations_sum()
+        )
+        .unwrap();
+    }
+}
+
+pub fn platform(
+    platform: &Platform,
+    seasonal_top: &SeasonalTop,
+    seasonal_top_records: &[SeasonalTopRecord],
+) -> String {
+    let mut out = String::new();
+    writeln!(out, "authority:                        {}", {
+        platform.authority
+    })
+    .unwrap();
+    if platform.pending_authority != Pubkey::default() {
+        writeln!(out, "pending authority:                {}", {
+            platform.pending_authority
+        })
+        .unwrap();
+    }
+    writeln!(out, "fee basis points:                 {}", {
+        platform.fee_basis_points
+    })
+    .unwrap();
+    writeln!(out, "fee exemption limit:              {}", {
+        platform.fee_exemption_limit
+    })
+    .unwrap();
+    writeln!(out, "unstake cooldown:                 {}", {
+        platform.unstake_cooldown
+    })
+    .unwrap();
+    writeln!(out, "liquidation limit:                {}", {
+        platform.liquidation_limit
+    })
+    .unwrap();
+    writeln!(out, "record donor bounty:              {}", {
+        platform.record_donor_bounty
+    })
+    .unwrap();
+    writeln!(out, "drop rewards bounty:              {}", {
+        platform.drop_rewards_bounty
+    })
+    .unwrap();
+    writeln!(out, "referral rate:                    {}", {
+        platform.referral_rate
+    })
+    .unwrap();
+    writeln!(out, "referral donation cap:            {}", {
+        platform.referral_donation_cap
+    })
+    .unwrap();
+    writeln!(out, "referral season cap:              {}", {
+        platform.referral_season_cap
+    })
+    .unwrap();
+    writeln!(out, "reward amount:                    {}", {
+        platform.reward_amount
+    })
+    .unwrap();
+    writeln!(
+        out,
+        "reward distribution:              {}",
+        REWARD_DISTRIBUTIONS
+            .get(platform.reward_distribution as usize)
+            .map_or("unknown", |d| d.0)
+    )
+    .unwrap();
+    let reward_tiers = { platform.reward_tiers };
+    writeln!(out, "reward tiers ({}):", { platform.reward_tiers_count }).unwrap();
+    for tier in &reward_tiers[..platform.reward_tiers_count as usize] {
+        writeln!(out, "  ..={:<3} {:>20}", tier.last_rank, tier.amount).unwrap();
+    }
+    if platform.reward_tiers_count == 0 {
+        writeln!(out, "  every rank gets the reward amount").unwrap();
+    }
+    writeln!(out, "reward cooldown:                  {}", {
+        platform.reward_cooldown
+    })
+    .unwrap();
+    writeln!(out, "reward procedure timeout:         {}", {
+        platform.reward_procedure_timeout
+    })
+    .unwrap();
+    writeln!(out, "last reward procedure:            {}", {
+        platform.last_reward_procedure_ts
+    })
+    .unwrap();
+    writeln!(out, "reward procedure in process:      {}", {
+        platform.reward_procedure_is_in_process
+    })
+    .unwrap();
+    writeln!(out, "season:                           {}", {
+        platform.season
+    })
+    .unwrap();
+    writeln!(
+        out,
+        "donors recorded:                  {}/{}",
+        { platform.donors_recorded },
+        { platform.donors_count }
+    )
+    .unwrap();
+    writeln!(out, "campaigns started:                {}", {
+        platform.campaigns_count
+    })
+    .unwrap();
+    writeln!(out, "sum of all donations:             {}", {
+        platform.sum_of_all_donations
+    })
+    .unwrap();
+    writeln!(out, "sum of active campaign donations: {}", {
+        platform.sum_of_active_campaign_donations
+    })
+    .unwrap();
+    writeln!(out, "avoided fees sum:                 {}", {
+        platform.avoided_fees_sum
+    })
+    .unwrap();
+    writeln!(out, "liquidations sum:                 {}", {
+        platform.liquidations_sum
+    })
+    .unwrap();
+
+    let campaigns = active_campaigns(platform);
+    writeln!(out, "active campaigns ({}):", campaigns.len()).unwrap();
+    for campaign in campaigns {
+        writeln!(
+            out,
+            "  {:>5} donations {:>20} withdrawn {:>20} goal {:>20}",
+            { campaign.id },
+            { campaign.donations_sum },
+            { campaign.withdrawn_sum },
+            { campaign.goal }
+        )
+        .unwrap();
+    }
+    write_top(&mut out, "top", &{ platform.top });
+    writeln!(out, "seasonal top capacity:            {}", {
+        seasonal_top.capacity
+    })
+    .unwrap();
+    if seasonal_top.cursor != 0 {
+        writeln!(out, "rewards distributed up to rank:   {}", {
+            seasonal_top.cursor
+        })
+        .unwrap();
+    }
+    write_top(&mut out, "seasonal top", seasonal_top_records);
+    out
+}
+
+pub fn campaign(campaign: &Campaign) -> String {
+    let mut out = String::new();
+    writeln!(out, "id:          {}", { campaign.id }).unwrap();
+    writeln!(out, "title:       {}", str_from_bytes(&{ campaign.title })).unwrap();
+    writeln!(out, "uri:         {}", str_from_bytes(&{ campaign.uri })).unwrap();
+    writeln!(
+        out,
+        "category:    {}",
+        CATEGORIES
+            .get(campaign.category as usize)
+            .map_or("unknown", |c| c.0)
+    )
+    .unwrap();
+    writeln!(out, "authority:   {}", { campaign.authority }).unwrap();
+    if campaign.pending_authority != Pubkey::default() {
+        writeln!(out, "pending authority: {}", { campaign.pending_authority }).unwrap();
+    }
+    writeln!(out, "beneficiary: {}", { campaign.beneficiary }).unwrap();
+    writeln!(out, "goal:        {}", { campaign.goal }).unwrap();
+    writeln!(out, "deadline:    {}", { campaign.deadline }).unwrap();
+    writeln!(
+        out,
+        "milestones:  {}/{} unlocked",
+        { campaign.milestones_unlocked },
+        { campaign.milestones_count }
+    )
+    .unwrap();
+    writeln!(out, "token vaults: {}", { campaign.token_vaults_count }).unwrap();
+    writeln!(out, "fee exemption deposits: {}", {
+        campaign.fee_exemption_deposits_sum
+    })
+    .unwrap();
+    writeln!(out, "liquidation deposits:   {}", {
+        campaign.liquidation_deposits_sum
+    })
+    .unwrap();
+    write_top(&mut out, "top", &{ campaign.top });
+    out
+}
+
+pub fn donor(donor: &Donor) -> String {
+    let mut out = String::new();
+    writeln!(out, "authority:               {}", { donor.authority }).unwrap();
+    writeln!(out, "donations sum:           {}", { donor.donations_sum }).unwrap();
+    writeln!(out, "recorded donations:      {}", {
+        donor.recorded_donations_sum
+    })
+    .unwrap();
+    writeln!(out, "rewarded donations:      {}", {
+        donor.rewarded_donations_sum
+    })
+    .unwrap();
+    if donor.referer != Pubkey::default() {
+        writeln!(out, "referer:                 {}", { donor.referer }).unwrap();
+    }
+    if donor.referral_code[0] != 0 {
+        writeln!(
+            out,
+            "referral code:           {}",
+            str_from_bytes(&donor.referral_code)
+        )
+        .unwrap();
+    }
+    writeln!(out, "referred donors:         {}", {
+        donor.referred_donors
+    })
+    .unwrap();
+    writeln!(out, "referred volume:         {}", {
+        donor.referred_volume
+    })
+    .unwrap();
+    writeln!(out, "referral rewards:        {}", {
+        donor.referral_rewards_sum
+    })
+    .unwrap();
+    writeln!(
+        out,
+        "season referral rewards: {} in season {}",
+        { donor.season_referral_rewards },
+        { donor.referral_season }
+    )
+    .unwrap();
+    out
+}
+
+pub fn season(season: &Season, records: &[SeasonRecord]) -> String {
+    let mut out = String::new();
+    writeln!(out, "season:          {}", { season.id }).unwrap();
+    writeln!(out, "started:         {}", { season.start_ts }).unwrap();
+    writeln!(out, "ended:           {}", { season.end_ts }).unwrap();
+    writeln!(out, "donations sum:   {}", { season.donations_sum }).unwrap();
+    writeln!(out, "donors recorded: {}", { season.donors_recorded }).unwrap();
+    writeln!(out, "rewards sum:     {}", { season.rewards_sum }).unwrap();
+    writeln!(out, "donors rewarded: {}", { season.donors_rewarded }).unwrap();
+    if season.rewards_committed {
+        writeln!(
+            out,
+            "merkle root:     {}",
+            solana_sdk::bs58::encode(season.merkle_root).into_string()
+        )
+        .unwrap();
+        writeln!(out, "claimed rewards: {}", { season.claimed_rewards_sum }).unwrap();
+    }
+    let top = season_top(records);
+    writeln!(out, "seasonal top ({}):", top.len()).unwrap();
+    for (i, record) in top.iter().enumerate() {
+        writeln!(
+            out,
+            "  {:>3}. {:<44} {:>20} reward {:>20}",
+            i + 1,
+            { record.donor },
+            { record.donations_sum },
+            { record.reward }
+        )
+        .unwrap();
+    }
+    out
+}
@@ -0,0 +1,541 @@
+use anchor_lang::prelude::Pubkey;
+use anyhow::{anyhow, Result};
+use clap::{Parser, Subcommand};
+use crowdfunding_client::{
+    accounts::{
+        fetch, fetch_campaign, fetch_chrt_vaults, fetch_donor, fetch_donor_donations_to_campaign,
+        fetch_platform, fetch_referral_code, fetch_season, fetch_seasonal_top,
+    },
+    crowdfunding::state::*,
+    instructions as ix,
+    pda::find_token_donations,
+};
+use solana_sdk::{signature::Keypair, signer::Signer};
+
+pub use crate::connection::Connection;
+
+pub mod connection;
+pub mod display;
+
+#[derive(Parser, Debug)]
+#[clap(version, about)]
+pub struct Cli {
+    /// RPC URL of the cluster
+    #[clap(long, short, default_value = "http://127.0.0.1:8899")]
+    pub url: String,
+    /// Keypair signing and paying for transactions
+    #[clap(long, short)]
+    pub keypair: Option<String>,
+    #[clap(subcommand)]
+    pub command: Command,
+}
+
+fn parse_category(s: &str) -> Result<CampaignCategory, String> {
+    display::CATEGORIES
+        .iter()
+        .find(|c| c.0 == s)
+        .map(|c| c.1)
+        .ok_or_else(|| format!("unknown category `{}`", s))
+}
+
+fn parse_reward_distribution(s: &str) -> Result<RewardDistribution, String> {
+    display::REWARD_DISTRIBUTIONS
+        .iter()
+        .find(|d| d.0 == s)
+        .map(|d| d.1)
+        .ok_or_else(|| format!("unknown reward distribution `{}`", s))
+}
+
+fn parse_chrt_vault(s: &str) -> Result<ChrtVault, String> {
+    display::CHRT_VAULTS
+        .iter()
+        .find(|v| v.0 == s)
+        .map(|v| v.1)
+        .ok_or_else(|| format!("unknown CHRT vault `{}`", s))
+}
+
+fn parse_reward_tier(s: &str) -> Result<RewardTier, String> {
+    let (last_rank, amount) = s
+        .split_once(':')
+        .ok_or_else(|| format!("reward tier `{}` is not LAST_RANK:AMOUNT", s))?;
+    Ok(RewardTier {
+        last_rank: last_rank.parse().map_err(|e| format!("{}", e))?,
+        amount: amount.parse().map_err(|e| format!("{}", e))?,
+    })
+}
+
+fn parse_hash(s: &str) -> Result<[u8; 32], String> {
+    let bytes = solana_sdk::bs58::decode(s)
+        .into_vec()
+        .map_err(|e| format!("{}", e))?;
+    bytes
+        .try_into()
+        .map_err(|_| format!("`{}` is not a 32 byte hash", s))
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Initialize the platform with the keypair as its authority
+    Initialize {
+        /// Ranks kept in the seasonal top
+        #[clap(long, default_value = "10")]
+        seasonal_top_capacity: u16,
+        #[clap(long)]
+        reward_cooldown: u32,
+        #[clap(long)]
+        reward_amount: u64,
+        #[clap(long, parse(try_from_str = parse_reward_distribution), default_value = "tiers")]
+        reward_distribution: RewardDistribution,
+        /// Reward tiers as LAST_RANK:AMOUNT, by default every seasonal top rank gets the reward amount
+        #[clap(long, use_value_delimiter = true, parse(try_from_str = parse_reward_tier))]
+        reward_tiers: Vec<RewardTier>,
+        #[clap(long)]
+        fee_basis_points: u16,
+        #[clap(long)]
+        fee_exemption_limit: u64,
+        #[clap(long)]
+        liquidation_limit: u64,
+    },
+    /// Register the keypair as a donor
+    RegisterDonor,
+    /// Register the one referral code others can donate with to refer the keypair, in lowercase
+    RegisterReferralCode { code: String },
+    /// Start a campaign with the keypair as its authority
+    StartCampaign {
+        #[clap(long)]
+        beneficiary: Pubkey,
+        #[clap(long)]
+        title: String,
+        #[clap(long)]
+        uri: String,
+        #[clap(long, parse(try_from_str = parse_category), default_value = "other")]
+        category: CampaignCategory,
+        #[clap(long)]
+        goal: Option<u64>,
+        #[clap(long)]
+        deadline: Option<u32>,
+        /// Milestone shares in basis points, summing up to 10000
+        #[clap(long, use_value_delimiter = true)]
+        milestones: Vec<u16>,
+    },
+    /// Donate lamports to a campaign, optionally minting CHRT to a referer
+    Donate {
+        campaign_id: u16,
+        lamports: u64,
+        #[clap(long)]
+        referer: Option<Pubkey>,
+        /// Referral code resolving to the referer
+        #[clap(long, conflicts_with = "referer")]
+        referral_code: Option<String>,
+    },
+    /// Credit the keypair's donations to a campaign that reached its goal, minting CHRT to the referer they were made with
+    CreditDonations { campaign_id: u16 },
+    /// Stake CHRT toward a campaign's fee exemption
+    Stake { campaign_id: u16, amount: u64 },
+    /// Start the cooldown for unstaking CHRT from a campaign
+    RequestUnstake { campaign_id: u16, amount: u64 },
+    /// Withdraw the CHRT whose unstake cooldown has passed
+    Unstake { campaign_id: u16 },
+    /// Deposit CHRT into a campaign's fee exemption vault
+    SupportCampaign { campaign_id: u16, amount: u64 },
+    /// Deposit CHRT into a campaign's liquidation vault
+    FlagCampaign { campaign_id: u16, amount: u64 },
+    /// Withdraw CHRT deposited into a vault of a stopped campaign
+    WithdrawChrtDeposit {
+        campaign_id: u16,
+        /// `fee-exemption` or `liquidation`
+        #[clap(parse(try_from_str = parse_chrt_vault))]
+        vault: ChrtVault,
+    },
+    /// Record the given donors for the reward procedure
+    RecordDonors { donors: Vec<Pubkey> },
+    /// Reward the next page of the seasonal top once every donor is recorded
+    DropRewards {
+        #[clap(long, default_value = "10")]
+        page_size: usize,
+    },
+    /// Commit the rewards of the next page of the seasonal top for donors to claim them
+    CommitRewards {
+        #[clap(long, default_value = "20")]
+        page_size: usize,
+    },
+    /// Claim the keypair's reward committed for a season
+    ClaimReward {
+        season: u32,
+        amount: u64,
+        /// Base58 Merkle proof hashes printed by the crank
+        #[clap(long, use_value_delimiter = true, parse(try_from_str = parse_hash))]
+        proof: Vec<[u8; 32]>,
+    },
+    /// Abort the reward procedure, discarding recorded donors
+    AbortRewardProcedure,
+    /// Change the number of ranks kept in the seasonal top between reward procedures
+    ResizeSeasonalTop { capacity: u16 },
+    /// Release the unlocked donations of a campaign to its beneficiary
+    WithdrawDonations { campaign_id: u16 },
+    /// Stop a campaign, paying its unlocked donations to its beneficiary and leaving locked ones to refund
+    StopCampaign { campaign_id: u16 },
+    /// Liquidate a campaign whose liquidation vault reached the limit
+    LiquidateCampaign { campaign_id: u16 },
+    /// Stop a campaign whose deadline has passed
+    ExpireCampaign { campaign_id: u16 },
+    /// Refund the keypair's share of donations to a campaign that missed its goal or milestones
+    RefundDonations { campaign_id: u16 },
+    /// Vote for unlocking the next milestone of a campaign
+    VoteMilestone { campaign_id: u16 },
+    /// Withdraw the collected platform fees
+    WithdrawFees,
+    /// Update the given platform config values, the reward ones only between reward procedures
+    UpdatePlatformConfig {
+        #[clap(long)]
+        reward_cooldown: Option<u32>,
+        /// Seconds after which recording donors restarts an unfinished reward procedure, 0 for none
+        #[clap(long)]
+        reward_procedure_timeout: Option<u32>,
+        #[clap(long)]
+        reward_amount: Option<u64>,
+        #[clap(long, parse(try_from_str = parse_reward_distribution))]
+        reward_distribution: Option<RewardDistribution>,
+        /// Reward tiers as LAST_RANK:AMOUNT
+        #[clap(long, use_value_delimiter = true, parse(try_from_str = parse_reward_tier))]
+        reward_tiers: Option<Vec<RewardTier>>,
+        #[clap(long)]
+        fee_basis_points: Option<u16>,
+        #[clap(long)]
+        fee_exemption_limit: Option<u64>,
+        #[clap(long)]
+        liquidation_limit: Option<u64>,
+        /// Lamports paid from the fee vault per recorded donor whose season fees cover it
+        #[clap(long)]
+        record_donor_bounty: Option<u64>,
+        /// Lamports paid from the fee vault for dropping rewards
+        #[clap(long)]
+        drop_rewards_bounty: Option<u64>,
+        /// CHRT base units minted to a referer per donated SOL
+        #[clap(long)]
+        referral_rate: Option<u64>,
+        /// Most CHRT base units a referer earns from one donation, 0 for no cap
+        #[clap(long)]
+        referral_donation_cap: Option<u64>,
+        /// Most CHRT base units a referer earns in a season, 0 for no cap
+        #[clap(long)]
+        referral_season_cap: Option<u64>,
+        /// Seconds requested unstakes stay locked
+        #[clap(long)]
+        unstake_cooldown: Option<u32>,
+    },
+    /// Propose a new platform authority
+    ProposePlatformAuthority { new_authority: Pubkey },
+    /// Accept the platform authority proposed to the keypair
+    AcceptPlatformAuthority,
+    /// Cancel the pending platform authority proposal
+    CancelPlatformAuthority,
+    /// Propose a new campaign authority
+    ProposeCampaignAuthority {
+        campaign_id: u16,
+        new_authority: Pubkey,
+    },
+    /// Accept the campaign authority proposed to the keypair
+    AcceptCampaignAuthority { campaign_id: u16 },
+    /// Cancel the pending campaign authority proposal
+    CancelCampaignAuthority { campaign_id: u16 },
+    /// Update the given campaign metadata values
+    UpdateCampaignMetadata {
+        campaign_id: u16,
+        #[clap(long)]
+        title: Option<String>,
+        #[clap(long)]
+        uri: Option<String>,
+        #[clap(long, parse(try_from_str = parse_category))]
+        category: Option<CampaignCategory>,
+    },
+    /// Open a vault accepting donations of the mint to a campaign
+    OpenTokenVault { campaign_id: u16, mint: Pubkey },
+    /// Donate tokens of the mint to a campaign
+    DonateToken {
+        campaign_id: u16,
+        mint: Pubkey,
+        amount: u64,
+    },
+    /// Release the unlocked token donations of a campaign to its beneficiary
+    WithdrawTokenDonations { campaign_id: u16, mint: Pubkey },
+    /// Pay out and close a campaign token vault
+    CloseTokenVault { campaign_id: u16, mint: Pubkey },
+    /// Refund the keypair's share of a token vault closed with locked milestones
+    RefundTokenDonations { campaign_id: u16, mint: Pubkey },
+    /// Move a liquidated campaign's token vault to the platform
+    LiquidateTokenVault { campaign_id: u16, mint: Pubkey },
+    /// Withdraw the collected platform fees of the mint
+    WithdrawTokenFees { mint: Pubkey },
+    /// Print the platform configuration, sums, tops and active campaigns
+    ShowPlatform,
+    /// Print a campaign and its top
+    ShowCampaign { campaign_id: u16 },
+    /// Print an ended season with its seasonal top and rewards
+    ShowSeason { season_id: u32 },
+    /// Print a donor and its referral statistics, the keypair's by default
+    ShowDonor { donor: Option<Pubkey> },
+}
+
+/// Seasonal top donors from the cursor on that the next reward page takes.
+async fn next_reward_page<C: Connection>(conn: &mut C, page_size: usize) -> Result<Vec<Pubkey>> {
+    let (seasonal_top, records) = fetch_seasonal_top(conn).await?;
+    Ok(records[..top_len(&records)]
+        .iter()
+        .skip(seasonal_top.cursor as usize)
+        .take(page_size)
+        .map(|d| d.donor)
+        .collect())
+}
+
+/// Executes `command` and returns what should be printed.
+pub async fn run<C: Connection>(
+    conn: &mut C,
+    signer: &Keypair,
+    command: Command,
+) -> Result<String> {
+    let authority = signer.pubkey();
+    let instruction = match command {
+        Command::Initialize {
+            seasonal_top_capacity,
+            reward_cooldown,
+            reward_amount,
+            reward_distribution,
+            reward_tiers,
+            fee_basis_points,
+            fee_exemption_limit,
+            liquidation_limit,
+        } => ix::initialize(
+            authority,
+            seasonal_top_capacity,
+            reward_cooldown,
+            reward_amount,
+            reward_distribution,
+            reward_tiers,
+            fee_basis_points,
+            fee_exemption_limit,
+            liquidation_limit,
+        ),
+        Command::RegisterDonor => ix::register_donor(authority),
+        Command::RegisterReferralCode { code } => ix::register_referral_code(authority, code),
+        Command::StartCampaign {
+            beneficiary,
+            title,
+            uri,
+            category,
+            goal,
+            deadline,
+            milestones,
+        } => {
+            let campaign_id = fetch_platform(conn).await?.campaigns_count;
+            ix::start_campaign(
+                authority,
+                campaign_id,
+                beneficiary,
+                title,
+                uri,
+                category,
+                goal,
+                deadline,
+                milestones,
+            )
+        }
+        Command::Donate {
+            campaign_id,
+            lamports,
+            referer: Some(referer),
+            ..
+        } => ix::donate_with_referer(authority, campaign_id, lamports, referer),
+        Command::Donate {
+            campaign_id,
+            lamports,
+            referral_code: Some(code),
+            ..
+        } => {
+            let ReferralCode { donor } = fetch_referral_code(conn, &code).await?;
+            ix::donate_with_referral_code(authority, campaign_id, lamports, code, donor)
+        }
+        Command::Donate {
+            campaign_id,
+            lamports,
+            ..
+        } => ix::donate(authority, campaign_id, lamports),
+        Command::CreditDonations { campaign_id } => {
+            let donations = fetch_donor_donations_to_campaign(conn, authority, campaign_id).await?;
+            if donations.uncredited_referred_sum != 0 {
+                ix::credit_donations_with_referer(authority, campaign_id, donations.referer)
+            } else {
+                ix::credit_donations(authority, campaign_id)
+            }
+        }
+        Command::Stake {
+            campaign_id,
+            amount,
+        } => ix::stake(authority, campaign_id, amount),
+        Command::RequestUnstake {
+            campaign_id,
+            amount,
+        } => ix::request_unstake(authority, campaign_id, amount),
+        Command::Unstake { campaign_id } => ix::unstake(authority, campaign_id),
+        Command::SupportCampaign {
+            campaign_id,
+            amount,
+        } => ix::support_campaign(authority, campaign_id, amount),
+        Command::FlagCampaign {
+            campaign_id,
+            amount,
+        } => ix::flag_campaign(authority, campaign_id, amount),
+        Command::WithdrawChrtDeposit { campaign_id, vault } => {
+            let chrt_vaults_payer = fetch_chrt_vaults(conn, campaign_id).await?.payer;
+            ix::withdraw_chrt_deposit(authority, campaign_id, vault, chrt_vaults_payer)
+        }
+        Command::RecordDonors { donors } => {
+            if donors.is_empty() {
+                return Err(anyhow!(
+                    "pass the donors to record, or use the crank to page through all of them"
+                ));
+            }
+            ix::record_donors(authority, &donors)
+        }
+        Command::DropRewards { page_size } => {
+            let season_id = fetch_platform(conn).await?.season;
+            let page = next_reward_page(conn, page_size).await?;
+            ix::drop_rewards(authority, season_id, &page)
+        }
+        Command::CommitRewards { page_size } => {
+            let season_id = fetch_platform(conn).await?.season;
+            let page = next_reward_page(conn, page_size).await?;
+            ix::commit_rewards(authority, season_id, &page)
+        }
+        Command::ClaimReward {
+            season,
+            amount,
+            proof,
+        } => ix::claim_reward(authority, season, amount, proof),
+        Command::AbortRewardProcedure => ix::abort_reward_procedure(authority),
+        Command::ResizeSeasonalTop { capacity } => ix::resize_seasonal_top(authority, capacity),
+        Command::WithdrawDonations { campaign_id } => {
+            let beneficiary = fetch_campaign(conn, campaign_id).await?.beneficiary;
+            ix::withdraw_donations(authority, campaign_id, beneficiary)
+        }
+        Command::StopCampaign { campaign_id } => {
+            let beneficiary = fetch_campaign(conn, campaign_id).await?.beneficiary;
+            let chrt_vaults_payer = fetch_chrt_vaults(conn, campaign_id).await?.payer;
+            ix::stop_campaign(authority, campaign_id, beneficiary, chrt_vaults_payer)
+        }
+        Command::LiquidateCampaign { campaign_id } => {
+            let beneficiary = fetch_campaign(conn, campaign_id).await?.beneficiary;
+            let chrt_vaults_payer = fetch_chrt_vaults(conn, campaign_id).await?.payer;
+            ix::liquidate_campaign(campaign_id, beneficiary, chrt_vaults_payer)
+        }
+        Command::ExpireCampaign { campaign_id } => {
+            let beneficiary = fetch_campaign(conn, campaign_id).await?.beneficiary;
+            let chrt_vaults_payer = fetch_chrt_vaults(conn, campaign_id).await?.payer;
+            ix::expire_campaign(campaign_id, beneficiary, chrt_vaults_payer)
+        }
+        Command::RefundDonations { campaign_id } => ix::refund_donations(authority, campaign_id),
+        Command::VoteMilestone { campaign_id } => {
+            let milestone = fetch_campaign(conn, campaign_id).await?.milestones_unlocked;
+            ix::vote_milestone(authority, campaign_id, milestone)
+        }
+        Command::WithdrawFees => ix::withdraw_fees(authority),
+        Command::UpdatePlatformConfig {
+            reward_cooldown,
+            reward_procedure_timeout,
+            reward_amount,
+            reward_distribution,
+            reward_tiers,
+            fee_basis_points,
+            fee_exemption_limit,
+            liquidation_limit,
+            record_donor_bounty,
+            drop_rewards_bounty,
+            referral_rate,
+            referral_donation_cap,
+            referral_season_cap,
+            unstake_cooldown,
+        } => ix::update_platform_config(
+            authority,
+            reward_cooldown,
+            reward_procedure_timeout,
+            reward_amount,
+            reward_distribution,
+            reward_tiers,
+            fee_basis_points,
+            fee_exemption_limit,
+            liquidation_limit,
+            record_donor_bounty,
+            drop_rewards_bounty,
+            referral_rate,
+            referral_donation_cap,
+            referral_season_cap,
+            unstake_cooldown,
+        ),
+        Command::ProposePlatformAuthority { new_authority } => {
+            ix::propose_platform_authority(authority, new_authority)
+        }
+        Command::AcceptPlatformAuthority => ix::accept_platform_authority(authority),
+        Command::CancelPlatformAuthority => ix::cancel_platform_authority(authority),
+        Command::ProposeCampaignAuthority {
+            campaign_id,
+            new_authority,
+        } => ix::propose_campaign_authority(authority, campaign_id, new_authority),
+        Command::AcceptCampaignAuthority { campaign_id } => {
+            ix::accept_campaign_authority(authority, campaign_id)
+        }
+        Command::CancelCampaignAuthority { campaign_id } => {
+            ix::cancel_campaign_authority(authority, campaign_id)
+        }
+        Command::UpdateCampaignMetadata {
+            campaign_id,
+            title,
+            uri,
+            category,
+        } => ix::update_campaign_metadata(authority, campaign_id, title, uri, category),
+        Command::OpenTokenVault { campaign_id, mint } => {
+            ix::open_token_vault(authority, campaign_id, mint)
+        }
+        Command::DonateToken {
+            campaign_id,
+            mint,
+            amount,
+        } => ix::donate_token(authority, campaign_id, mint, amount),
+        Command::WithdrawTokenDonations { campaign_id, mint } => {
+            let beneficiary = fetch_campaign(conn, campaign_id).await?.beneficiary;
+            ix::withdraw_token_donations(authority, campaign_id, mint, beneficiary)
+        }
+        Command::CloseTokenVault { campaign_id, mint } => {
+            let beneficiary = fetch_campaign(conn, campaign_id).await?.beneficiary;
+            ix::close_token_vault(authority, campaign_id, mint, beneficiary)
+        }
+        Command::RefundTokenDonations { campaign_id, mint } => {
+            let token_donations: TokenDonations =
+                fetch(conn, find_token_donations(campaign_id, mint)).await?;
+            ix::refund_token_donations(authority, campaign_id, mint, token_donations.beneficiary)
+        }
+        Command::LiquidateTokenVault { campaign_id, mint } => {
+            let beneficiary = fetch_campaign(conn, campaign_id).await?.beneficiary;
+            ix::liquidate_token_vault(campaign_id, mint, beneficiary)
+        }
+        Command::WithdrawTokenFees { mint } => ix::withdraw_token_fees(authority, mint),
+        Command::ShowPlatform => {
+            let platform = fetch_platform(conn).await?;
+            let (seasonal_top, records) = fetch_seasonal_top(conn).await?;
+            return Ok(display::platform(&platform, &seasonal_top, &records));
+        }
+        Command::ShowCampaign { campaign_id } => {
+            return Ok(display::campaign(&fetch_campaign(conn, campaign_id).await?))
+        }
+        Command::ShowSeason { season_id } => {
+            let (season, records) = fetch_season(conn, season_id).await?;
+            return Ok(display::season(&season, &records));
+        }
+        Command::ShowDonor { donor } => {
+            let donor = donor.unwrap_or(authority);
+            let donor = fetch_donor(conn, donor).await?;
+            return Ok(display::donor(&donor));
+        }
+    };
+
+    Ok(conn.send(&[instruction], signer).await?.to_string())
+}
@@ -0,0 +1,21 @@
+use anyhow::{anyhow, Result};
+use clap::Parser;
+use crowdfunding_cli::{run, Cli};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::{commitment_config::CommitmentConfig, signature::read_keypair_file};
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let cli = Cli::parse();
+    let keypair_path = match cli.keypair {
+        Some(path) => path,
+        None => format!("{}/.config/solana/id.json", std::env::var("HOME")?),
+    };
+    let signer = read_keypair_file(&keypair_path)
+        .map_err(|e| anyhow!("failed to read keypair {}: {}", keypair_path, e))?;
+    let mut client = RpcClient::new_with_commitment(cli.url, CommitmentConfig::confirmed());
+
+    println!("{}", run(&mut client, &signer, cli.command).await?);
+
+    Ok(())
+}
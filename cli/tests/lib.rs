@@ -0,0 +1,103 @@
+#![cfg(feature = "test-bpf")]
+
+use anchor_lang::prelude::Pubkey;
+use anyhow::Result;
+use async_trait::async_trait;
+use clap::Parser;
+use crowdfunding_cli::{run, Cli, Connection};
+use crowdfunding_client::{accounts::AccountSource, Error};
+use solana_program_test::{tokio, ProgramTest, ProgramTestContext};
+use solana_sdk::{
+    instruction::Instruction,
+    signature::{Keypair, Signature},
+    signer::Signer,
+    system_instruction,
+    transaction::Transaction,
+};
+
+struct TestConnection(ProgramTestContext);
+
+#[async_trait(?Send)]
+impl AccountSource for TestConnection {
+    async fn get_account_data(&self, address: Pubkey) -> crowdfunding_client::Result<Vec<u8>> {
+        Ok(self
+            .0
+            .banks_client
+            .clone()
+            .get_account(address)
+            .await
+            .map_err(|e| Error::Source(e.into()))?
+            .ok_or_else(|| Error::Source(format!("account {} not found", address).into()))?
+            .data)
+    }
+}
+
+#[async_trait(?Send)]
+impl Connection for TestConnection {
+    async fn send(&mut self, instructions: &[Instruction], signer: &Keypair) -> Result<Signature> {
+        let transaction = Transaction::new_signed_with_payer(
+            instructions,
+            Some(&signer.pubkey()),
+            &[signer],
+            self.0.last_blockhash,
+        );
+        let signature = transaction.signatures[0];
+        self.0.banks_client.process_transaction(transaction).await?;
+        Ok(signature)
+    }
+}
+
+async fn exec(conn: &mut TestConnection, signer: &Keypair, args: &str) -> String {
+    let cli = Cli::parse_from(["crowdfunding-cli"].into_iter().chain(args.split(' ')));
+    run(conn, signer, cli.command).await.unwrap()
+}
+
+#[tokio::test]
+async fn test_cli() {
+    let pt = ProgramTest::new("crowdfunding", crowdfunding_client::crowdfunding::ID, None);
+    let mut conn = TestConnection(pt.start_with_context().await);
+    let operator = Keypair::new();
+    let ptc = &mut conn.0;
+    ptc.banks_client
+        .process_transaction(Transaction::new_signed_with_payer(
+            &[system_instruction::transfer(
+                &ptc.payer.pubkey(),
+                &operator.pubkey(),
+                1_000_000_000,
+            )],
+            Some(&ptc.payer.pubkey()),
+            &[&ptc.payer],
+            ptc.last_blockhash,
+        ))
+        .await
+        .unwrap();
+
+    exec(
+        &mut conn,
+        &operator,
+        "initialize --reward-cooldown 0 --reward-amount 1000 --fee-basis-points 300 \
+         --fee-exemption-limit 1000 --liquidation-limit 1000",
+    )
+    .await;
+    exec(&mut conn, &operator, "register-donor").await;
+    exec(
+        &mut conn,
+        &operator,
+        &format!(
+            "start-campaign --beneficiary {} --title Campaign --uri https://example.com \
+             --category charity",
+            operator.pubkey()
+        ),
+    )
+    .await;
+    exec(&mut conn, &operator, "donate 0 1000").await;
+
+    let platform = exec(&mut conn, &operator, "show-platform").await;
+    assert!(platform.contains("sum of all donations:             970"));
+    assert!(platform.contains("active campaigns (1):"));
+
+    let campaign = exec(&mut conn, &operator, "show-campaign 0").await;
+    assert!(campaign.contains("title:       Campaign"));
+    assert!(campaign.contains("category:    charity"));
+    assert!(campaign.contains(&format!("    1. {:<44} {:>20}", operator.pubkey(), 970)));
+}
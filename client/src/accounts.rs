@@ -0,0 +1,161 @@
+use crate::{pda::*, Result};
+use anchor_lang::{prelude::Pubkey, AccountDeserialize, Discriminator};
+use async_trait::async_trait;
+use bytemuck::Pod;
+use core::mem::size_of;
+use crowdfunding::state::*;
+use solana_account_decoder::UiAccountEncoding;
+use solana_client::{
+    nonblocking::rpc_client::RpcClient,
+    rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig},
+    rpc_filter::{Memcmp, MemcmpEncodedBytes, RpcFilterType},
+};
+
+/// Where the fetch helpers read single accounts from: an `RpcClient`, or a test bank.
+#[async_trait(?Send)]
+pub trait AccountSource {
+    async fn get_account_data(&self, address: Pubkey) -> Result<Vec<u8>>;
+}
+
+#[async_trait(?Send)]
+impl AccountSource for RpcClient {
+    async fn get_account_data(&self, address: Pubkey) -> Result<Vec<u8>> {
+        Ok(RpcClient::get_account_data(self, &address).await?)
+    }
+}
+
+pub fn decode<T: AccountDeserialize>(mut data: &[u8]) -> Result<T> {
+    Ok(T::try_deserialize(&mut data)?)
+}
+
+/// Decodes an account holding a `T` followed by a list of `R`s.
+pub fn decode_with_records<T: AccountDeserialize, R: Pod>(data: &[u8]) -> Result<(T, Vec<R>)> {
+    let (header, records) = data.split_at(data.len().min(8 + size_of::<T>()));
+    let records = records
+        .chunks_exact(size_of::<R>())
+        .map(bytemuck::pod_read_unaligned)
+        .collect();
+    Ok((decode(header)?, records))
+}
+
+pub async fn fetch<T: AccountDeserialize, S: AccountSource>(
+    source: &S,
+    address: Pubkey,
+) -> Result<T> {
+    decode(&source.get_account_data(address).await?)
+}
+
+pub async fn fetch_platform<S: AccountSource>(source: &S) -> Result<Platform> {
+    fetch(source, find_platform()).await
+}
+
+pub async fn fetch_campaign<S: AccountSource>(source: &S, campaign_id: u16) -> Result<Campaign> {
+    fetch(source, find_campaign(campaign_id)).await
+}
+
+pub async fn fetch_chrt_vaults<S: AccountSource>(
+    source: &S,
+    campaign_id: u16,
+) -> Result<ChrtVaults> {
+    fetch(source, find_chrt_vaults(campaign_id)).await
+}
+
+pub async fn fetch_donor<S: AccountSource>(source: &S, donor_authority: Pubkey) -> Result<Donor> {
+    fetch(source, find_donor(donor_authority)).await
+}
+
+pub async fn fetch_referral_code<S: AccountSource>(source: &S, code: &str) -> Result<ReferralCode> {
+    fetch(source, find_referral_code(code)).await
+}
+
+pub async fn fetch_seasonal_top<S: AccountSource>(
+    source: &S,
+) -> Result<(SeasonalTop, Vec<SeasonalTopRecord>)> {
+    decode_with_records(&source.get_account_data(find_seasonal_top()).await?)
+}
+
+pub async fn fetch_season<S: AccountSource>(
+    source: &S,
+    season_id: u32,
+) -> Result<(Season, Vec<SeasonRecord>)> {
+    decode_with_records(&source.get_account_data(find_season(season_id)).await?)
+}
+
+/// Fetches the data of every account of the type through `getProgramAccounts`,
+/// of the given size if it is fixed.
+async fn fetch_all_data<T: Discriminator>(
+    client: &RpcClient,
+    data_size: Option<usize>,
+) -> Result<Vec<Vec<u8>>> {
+    let mut filters = vec![RpcFilterType::Memcmp(Memcmp {
+        offset: 0,
+        bytes: MemcmpEncodedBytes::Base58(
+            solana_sdk::bs58::encode(T::discriminator()).into_string(),
+        ),
+        encoding: None,
+    })];
+    if let Some(data_size) = data_size {
+        filters.push(RpcFilterType::DataSize(data_size as u64));
+    }
+    let config = RpcProgramAccountsConfig {
+        filters: Some(filters),
+        account_config: RpcAccountInfoConfig {
+            encoding: Some(UiAccountEncoding::Base64),
+            ..Default::default()
+        },
+        with_context: None,
+    };
+    Ok(client
+        .get_program_accounts_with_config(&crowdfunding::ID, config)
+        .await?
+        .into_iter()
+        .map(|(_, account)| account.data)
+        .collect())
+}
+
+/// Fetches every registered `Donor` through `getProgramAccounts`.
+pub async fn fetch_all_donors(client: &RpcClient) -> Result<Vec<Donor>> {
+    fetch_all_data::<Donor>(client, Some(8 + size_of::<Donor>()))
+        .await?
+        .iter()
+        .map(|data| decode(data))
+        .collect()
+}
+
+/// Every season with its seasonal top in order, for rebuilding the hall of fame.
+pub async fn fetch_all_seasons(client: &RpcClient) -> Result<Vec<(Season, Vec<SeasonRecord>)>> {
+    let mut seasons = fetch_all_data::<Season>(client, None)
+        .await?
+        .iter()
+        .map(|data| decode_with_records(data))
+        .collect::<Result<Vec<(Season, Vec<SeasonRecord>)>>>()?;
+    seasons.sort_by_key(|(s, _)| s.id);
+    Ok(seasons)
+}
+
+pub async fn fetch_total_donations_to_campaign<S: AccountSource>(
+    source: &S,
+    campaign_id: u16,
+) -> Result<Donations> {
+    fetch(source, find_total_donations_to_campaign(campaign_id)).await
+}
+
+pub async fn fetch_donor_donations_to_campaign<S: AccountSource>(
+    source: &S,
+    donor_authority: Pubkey,
+    campaign_id: u16,
+) -> Result<Donations> {
+    fetch(
+        source,
+        find_donor_donations_to_campaign(donor_authority, campaign_id),
+    )
+    .await
+}
+
+pub fn active_campaigns(platform: &Platform) -> &[CampaignRecord] {
+    &platform.active_campaigns[..platform.active_campaigns_count as usize]
+}
+
+pub fn season_top(records: &[SeasonRecord]) -> &[SeasonRecord] {
+    &records[..top_len(records)]
+}
@@ -0,0 +1,875 @@
+use crate::pda::*;
+use anchor_lang::{
+    prelude::{AccountMeta, Pubkey},
+    solana_program::{instruction::Instruction, system_program, sysvar},
+    InstructionData, ToAccountMetas,
+};
+use crowdfunding::{
+    accounts, instruction,
+    state::{CampaignCategory, ChrtVault, RewardDistribution, RewardTier},
+};
+use spl_associated_token_account::get_associated_token_address;
+
+fn build(data: impl InstructionData, accounts: impl ToAccountMetas) -> Instruction {
+    Instruction {
+        program_id: crowdfunding::ID,
+        data: data.data(),
+        accounts: accounts.to_account_metas(None),
+    }
+}
+
+fn donate_accounts(donor_authority: Pubkey, campaign_id: u16) -> accounts::Donate {
+    accounts::Donate {
+        platform: find_platform(),
+        fee_vault: find_fee_vault(),
+        sol_vault: find_sol_vault(),
+        campaign: find_campaign(campaign_id),
+        total_donations_to_campaign: find_total_donations_to_campaign(campaign_id),
+        stake_pool: find_stake_pool(campaign_id),
+        donor: find_donor(donor_authority),
+        donor_authority,
+        donor_donations_to_campaign: find_donor_donations_to_campaign(donor_authority, campaign_id),
+        system_program: system_program::ID,
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn initialize(
+    platform_authority: Pubkey,
+    seasonal_top_capacity: u16,
+    reward_cooldown: u32,
+    reward_amount: u64,
+    reward_distribution: RewardDistribution,
+    reward_tiers: Vec<RewardTier>,
+    fee_basis_points: u16,
+    fee_exemption_limit: u64,
+    liquidation_limit: u64,
+) -> Instruction {
+    build(
+        instruction::Initialize {
+            seasonal_top_capacity,
+            reward_cooldown,
+            reward_amount,
+            reward_distribution,
+            reward_tiers,
+            fee_basis_points,
+            fee_exemption_limit,
+            liquidation_limit,
+        },
+        accounts::Initialize {
+            platform: find_platform(),
+            platform_authority,
+            seasonal_top: find_seasonal_top(),
+            fee_vault: find_fee_vault(),
+            sol_vault: find_sol_vault(),
+            chrt_mint: find_chrt_mint(),
+            rent: sysvar::rent::ID,
+            token_program: anchor_spl::token::ID,
+            system_program: system_program::ID,
+        },
+    )
+}
+
+pub fn register_donor(donor_authority: Pubkey) -> Instruction {
+    build(
+        instruction::RegisterDonor {},
+        accounts::RegisterDonor {
+            platform: find_platform(),
+            donor: find_donor(donor_authority),
+            donor_authority,
+            system_program: system_program::ID,
+        },
+    )
+}
+
+/// `code` is lowercased, the only case the program accepts.
+pub fn register_referral_code(donor_authority: Pubkey, code: String) -> Instruction {
+    let code = code.to_ascii_lowercase();
+    build(
+        instruction::RegisterReferralCode { code: code.clone() },
+        accounts::RegisterReferralCode {
+            donor: find_donor(donor_authority),
+            referral_code: find_referral_code(&code),
+            donor_authority,
+            system_program: system_program::ID,
+        },
+    )
+}
+
+/// `campaign_id` must be the current `Platform::campaigns_count`.
+#[allow(clippy::too_many_arguments)]
+pub fn start_campaign(
+    campaign_authority: Pubkey,
+    campaign_id: u16,
+    beneficiary: Pubkey,
+    title: String,
+    uri: String,
+    category: CampaignCategory,
+    goal: Option<u64>,
+    deadline: Option<u32>,
+    milestones: Vec<u16>,
+) -> Instruction {
+    build(
+        instruction::StartCampaign {
+            beneficiary,
+            title,
+            uri,
+            category,
+            goal,
+            deadline,
+            milestones,
+        },
+        accounts::StartCampaign {
+            platform: find_platform(),
+            chrt_mint: find_chrt_mint(),
+            campaign: find_campaign(campaign_id),
+            campaign_authority,
+            total_donations_to_campaign: find_total_donations_to_campaign(campaign_id),
+            fee_exemption_vault: find_fee_exemption_vault(campaign_id),
+            liquidation_vault: find_liquidation_vault(campaign_id),
+            chrt_vaults: find_chrt_vaults(campaign_id),
+            stake_pool: find_stake_pool(campaign_id),
+            rent: sysvar::rent::ID,
+            token_program: anchor_spl::token::ID,
+            system_program: system_program::ID,
+        },
+    )
+}
+
+pub fn donate(donor_authority: Pubkey, campaign_id: u16, amount: u64) -> Instruction {
+    build(
+        instruction::Donate { amount },
+        donate_accounts(donor_authority, campaign_id),
+    )
+}
+
+fn donate_with_referer_accounts(
+    donor_authority: Pubkey,
+    campaign_id: u16,
+    referer_authority: Pubkey,
+) -> accounts::DonateWithReferer {
+    accounts::DonateWithReferer {
+        donate: donate_accounts(donor_authority, campaign_id),
+        chrt_mint: find_chrt_mint(),
+        referer: find_donor(referer_authority),
+        referer_authority,
+        referer_chrt: find_chrt_ata(referer_authority),
+        rent: sysvar::rent::ID,
+        token_program: anchor_spl::token::ID,
+        associated_token_program: spl_associated_token_account::ID,
+        system_program: system_program::ID,
+    }
+}
+
+pub fn donate_with_referer(
+    donor_authority: Pubkey,
+    campaign_id: u16,
+    amount: u64,
+    referer_authority: Pubkey,
+) -> Instruction {
+    build(
+        instruction::DonateWithReferer { amount },
+        donate_with_referer_accounts(donor_authority, campaign_id, referer_authority),
+    )
+}
+
+/// `referer_authority` is the `ReferralCode::donor` registered for `code`, which is lowercased.
+pub fn donate_with_referral_code(
+    donor_authority: Pubkey,
+    campaign_id: u16,
+    amount: u64,
+    code: String,
+    referer_authority: Pubkey,
+) -> Instruction {
+    let code = code.to_ascii_lowercase();
+    build(
+        instruction::DonateWithReferralCode {
+            code: code.clone(),
+            amount,
+        },
+        accounts::DonateWithReferralCode {
+            donate_with_referer: donate_with_referer_accounts(
+                donor_authority,
+                campaign_id,
+                referer_authority,
+            ),
+            referral_code: find_referral_code(&code),
+        },
+    )
+}
+
+fn credit_donations_accounts(
+    donor_authority: Pubkey,
+    campaign_id: u16,
+) -> accounts::CreditDonations {
+    accounts::CreditDonations {
+        platform: find_platform(),
+        total_donations_to_campaign: find_total_donations_to_campaign(campaign_id),
+        donor: find_donor(donor_authority),
+        donor_authority,
+        donor_donations_to_campaign: find_donor_donations_to_campaign(donor_authority, campaign_id),
+    }
+}
+
+pub fn credit_donations(donor_authority: Pubkey, campaign_id: u16) -> Instruction {
+    build(
+        instruction::CreditDonations { campaign_id },
+        credit_donations_accounts(donor_authority, campaign_id),
+    )
+}
+
+pub fn credit_donations_with_referer(
+    donor_authority: Pubkey,
+    campaign_id: u16,
+    referer_authority: Pubkey,
+) -> Instruction {
+    build(
+        instruction::CreditDonationsWithReferer { campaign_id },
+        accounts::CreditDonationsWithReferer {
+            credit: credit_donations_accounts(donor_authority, campaign_id),
+            chrt_mint: find_chrt_mint(),
+            referer: find_donor(referer_authority),
+            referer_authority,
+            referer_chrt: find_chrt_ata(referer_authority),
+            rent: sysvar::rent::ID,
+            token_program: anchor_spl::token::ID,
+            associated_token_program: spl_associated_token_account::ID,
+            system_program: system_program::ID,
+        },
+    )
+}
+
+pub fn stake(staker: Pubkey, campaign_id: u16, amount: u64) -> Instruction {
+    build(
+        instruction::Stake {
+            campaign_id,
+            amount,
+        },
+        accounts::Stake {
+            platform: find_platform(),
+            campaign: find_campaign(campaign_id),
+            stake_pool: find_stake_pool(campaign_id),
+            stake_position: find_stake_position(campaign_id, staker),
+            stake_vault: find_stake_vault(),
+            chrt_mint: find_chrt_mint(),
+            staker,
+            staker_chrt: find_chrt_ata(staker),
+            rent: sysvar::rent::ID,
+            token_program: anchor_spl::token::ID,
+            system_program: system_program::ID,
+        },
+    )
+}
+
+pub fn request_unstake(staker: Pubkey, campaign_id: u16, amount: u64) -> Instruction {
+    build(
+        instruction::RequestUnstake {
+            campaign_id,
+            amount,
+        },
+        accounts::RequestUnstake {
+            platform: find_platform(),
+            stake_pool: find_stake_pool(campaign_id),
+            stake_position: find_stake_position(campaign_id, staker),
+            staker,
+        },
+    )
+}
+
+pub fn unstake(staker: Pubkey, campaign_id: u16) -> Instruction {
+    build(
+        instruction::Unstake { campaign_id },
+        accounts::Unstake {
+            platform: find_platform(),
+            stake_position: find_stake_position(campaign_id, staker),
+            stake_vault: find_stake_vault(),
+            staker,
+            staker_chrt: find_chrt_ata(staker),
+            token_program: anchor_spl::token::ID,
+        },
+    )
+}
+
+pub fn support_campaign(supporter: Pubkey, campaign_id: u16, amount: u64) -> Instruction {
+    build(
+        instruction::SupportCampaign {
+            campaign_id,
+            amount,
+        },
+        accounts::SupportCampaign {
+            campaign: find_campaign(campaign_id),
+            fee_exemption_vault: find_fee_exemption_vault(campaign_id),
+            chrt_deposit: find_chrt_deposit(campaign_id, ChrtVault::FeeExemption, supporter),
+            supporter,
+            supporter_chrt: find_chrt_ata(supporter),
+            token_program: anchor_spl::token::ID,
+            system_program: system_program::ID,
+        },
+    )
+}
+
+pub fn flag_campaign(flagger: Pubkey, campaign_id: u16, amount: u64) -> Instruction {
+    build(
+        instruction::FlagCampaign {
+            campaign_id,
+            amount,
+        },
+        accounts::FlagCampaign {
+            campaign: find_campaign(campaign_id),
+            liquidation_vault: find_liquidation_vault(campaign_id),
+            chrt_deposit: find_chrt_deposit(campaign_id, ChrtVault::Liquidation, flagger),
+            flagger,
+            flagger_chrt: find_chrt_ata(flagger),
+            token_program: anchor_spl::token::ID,
+            system_program: system_program::ID,
+        },
+    )
+}
+
+/// `chrt_vaults_payer` is `ChrtVaults::payer`.
+pub fn withdraw_chrt_deposit(
+    depositor: Pubkey,
+    campaign_id: u16,
+    vault: ChrtVault,
+    chrt_vaults_payer: Pubkey,
+) -> Instruction {
+    build(
+        instruction::WithdrawChrtDeposit { campaign_id, vault },
+        accounts::WithdrawChrtDeposit {
+            platform: find_platform(),
+            chrt_mint: find_chrt_mint(),
+            campaign: find_campaign(campaign_id),
+            chrt_vault: find_chrt_vault(campaign_id, vault),
+            chrt_vaults: find_chrt_vaults(campaign_id),
+            chrt_vaults_payer,
+            chrt_deposit: find_chrt_deposit(campaign_id, vault, depositor),
+            depositor,
+            depositor_chrt: find_chrt_ata(depositor),
+            token_program: anchor_spl::token::ID,
+        },
+    )
+}
+
+/// `donor_authorities` are the wallets whose `Donor` accounts get recorded.
+pub fn record_donors(keeper: Pubkey, donor_authorities: &[Pubkey]) -> Instruction {
+    let mut ix = build(
+        instruction::RecordDonors {},
+        accounts::RecordDonors {
+            platform: find_platform(),
+            seasonal_top: find_seasonal_top(),
+            fee_vault: find_fee_vault(),
+            keeper,
+        },
+    );
+    ix.accounts.extend(
+        donor_authorities
+            .iter()
+            .map(|&d| AccountMeta::new(find_donor(d), false)),
+    );
+    ix
+}
+
+/// `season_id` is the current `Platform::season` and `page` are the seasonal top donors
+/// from `SeasonalTop::cursor` on.
+pub fn drop_rewards(keeper: Pubkey, season_id: u32, page: &[Pubkey]) -> Instruction {
+    let mut ix = build(
+        instruction::DropRewards {},
+        accounts::DropRewards {
+            platform: find_platform(),
+            seasonal_top: find_seasonal_top(),
+            fee_vault: find_fee_vault(),
+            keeper,
+            season: find_season(season_id),
+            chrt_mint: find_chrt_mint(),
+            token_program: anchor_spl::token::ID,
+            system_program: system_program::ID,
+        },
+    );
+    for &donor_authority in page {
+        ix.accounts
+            .push(AccountMeta::new(find_donor(donor_authority), false));
+        ix.accounts
+            .push(AccountMeta::new(find_chrt_ata(donor_authority), false));
+    }
+    ix
+}
+
+/// Commits the rewards of the seasonal top instead of minting them, see `drop_rewards`.
+pub fn commit_rewards(keeper: Pubkey, season_id: u32, page: &[Pubkey]) -> Instruction {
+    let mut ix = build(
+        instruction::CommitRewards {},
+        accounts::CommitRewards {
+            platform: find_platform(),
+            seasonal_top: find_seasonal_top(),
+            fee_vault: find_fee_vault(),
+            keeper,
+            season: find_season(season_id),
+            system_program: system_program::ID,
+        },
+    );
+    for &donor_authority in page {
+        ix.accounts
+            .push(AccountMeta::new(find_donor(donor_authority), false));
+    }
+    ix
+}
+
+pub fn claim_reward(
+    donor_authority: Pubkey,
+    season_id: u32,
+    amount: u64,
+    proof: Vec<[u8; 32]>,
+) -> Instruction {
+    build(
+        instruction::ClaimReward {
+            season_id,
+            amount,
+            proof,
+        },
+        accounts::ClaimReward {
+            platform: find_platform(),
+            season: find_season(season_id),
+            reward_claim: find_reward_claim(season_id, donor_authority),
+            donor_authority,
+            donor_chrt: find_chrt_ata(donor_authority),
+            chrt_mint: find_chrt_mint(),
+            rent: sysvar::rent::ID,
+            token_program: anchor_spl::token::ID,
+            associated_token_program: spl_associated_token_account::ID,
+            system_program: system_program::ID,
+        },
+    )
+}
+
+pub fn resize_seasonal_top(platform_authority: Pubkey, capacity: u16) -> Instruction {
+    build(
+        instruction::ResizeSeasonalTop { capacity },
+        accounts::ResizeSeasonalTop {
+            platform: find_platform(),
+            seasonal_top: find_seasonal_top(),
+            platform_authority,
+            system_program: system_program::ID,
+        },
+    )
+}
+
+pub fn abort_reward_procedure(platform_authority: Pubkey) -> Instruction {
+    build(
+        instruction::AbortRewardProcedure {},
+        accounts::AbortRewardProcedure {
+            platform: find_platform(),
+            seasonal_top: find_seasonal_top(),
+            platform_authority,
+        },
+    )
+}
+
+pub fn withdraw_donations(
+    campaign_authority: Pubkey,
+    campaign_id: u16,
+    beneficiary: Pubkey,
+) -> Instruction {
+    build(
+        instruction::WithdrawDonations {},
+        accounts::WithdrawDonations {
+            platform: find_platform(),
+            sol_vault: find_sol_vault(),
+            campaign: find_campaign(campaign_id),
+            campaign_authority,
+            beneficiary,
+        },
+    )
+}
+
+/// `chrt_vaults_payer` is `ChrtVaults::payer`.
+pub fn stop_campaign(
+    campaign_authority: Pubkey,
+    campaign_id: u16,
+    beneficiary: Pubkey,
+    chrt_vaults_payer: Pubkey,
+) -> Instruction {
+    build(
+        instruction::StopCampaign {},
+        accounts::StopCampaign {
+            platform: find_platform(),
+            sol_vault: find_sol_vault(),
+            chrt_mint: find_chrt_mint(),
+            campaign: find_campaign(campaign_id),
+            campaign_authority,
+            beneficiary,
+            total_donations_to_campaign: find_total_donations_to_campaign(campaign_id),
+            fee_exemption_vault: find_fee_exemption_vault(campaign_id),
+            liquidation_vault: find_liquidation_vault(campaign_id),
+            chrt_vaults: find_chrt_vaults(campaign_id),
+            chrt_vaults_payer,
+            token_program: anchor_spl::token::ID,
+            system_program: system_program::ID,
+        },
+    )
+}
+
+/// `chrt_vaults_payer` is `ChrtVaults::payer`.
+pub fn liquidate_campaign(
+    campaign_id: u16,
+    beneficiary: Pubkey,
+    chrt_vaults_payer: Pubkey,
+) -> Instruction {
+    build(
+        instruction::LiquidateCampaign {},
+        accounts::LiquidateCampaign {
+            platform: find_platform(),
+            fee_vault: find_fee_vault(),
+            sol_vault: find_sol_vault(),
+            chrt_mint: find_chrt_mint(),
+            campaign: find_campaign(campaign_id),
+            beneficiary,
+            fee_exemption_vault: find_fee_exemption_vault(campaign_id),
+            liquidation_vault: find_liquidation_vault(campaign_id),
+            chrt_vaults: find_chrt_vaults(campaign_id),
+            chrt_vaults_payer,
+            token_program: anchor_spl::token::ID,
+            system_program: system_program::ID,
+        },
+    )
+}
+
+pub fn withdraw_fees(platform_authority: Pubkey) -> Instruction {
+    build(
+        instruction::WithdrawFees {},
+        accounts::WithdrawFees {
+            platform: find_platform(),
+            platform_authority,
+            fee_vault: find_fee_vault(),
+        },
+    )
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn update_platform_config(
+    platform_authority: Pubkey,
+    reward_cooldown: Option<u32>,
+    reward_procedure_timeout: Option<u32>,
+    reward_amount: Option<u64>,
+    reward_distribution: Option<RewardDistribution>,
+    reward_tiers: Option<Vec<RewardTier>>,
+    fee_basis_points: Option<u16>,
+    fee_exemption_limit: Option<u64>,
+    liquidation_limit: Option<u64>,
+    record_donor_bounty: Option<u64>,
+    drop_rewards_bounty: Option<u64>,
+    referral_rate: Option<u64>,
+    referral_donation_cap: Option<u64>,
+    referral_season_cap: Option<u64>,
+    unstake_cooldown: Option<u32>,
+) -> Instruction {
+    build(
+        instruction::UpdatePlatformConfig {
+            reward_cooldown,
+            reward_procedure_timeout,
+            reward_amount,
+            reward_distribution,
+            reward_tiers,
+            fee_basis_points,
+            fee_exemption_limit,
+            liquidation_limit,
+            record_donor_bounty,
+            drop_rewards_bounty,
+            referral_rate,
+            referral_donation_cap,
+            referral_season_cap,
+            unstake_cooldown,
+        },
+        accounts::UpdatePlatformConfig {
+            platform: find_platform(),
+            platform_authority,
+        },
+    )
+}
+
+pub fn propose_platform_authority(
+    platform_authority: Pubkey,
+    new_authority: Pubkey,
+) -> Instruction {
+    build(
+        instruction::ProposePlatformAuthority { new_authority },
+        accounts::ProposePlatformAuthority {
+            platform: find_platform(),
+            platform_authority,
+        },
+    )
+}
+
+pub fn accept_platform_authority(new_authority: Pubkey) -> Instruction {
+    build(
+        instruction::AcceptPlatformAuthority {},
+        accounts::AcceptPlatformAuthority {
+            platform: find_platform(),
+            new_authority,
+        },
+    )
+}
+
+pub fn cancel_platform_authority(platform_authority: Pubkey) -> Instruction {
+    build(
+        instruction::CancelPlatformAuthority {},
+        accounts::CancelPlatformAuthority {
+            platform: find_platform(),
+            platform_authority,
+        },
+    )
+}
+
+pub fn propose_campaign_authority(
+    campaign_authority: Pubkey,
+    campaign_id: u16,
+    new_authority: Pubkey,
+) -> Instruction {
+    build(
+        instruction::ProposeCampaignAuthority { new_authority },
+        accounts::ProposeCampaignAuthority {
+            campaign: find_campaign(campaign_id),
+            campaign_authority,
+        },
+    )
+}
+
+pub fn accept_campaign_authority(new_authority: Pubkey, campaign_id: u16) -> Instruction {
+    build(
+        instruction::AcceptCampaignAuthority {},
+        accounts::AcceptCampaignAuthority {
+            campaign: find_campaign(campaign_id),
+            new_authority,
+        },
+    )
+}
+
+pub fn cancel_campaign_authority(campaign_authority: Pubkey, campaign_id: u16) -> Instruction {
+    build(
+        instruction::CancelCampaignAuthority {},
+        accounts::CancelCampaignAuthority {
+            campaign: find_campaign(campaign_id),
+            campaign_authority,
+        },
+    )
+}
+
+pub fn update_campaign_metadata(
+    campaign_authority: Pubkey,
+    campaign_id: u16,
+    title: Option<String>,
+    uri: Option<String>,
+    category: Option<CampaignCategory>,
+) -> Instruction {
+    build(
+        instruction::UpdateCampaignMetadata {
+            title,
+            uri,
+            category,
+        },
+        accounts::UpdateCampaignMetadata {
+            campaign: find_campaign(campaign_id),
+            campaign_authority,
+        },
+    )
+}
+
+pub fn refund_donations(donor_authority: Pubkey, campaign_id: u16) -> Instruction {
+    build(
+        instruction::RefundDonations { campaign_id },
+        accounts::RefundDonations {
+            platform: find_platform(),
+            sol_vault: find_sol_vault(),
+            total_donations_to_campaign: find_total_donations_to_campaign(campaign_id),
+            donor_authority,
+            donor_donations_to_campaign: find_donor_donations_to_campaign(
+                donor_authority,
+                campaign_id,
+            ),
+        },
+    )
+}
+
+/// `chrt_vaults_payer` is `ChrtVaults::payer`.
+pub fn expire_campaign(
+    campaign_id: u16,
+    beneficiary: Pubkey,
+    chrt_vaults_payer: Pubkey,
+) -> Instruction {
+    build(
+        instruction::ExpireCampaign {},
+        accounts::ExpireCampaign {
+            platform: find_platform(),
+            sol_vault: find_sol_vault(),
+            chrt_mint: find_chrt_mint(),
+            campaign: find_campaign(campaign_id),
+            beneficiary,
+            total_donations_to_campaign: find_total_donations_to_campaign(campaign_id),
+            fee_exemption_vault: find_fee_exemption_vault(campaign_id),
+            liquidation_vault: find_liquidation_vault(campaign_id),
+            chrt_vaults: find_chrt_vaults(campaign_id),
+            chrt_vaults_payer,
+            token_program: anchor_spl::token::ID,
+            system_program: system_program::ID,
+        },
+    )
+}
+
+/// `milestone` must be the current `Campaign::milestones_unlocked`.
+pub fn vote_milestone(donor_authority: Pubkey, campaign_id: u16, milestone: u8) -> Instruction {
+    build(
+        instruction::VoteMilestone {},
+        accounts::VoteMilestone {
+            campaign: find_campaign(campaign_id),
+            total_donations_to_campaign: find_total_donations_to_campaign(campaign_id),
+            donor_authority,
+            donor_donations_to_campaign: find_donor_donations_to_campaign(
+                donor_authority,
+                campaign_id,
+            ),
+            milestone_vote: find_milestone_vote(donor_authority, campaign_id, milestone),
+            system_program: system_program::ID,
+        },
+    )
+}
+
+pub fn open_token_vault(campaign_authority: Pubkey, campaign_id: u16, mint: Pubkey) -> Instruction {
+    build(
+        instruction::OpenTokenVault {},
+        accounts::OpenTokenVault {
+            platform: find_platform(),
+            campaign: find_campaign(campaign_id),
+            campaign_authority,
+            mint,
+            token_vault: find_token_vault(campaign_id, mint),
+            token_donations: find_token_donations(campaign_id, mint),
+            rent: sysvar::rent::ID,
+            token_program: anchor_spl::token::ID,
+            system_program: system_program::ID,
+        },
+    )
+}
+
+pub fn donate_token(
+    donor_authority: Pubkey,
+    campaign_id: u16,
+    mint: Pubkey,
+    amount: u64,
+) -> Instruction {
+    build(
+        instruction::DonateToken { amount },
+        accounts::DonateToken {
+            platform: find_platform(),
+            campaign: find_campaign(campaign_id),
+            stake_pool: find_stake_pool(campaign_id),
+            mint,
+            token_vault: find_token_vault(campaign_id, mint),
+            token_donations: find_token_donations(campaign_id, mint),
+            token_fee_vault: find_token_fee_vault(mint),
+            donor_authority,
+            donor_token_account: get_associated_token_address(&donor_authority, &mint),
+            donor_token_donations: find_donor_token_donations(donor_authority, campaign_id, mint),
+            rent: sysvar::rent::ID,
+            token_program: anchor_spl::token::ID,
+            system_program: system_program::ID,
+        },
+    )
+}
+
+pub fn withdraw_token_donations(
+    campaign_authority: Pubkey,
+    campaign_id: u16,
+    mint: Pubkey,
+    beneficiary: Pubkey,
+) -> Instruction {
+    build(
+        instruction::WithdrawTokenDonations {},
+        accounts::WithdrawTokenDonations {
+            platform: find_platform(),
+            campaign: find_campaign(campaign_id),
+            campaign_authority,
+            mint,
+            token_vault: find_token_vault(campaign_id, mint),
+            token_donations: find_token_donations(campaign_id, mint),
+            beneficiary_token_account: get_associated_token_address(&beneficiary, &mint),
+            token_program: anchor_spl::token::ID,
+        },
+    )
+}
+
+pub fn close_token_vault(
+    authority: Pubkey,
+    campaign_id: u16,
+    mint: Pubkey,
+    beneficiary: Pubkey,
+) -> Instruction {
+    build(
+        instruction::CloseTokenVault {},
+        accounts::CloseTokenVault {
+            platform: find_platform(),
+            campaign: find_campaign(campaign_id),
+            authority,
+            beneficiary,
+            mint,
+            token_vault: find_token_vault(campaign_id, mint),
+            token_donations: find_token_donations(campaign_id, mint),
+            beneficiary_token_account: get_associated_token_address(&beneficiary, &mint),
+            token_program: anchor_spl::token::ID,
+        },
+    )
+}
+
+/// `beneficiary` must be the `TokenDonations::beneficiary` recorded when the vault was closed.
+pub fn refund_token_donations(
+    donor_authority: Pubkey,
+    campaign_id: u16,
+    mint: Pubkey,
+    beneficiary: Pubkey,
+) -> Instruction {
+    build(
+        instruction::RefundTokenDonations { campaign_id },
+        accounts::RefundTokenDonations {
+            platform: find_platform(),
+            mint,
+            token_vault: find_token_vault(campaign_id, mint),
+            token_donations: find_token_donations(campaign_id, mint),
+            beneficiary,
+            donor_authority,
+            donor_token_donations: find_donor_token_donations(donor_authority, campaign_id, mint),
+            donor_token_account: get_associated_token_address(&donor_authority, &mint),
+            token_program: anchor_spl::token::ID,
+        },
+    )
+}
+
+pub fn liquidate_token_vault(campaign_id: u16, mint: Pubkey, beneficiary: Pubkey) -> Instruction {
+    build(
+        instruction::LiquidateTokenVault {},
+        accounts::LiquidateTokenVault {
+            platform: find_platform(),
+            campaign: find_campaign(campaign_id),
+            beneficiary,
+            mint,
+            token_vault: find_token_vault(campaign_id, mint),
+            token_donations: find_token_donations(campaign_id, mint),
+            token_fee_vault: find_token_fee_vault(mint),
+            token_program: anchor_spl::token::ID,
+        },
+    )
+}
+
+pub fn withdraw_token_fees(platform_authority: Pubkey, mint: Pubkey) -> Instruction {
+    build(
+        instruction::WithdrawTokenFees {},
+        accounts::WithdrawTokenFees {
+            platform: find_platform(),
+            platform_authority,
+            mint,
+            token_fee_vault: find_token_fee_vault(mint),
+            platform_authority_token_account: get_associated_token_address(
+                &platform_authority,
+                &mint,
+            ),
+            token_program: anchor_spl::token::ID,
+        },
+    )
+}
@@ -0,0 +1,30 @@
+pub use crowdfunding;
+
+pub mod accounts;
+pub mod instructions;
+pub mod pda;
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error(transparent)]
+    Rpc(Box<solana_client::client_error::ClientError>),
+    #[error(transparent)]
+    Anchor(Box<anchor_lang::error::Error>),
+    /// Error of an `accounts::AccountSource` other than `RpcClient`.
+    #[error(transparent)]
+    Source(Box<dyn std::error::Error + Send + Sync>),
+}
+
+impl From<solana_client::client_error::ClientError> for Error {
+    fn from(e: solana_client::client_error::ClientError) -> Self {
+        Error::Rpc(Box::new(e))
+    }
+}
+
+impl From<anchor_lang::error::Error> for Error {
+    fn from(e: anchor_lang::error::Error) -> Self {
+        Error::Anchor(Box::new(e))
+    }
+}
+
+pub type Result<T> = core::result::Result<T, Error>;
@@ -0,0 +1,141 @@
+use anchor_lang::prelude::Pubkey;
+use crowdfunding::state::ChrtVault;
+
+fn find_pda(seeds: &[&[u8]]) -> Pubkey {
+    Pubkey::find_program_address(seeds, &crowdfunding::ID).0
+}
+
+pub fn find_platform() -> Pubkey {
+    find_pda(&[b"platform"])
+}
+
+pub fn find_fee_vault() -> Pubkey {
+    find_pda(&[b"fee_vault"])
+}
+
+pub fn find_sol_vault() -> Pubkey {
+    find_pda(&[b"sol_vault"])
+}
+
+pub fn find_chrt_mint() -> Pubkey {
+    find_pda(&[b"chrt_mint"])
+}
+
+pub fn find_donor(donor_authority: Pubkey) -> Pubkey {
+    find_pda(&[b"donor", &donor_authority.to_bytes()])
+}
+
+/// `code` is lowercased like the instruction builders do.
+pub fn find_referral_code(code: &str) -> Pubkey {
+    find_pda(&[b"referral_code", code.to_ascii_lowercase().as_bytes()])
+}
+
+pub fn find_seasonal_top() -> Pubkey {
+    find_pda(&[b"seasonal_top"])
+}
+
+pub fn find_season(season_id: u32) -> Pubkey {
+    find_pda(&[b"season", &season_id.to_le_bytes()])
+}
+
+pub fn find_reward_claim(season_id: u32, donor_authority: Pubkey) -> Pubkey {
+    find_pda(&[
+        b"reward_claim",
+        &season_id.to_le_bytes(),
+        &donor_authority.to_bytes(),
+    ])
+}
+
+pub fn find_campaign(campaign_id: u16) -> Pubkey {
+    find_pda(&[b"campaign", &campaign_id.to_le_bytes()])
+}
+
+pub fn find_total_donations_to_campaign(campaign_id: u16) -> Pubkey {
+    find_pda(&[b"donations", &campaign_id.to_le_bytes()])
+}
+
+pub fn find_donor_donations_to_campaign(donor_authority: Pubkey, campaign_id: u16) -> Pubkey {
+    find_pda(&[
+        b"donations",
+        &donor_authority.to_bytes(),
+        &campaign_id.to_le_bytes(),
+    ])
+}
+
+pub fn find_fee_exemption_vault(campaign_id: u16) -> Pubkey {
+    find_pda(&[b"fee_exemption_vault", &campaign_id.to_le_bytes()])
+}
+
+pub fn find_liquidation_vault(campaign_id: u16) -> Pubkey {
+    find_pda(&[b"liquidation_vault", &campaign_id.to_le_bytes()])
+}
+
+pub fn find_chrt_vault(campaign_id: u16, vault: ChrtVault) -> Pubkey {
+    find_pda(&[vault.seed(), &campaign_id.to_le_bytes()])
+}
+
+pub fn find_chrt_vaults(campaign_id: u16) -> Pubkey {
+    find_pda(&[b"chrt_vaults", &campaign_id.to_le_bytes()])
+}
+
+pub fn find_chrt_deposit(campaign_id: u16, vault: ChrtVault, depositor: Pubkey) -> Pubkey {
+    find_pda(&[
+        vault.deposit_seed(),
+        &campaign_id.to_le_bytes(),
+        &depositor.to_bytes(),
+    ])
+}
+
+pub fn find_stake_pool(campaign_id: u16) -> Pubkey {
+    find_pda(&[b"stake_pool", &campaign_id.to_le_bytes()])
+}
+
+pub fn find_stake_position(campaign_id: u16, staker: Pubkey) -> Pubkey {
+    find_pda(&[b"stake", &campaign_id.to_le_bytes(), &staker.to_bytes()])
+}
+
+pub fn find_stake_vault() -> Pubkey {
+    find_pda(&[b"stake_vault"])
+}
+
+pub fn find_milestone_vote(donor_authority: Pubkey, campaign_id: u16, milestone: u8) -> Pubkey {
+    find_pda(&[
+        b"milestone_vote",
+        &donor_authority.to_bytes(),
+        &campaign_id.to_le_bytes(),
+        &[milestone],
+    ])
+}
+
+pub fn find_token_vault(campaign_id: u16, mint: Pubkey) -> Pubkey {
+    find_pda(&[b"token_vault", &campaign_id.to_le_bytes(), &mint.to_bytes()])
+}
+
+pub fn find_token_donations(campaign_id: u16, mint: Pubkey) -> Pubkey {
+    find_pda(&[
+        b"token_donations",
+        &campaign_id.to_le_bytes(),
+        &mint.to_bytes(),
+    ])
+}
+
+pub fn find_donor_token_donations(
+    donor_authority: Pubkey,
+    campaign_id: u16,
+    mint: Pubkey,
+) -> Pubkey {
+    find_pda(&[
+        b"token_donations",
+        &donor_authority.to_bytes(),
+        &campaign_id.to_le_bytes(),
+        &mint.to_bytes(),
+    ])
+}
+
+pub fn find_token_fee_vault(mint: Pubkey) -> Pubkey {
+    find_pda(&[b"token_fee_vault", &mint.to_bytes()])
+}
+
+pub fn find_chrt_ata(wallet_address: Pubkey) -> Pubkey {
+    spl_associated_token_account::get_associated_token_address(&wallet_address, &find_chrt_mint())
+}
@@ -0,0 +1,103 @@
+use anchor_lang::{prelude::Pubkey, Discriminator};
+use bytemuck::Zeroable;
+use crowdfunding_client::{accounts::*, crowdfunding::state::*, pda::*};
+use spl_associated_token_account::get_associated_token_address;
+
+fn account_data<T: Discriminator + bytemuck::Pod>(account: &T) -> Vec<u8> {
+    let mut data = T::discriminator().to_vec();
+    data.extend_from_slice(bytemuck::bytes_of(account));
+    data
+}
+
+#[test]
+fn test_pda_derivations() {
+    assert_eq!(
+        find_platform(),
+        Pubkey::find_program_address(&[b"platform"], &crowdfunding_client::crowdfunding::ID).0
+    );
+    assert_ne!(find_campaign(0), find_campaign(1));
+    assert_ne!(
+        find_total_donations_to_campaign(0),
+        find_donor_donations_to_campaign(Pubkey::default(), 0)
+    );
+    assert_ne!(find_season(1), find_season(2));
+    assert_eq!(find_referral_code("Alice"), find_referral_code("alice"));
+
+    let donor_authority = Pubkey::new_unique();
+    assert_ne!(
+        find_reward_claim(1, donor_authority),
+        find_reward_claim(2, donor_authority)
+    );
+    assert_ne!(
+        find_milestone_vote(donor_authority, 0, 0),
+        find_milestone_vote(donor_authority, 0, 1)
+    );
+    assert_eq!(
+        find_chrt_ata(donor_authority),
+        get_associated_token_address(&donor_authority, &find_chrt_mint())
+    );
+}
+
+#[test]
+fn test_chrt_vault_derivations() {
+    assert_eq!(
+        find_chrt_vault(3, ChrtVault::FeeExemption),
+        find_fee_exemption_vault(3)
+    );
+    assert_eq!(
+        find_chrt_vault(3, ChrtVault::Liquidation),
+        find_liquidation_vault(3)
+    );
+
+    let depositor = Pubkey::new_unique();
+    assert_ne!(
+        find_chrt_deposit(3, ChrtVault::FeeExemption, depositor),
+        find_chrt_deposit(3, ChrtVault::Liquidation, depositor)
+    );
+}
+
+#[test]
+fn test_decode() {
+    let mut donor = Donor::zeroed();
+    donor.authority = Pubkey::new_unique();
+    donor.donations_sum = 1000;
+
+    let decoded: Donor = decode(&account_data(&donor)).unwrap();
+    assert_eq!(decoded.authority, donor.authority);
+    assert_eq!({ decoded.donations_sum }, 1000);
+
+    assert!(decode::<Campaign>(&account_data(&donor)).is_err());
+    assert!(decode::<Donor>(&[]).is_err());
+}
+
+#[test]
+fn test_decode_with_records() {
+    let mut seasonal_top = SeasonalTop::zeroed();
+    seasonal_top.capacity = 2;
+    seasonal_top.cursor = 1;
+    let records = [
+        SeasonalTopRecord {
+            donor: Pubkey::new_unique(),
+            donations_sum: 200,
+            unrewarded_sum: 200,
+        },
+        SeasonalTopRecord {
+            donor: Pubkey::new_unique(),
+            donations_sum: 100,
+            unrewarded_sum: 100,
+        },
+    ];
+
+    let mut data = account_data(&seasonal_top);
+    data.extend_from_slice(bytemuck::cast_slice(&records));
+    let (decoded, decoded_records) =
+        decode_with_records::<SeasonalTop, SeasonalTopRecord>(&data).unwrap();
+    assert_eq!({ decoded.cursor }, 1);
+    assert_eq!(decoded_records.len(), 2);
+    assert_eq!(decoded_records[1].donor, records[1].donor);
+
+    let (_, decoded_records) =
+        decode_with_records::<SeasonalTop, SeasonalTopRecord>(&account_data(&seasonal_top))
+            .unwrap();
+    assert!(decoded_records.is_empty());
+}
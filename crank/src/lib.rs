@@ -0,0 +1,258 @@
+use anchor_lang::prelude::Pubkey;
+use anyhow::{anyhow, Result};
+use crowdfunding_client::{
+    accounts::{fetch_all_donors, fetch_platform, fetch_seasonal_top},
+    crowdfunding::{merkle::*, state::*},
+    instructions as ix,
+    pda::{find_chrt_ata, find_chrt_mint},
+};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::{
+    instruction::Instruction,
+    signature::{Keypair, Signature},
+    signer::Signer,
+    transaction::Transaction,
+};
+use spl_associated_token_account::instruction::create_associated_token_account;
+
+/// Donors per `record_donors` transaction, keeping it below the packet size limit.
+pub const DEFAULT_BATCH_SIZE: usize = 20;
+/// Associated token accounts created per transaction.
+pub const ATAS_PER_TRANSACTION: usize = 8;
+/// Winners per `drop_rewards` transaction, each passing a donor and a CHRT account.
+pub const DROP_REWARDS_PAGE_SIZE: usize = 10;
+/// Winners per `commit_rewards` transaction.
+pub const COMMIT_REWARDS_PAGE_SIZE: usize = 20;
+
+/// Authorities of the donors that are not recorded in the current reward procedure yet.
+pub fn pending_donors(platform: &Platform, donors: &[Donor]) -> Vec<Pubkey> {
+    donors
+        .iter()
+        .filter(|d| {
+            !platform.reward_procedure_is_in_process
+                || d.last_record_ts < platform.last_reward_procedure_ts
+        })
+        .map(|d| d.authority)
+        .collect()
+}
+
+pub fn record_donors_instructions(
+    keeper: Pubkey,
+    pending: &[Pubkey],
+    batch_size: usize,
+) -> Vec<Instruction> {
+    pending
+        .chunks(batch_size)
+        .map(|batch| ix::record_donors(keeper, batch))
+        .collect()
+}
+
+/// Seasonal top donors not rewarded yet, split into pages of `page_size`.
+///
+/// An empty seasonal top still takes one empty page to end the reward procedure.
+pub fn seasonal_top_pages(
+    seasonal_top: &SeasonalTop,
+    records: &[SeasonalTopRecord],
+    page_size: usize,
+) -> Vec<Vec<Pubkey>> {
+    let donors: Vec<_> = records[..top_len(records)]
+        .iter()
+        .skip(seasonal_top.cursor as usize)
+        .map(|d| d.donor)
+        .collect();
+    if donors.is_empty() {
+        return vec![vec![]];
+    }
+    donors.chunks(page_size).map(<[_]>::to_vec).collect()
+}
+
+/// Rewards the ended reward procedure gives to the seasonal top, mirroring the program.
+pub fn seasonal_rewards(platform: &Platform, records: &[SeasonalTopRecord]) -> Vec<(Pubkey, u64)> {
+    let seasonal_top = &records[..top_len(records)];
+    let unrewarded_sums: Vec<_> = seasonal_top.iter().map(|d| d.unrewarded_sum).collect();
+    seasonal_top
+        .iter()
+        .zip(platform.calculate_rewards(&unrewarded_sums))
+        .filter(|&(_, amount)| amount != 0)
+        .map(|(record, amount)| (record.donor, amount))
+        .collect()
+}
+
+/// Winners of a page with a reward, the only ones `drop_rewards` mints CHRT to.
+pub fn rewarded_winners(page: &[Pubkey], rewards: &[(Pubkey, u64)]) -> Vec<Pubkey> {
+    page.iter()
+        .copied()
+        .filter(|winner| rewards.iter().any(|(donor, _)| donor == winner))
+        .collect()
+}
+
+/// Merkle proofs of the `seasonal_rewards` committed by `commit_rewards`.
+pub fn reward_proofs(rewards: &[(Pubkey, u64)]) -> Vec<Vec<Hash>> {
+    let leaves: Vec<_> = rewards
+        .iter()
+        .map(|(donor, amount)| reward_leaf(donor, *amount))
+        .collect();
+    (0..leaves.len())
+        .map(|i| merkle_proof(&leaves, i))
+        .collect()
+}
+
+/// A reward committed for a donor to claim with `claim_reward`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommittedReward {
+    pub season: u32,
+    pub donor: Pubkey,
+    pub amount: u64,
+    pub proof: Vec<Hash>,
+}
+
+/// The `seasonal_rewards` of the ended reward procedure with their Merkle proofs.
+pub fn committed_rewards(
+    platform: &Platform,
+    records: &[SeasonalTopRecord],
+) -> Vec<CommittedReward> {
+    let rewards = seasonal_rewards(platform, records);
+    rewards
+        .iter()
+        .zip(reward_proofs(&rewards))
+        .map(|(&(donor, amount), proof)| CommittedReward {
+            season: platform.season,
+            donor,
+            amount,
+            proof,
+        })
+        .collect()
+}
+
+/// Transactions sent by a reward procedure run, named by the instruction they carry.
+#[derive(Debug, Default)]
+pub struct Report {
+    pub transactions: Vec<(&'static str, Signature)>,
+    /// Rewards for donors to claim, empty when the rewards are minted instead.
+    pub rewards: Vec<CommittedReward>,
+}
+
+pub struct Crank {
+    pub client: RpcClient,
+    pub payer: Keypair,
+    pub batch_size: usize,
+    /// Commits a Merkle root for donors to claim their rewards instead of minting them.
+    pub commit_rewards: bool,
+}
+
+impl Crank {
+    async fn send(&self, instructions: &[Instruction]) -> Result<Signature> {
+        let blockhash = self.client.get_latest_blockhash().await?;
+        let transaction = Transaction::new_signed_with_payer(
+            instructions,
+            Some(&self.payer.pubkey()),
+            &[&self.payer],
+            blockhash,
+        );
+        Ok(self
+            .client
+            .send_and_confirm_transaction(&transaction)
+            .await?)
+    }
+
+    /// Records every donor, returning once `donors_recorded == donors_count`.
+    pub async fn record_donors(&self, report: &mut Report) -> Result<()> {
+        loop {
+            let platform = fetch_platform(&self.client).await?;
+            if platform.reward_procedure_is_in_process
+                && platform.donors_recorded == platform.donors_count
+            {
+                return Ok(());
+            }
+
+            let donors = fetch_all_donors(&self.client).await?;
+            let pending = pending_donors(&platform, &donors);
+            if pending.is_empty() {
+                return Err(anyhow!(
+                    "{} of {} donors recorded, but no unrecorded donor found",
+                    { platform.donors_recorded },
+                    { platform.donors_count }
+                ));
+            }
+            for instruction in
+                record_donors_instructions(self.payer.pubkey(), &pending, self.batch_size)
+            {
+                let signature = self.send(&[instruction]).await?;
+                report.transactions.push(("record_donors", signature));
+            }
+        }
+    }
+
+    /// Creates the CHRT token accounts that `drop_rewards` mints to but are missing.
+    pub async fn create_missing_chrt_atas(
+        &self,
+        owners: &[Pubkey],
+        report: &mut Report,
+    ) -> Result<()> {
+        let atas: Vec<_> = owners.iter().map(|&o| find_chrt_ata(o)).collect();
+        let accounts = self.client.get_multiple_accounts(&atas).await?;
+        let instructions: Vec<_> = owners
+            .iter()
+            .zip(accounts)
+            .filter(|(_, account)| account.is_none())
+            .map(|(owner, _)| {
+                create_associated_token_account(&self.payer.pubkey(), owner, &find_chrt_mint())
+            })
+            .collect();
+        for chunk in instructions.chunks(ATAS_PER_TRANSACTION) {
+            let signature = self.send(chunk).await?;
+            report
+                .transactions
+                .push(("create CHRT accounts", signature));
+        }
+        Ok(())
+    }
+
+    /// Mints the rewards page by page, continuing an interrupted distribution.
+    pub async fn drop_rewards(&self, report: &mut Report) -> Result<()> {
+        let platform = fetch_platform(&self.client).await?;
+        let (seasonal_top, records) = fetch_seasonal_top(&self.client).await?;
+        let rewards = seasonal_rewards(&platform, &records);
+        for page in seasonal_top_pages(&seasonal_top, &records, DROP_REWARDS_PAGE_SIZE) {
+            self.create_missing_chrt_atas(&rewarded_winners(&page, &rewards), report)
+                .await?;
+            let signature = self
+                .send(&[ix::drop_rewards(
+                    self.payer.pubkey(),
+                    platform.season,
+                    &page,
+                )])
+                .await?;
+            report.transactions.push(("drop_rewards", signature));
+        }
+        Ok(())
+    }
+
+    /// Reports the amount and proof every rewarded donor needs for `claim_reward`.
+    pub async fn commit_rewards(&self, report: &mut Report) -> Result<()> {
+        let platform = fetch_platform(&self.client).await?;
+        let (seasonal_top, records) = fetch_seasonal_top(&self.client).await?;
+        report.rewards = committed_rewards(&platform, &records);
+        for page in seasonal_top_pages(&seasonal_top, &records, COMMIT_REWARDS_PAGE_SIZE) {
+            let signature = self
+                .send(&[ix::commit_rewards(
+                    self.payer.pubkey(),
+                    platform.season,
+                    &page,
+                )])
+                .await?;
+            report.transactions.push(("commit_rewards", signature));
+        }
+        Ok(())
+    }
+
+    /// Runs a whole reward procedure. A failed run still reports what it sent before failing.
+    pub async fn run(&self, report: &mut Report) -> Result<()> {
+        self.record_donors(report).await?;
+        if self.commit_rewards {
+            self.commit_rewards(report).await
+        } else {
+            self.drop_rewards(report).await
+        }
+    }
+}
@@ -0,0 +1,61 @@
+use anyhow::{anyhow, Result};
+use clap::Parser;
+use crowdfunding_crank::{Crank, Report, DEFAULT_BATCH_SIZE};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::{commitment_config::CommitmentConfig, signature::read_keypair_file};
+
+#[derive(Parser, Debug)]
+#[clap(version, about)]
+struct Args {
+    /// RPC URL of the cluster
+    #[clap(long, short, default_value = "http://127.0.0.1:8899")]
+    url: String,
+    /// Keypair paying for the crank transactions
+    #[clap(long, short)]
+    keypair: Option<String>,
+    /// Donors recorded per transaction
+    #[clap(long, default_value_t = DEFAULT_BATCH_SIZE)]
+    batch_size: usize,
+    /// Commit a Merkle root of the rewards for donors to claim instead of minting them
+    #[clap(long)]
+    commit_rewards: bool,
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let args = Args::parse();
+    let keypair_path = match args.keypair {
+        Some(path) => path,
+        None => format!("{}/.config/solana/id.json", std::env::var("HOME")?),
+    };
+    let payer = read_keypair_file(&keypair_path)
+        .map_err(|e| anyhow!("failed to read keypair {}: {}", keypair_path, e))?;
+
+    let crank = Crank {
+        client: RpcClient::new_with_commitment(args.url, CommitmentConfig::confirmed()),
+        payer,
+        batch_size: args.batch_size,
+        commit_rewards: args.commit_rewards,
+    };
+    let mut report = Report::default();
+    let result = crank.run(&mut report).await;
+
+    for (instruction, signature) in &report.transactions {
+        println!("{}: {}", instruction, signature);
+    }
+    for reward in &report.rewards {
+        let proof: Vec<_> = reward
+            .proof
+            .iter()
+            .map(|hash| solana_sdk::bs58::encode(hash).into_string())
+            .collect();
+        println!(
+            "season {} reward {} {} proof {}",
+            reward.season,
+            reward.donor,
+            reward.amount,
+            proof.join(",")
+        );
+    }
+    result
+}
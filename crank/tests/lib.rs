@@ -0,0 +1,174 @@
+use anchor_lang::prelude::Pubkey;
+use bytemuck::Zeroable;
+use crowdfunding_client::{
+    crowdfunding::{merkle::*, state::*},
+    pda::find_donor,
+};
+use crowdfunding_crank::*;
+
+fn donor(last_record_ts: u32) -> Donor {
+    Donor {
+        authority: Pubkey::new_unique(),
+        last_record_ts,
+        ..Zeroable::zeroed()
+    }
+}
+
+#[test]
+fn test_pending_donors() {
+    let donors = [donor(0), donor(100), donor(200)];
+    let mut platform = Platform::zeroed();
+    platform.last_reward_procedure_ts = 200;
+
+    assert_eq!(pending_donors(&platform, &donors).len(), 3);
+
+    platform.reward_procedure_is_in_process = true;
+    assert_eq!(
+        pending_donors(&platform, &donors),
+        [donors[0].authority, donors[1].authority]
+    );
+}
+
+#[test]
+fn test_record_donors_instructions() {
+    let pending: Vec<_> = (0..45).map(|_| Pubkey::new_unique()).collect();
+    let instructions = record_donors_instructions(Pubkey::new_unique(), &pending, 20);
+
+    assert_eq!(
+        instructions
+            .iter()
+            .map(|ix| ix.accounts.len())
+            .collect::<Vec<_>>(),
+        [24, 24, 9]
+    );
+    assert_eq!(instructions[2].accounts[8].pubkey, find_donor(pending[44]));
+}
+
+#[test]
+fn test_seasonal_top_pages() {
+    let mut records = vec![SeasonalTopRecord::default(); 10];
+    for record in &mut records[..7] {
+        record.donor = Pubkey::new_unique();
+        record.donations_sum = 100;
+    }
+    let mut seasonal_top = SeasonalTop::zeroed();
+    seasonal_top.capacity = 10;
+
+    let pages = seasonal_top_pages(&seasonal_top, &records, 3);
+    assert_eq!(pages.iter().map(Vec::len).collect::<Vec<_>>(), [3, 3, 1]);
+    assert_eq!(pages[2][0], records[6].donor);
+
+    seasonal_top.cursor = 6;
+    assert_eq!(
+        seasonal_top_pages(&seasonal_top, &records, 3),
+        [vec![records[6].donor]]
+    );
+
+    let empty = vec![SeasonalTopRecord::default(); 10];
+    seasonal_top.cursor = 0;
+    assert_eq!(
+        seasonal_top_pages(&seasonal_top, &empty, 3),
+        [Vec::<Pubkey>::new()]
+    );
+}
+
+#[test]
+fn test_seasonal_rewards() {
+    let mut records = vec![SeasonalTopRecord::default(); 5];
+    for (record, (donations_sum, unrewarded_sum)) in
+        records.iter_mut().zip([(300, 300), (200, 100), (100, 100)])
+    {
+        record.donor = Pubkey::new_unique();
+        record.donations_sum = donations_sum;
+        record.unrewarded_sum = unrewarded_sum;
+    }
+    let mut platform = Platform::zeroed();
+    platform.reward_distribution = RewardDistribution::Proportional as u8;
+    platform.reward_amount = 1000;
+
+    let rewards = seasonal_rewards(&platform, &records);
+    assert_eq!(
+        rewards,
+        [
+            (records[0].donor, 600),
+            (records[1].donor, 200),
+            (records[2].donor, 200)
+        ]
+    );
+
+    let leaves: Vec<_> = rewards
+        .iter()
+        .map(|(donor, amount)| reward_leaf(donor, *amount))
+        .collect();
+    let root = merkle_root(&leaves);
+    for (i, proof) in reward_proofs(&rewards).iter().enumerate() {
+        assert!(verify_proof(proof, &root, leaves[i]));
+        assert!(!verify_proof(proof, &root, reward_leaf(&rewards[i].0, 1)));
+    }
+}
+
+#[test]
+fn test_rewarded_winners() {
+    let mut records = vec![SeasonalTopRecord::default(); 3];
+    for (record, unrewarded_sum) in records.iter_mut().zip([300, 0, 100]) {
+        record.donor = Pubkey::new_unique();
+        record.donations_sum = 300;
+        record.unrewarded_sum = unrewarded_sum;
+    }
+    let mut platform = Platform::zeroed();
+    platform.reward_distribution = RewardDistribution::Proportional as u8;
+    platform.reward_amount = 1000;
+
+    let rewards = seasonal_rewards(&platform, &records);
+    let page: Vec<_> = records.iter().map(|d| d.donor).collect();
+    assert_eq!(
+        rewarded_winners(&page, &rewards),
+        [records[0].donor, records[2].donor]
+    );
+}
+
+#[test]
+fn test_seasonal_rewards_without_tiers() {
+    let mut records = vec![SeasonalTopRecord::default(); 3];
+    for (record, unrewarded_sum) in records.iter_mut().zip([300, 100]) {
+        record.donor = Pubkey::new_unique();
+        record.donations_sum = unrewarded_sum;
+        record.unrewarded_sum = unrewarded_sum;
+    }
+    let mut platform = Platform::zeroed();
+    platform.reward_distribution = RewardDistribution::Tiers as u8;
+    platform.reward_amount = 1000;
+
+    assert_eq!(
+        seasonal_rewards(&platform, &records),
+        [(records[0].donor, 1000), (records[1].donor, 1000)]
+    );
+}
+
+#[test]
+fn test_committed_rewards() {
+    let mut records = vec![SeasonalTopRecord::default(); 3];
+    for (record, unrewarded_sum) in records.iter_mut().zip([300, 100]) {
+        record.donor = Pubkey::new_unique();
+        record.donations_sum = unrewarded_sum;
+        record.unrewarded_sum = unrewarded_sum;
+    }
+    let mut platform = Platform::zeroed();
+    platform.season = 4;
+    platform.reward_distribution = RewardDistribution::Proportional as u8;
+    platform.reward_amount = 1000;
+
+    let rewards = committed_rewards(&platform, &records);
+    let seasonal_rewards = seasonal_rewards(&platform, &records);
+    assert_eq!(rewards.len(), 2);
+    for ((reward, &(donor, amount)), proof) in rewards
+        .iter()
+        .zip(&seasonal_rewards)
+        .zip(reward_proofs(&seasonal_rewards))
+    {
+        assert_eq!(reward.season, 4);
+        assert_eq!(reward.donor, donor);
+        assert_eq!(reward.amount, amount);
+        assert_eq!(reward.proof, proof);
+    }
+}
@@ -2,15 +2,33 @@ use anchor_lang::prelude::*;
 
 #[constant]
 pub const CHRT_DECIMALS: u8 = 3;
+/// `Platform::unstake_cooldown` a platform starts with, one week.
+#[constant]
+pub const DEFAULT_UNSTAKE_COOLDOWN: u32 = 604_800;
+/// `Platform::referral_rate` a platform starts with, 101 CHRT per SOL.
+#[constant]
+pub const DEFAULT_REFERRAL_RATE: u64 = 101_000;
 #[constant]
 pub const PLATFORM_FEE_NUM: u64 = 3;
 #[constant]
 pub const PLATFORM_FEE_DENOM: u64 = 100;
 #[constant]
-pub const SEASONAL_TOP_CAPACITY: usize = 10;
+pub const MAX_SEASONAL_TOP_CAPACITY: u16 = 200;
 #[constant]
 pub const PLATFORM_TOP_CAPACITY: usize = 128;
 #[constant]
 pub const CAMPAIGN_TOP_CAPACITY: usize = 10;
 #[constant]
 pub const ACTIVE_CAMPAIGNS_CAPACITY: usize = 128;
+#[constant]
+pub const CAMPAIGN_TITLE_CAPACITY: usize = 64;
+#[constant]
+pub const CAMPAIGN_URI_CAPACITY: usize = 200;
+#[constant]
+pub const MILESTONES_CAPACITY: usize = 8;
+#[constant]
+pub const TOKEN_VAULTS_CAPACITY: u8 = 16;
+#[constant]
+pub const REWARD_TIERS_CAPACITY: usize = 8;
+#[constant]
+pub const REFERRAL_CODE_CAPACITY: usize = 16;
@@ -27,6 +27,111 @@ pub enum CrowdfundingError {
     #[msg("Donors passed for reward must match those in seasonal top")]
     IncorrectSeasonalTop,
     /// 6008 0x1778
-    #[msg("Cannot donate during reward procedure")]
+    #[msg("Not allowed during reward procedure")]
     RewardProcedureInProcess,
+    /// 6009 0x1779
+    #[msg("Fee basis points must not exceed 10000")]
+    InvalidFeeBasisPoints,
+    /// 6010 0x177a
+    #[msg("Liquidation limit must be greater than zero")]
+    InvalidLiquidationLimit,
+    /// 6011 0x177b
+    #[msg("There is no pending authority transfer")]
+    NoPendingAuthority,
+    /// 6012 0x177c
+    #[msg("Campaign title is too long")]
+    TitleTooLong,
+    /// 6013 0x177d
+    #[msg("Campaign metadata URI is too long")]
+    UriTooLong,
+    /// 6014 0x177e
+    #[msg("Funding goal and deadline must be set together")]
+    InvalidFundingGoal,
+    /// 6015 0x177f
+    #[msg("Campaign deadline must be in the future")]
+    InvalidDeadline,
+    /// 6016 0x1780
+    #[msg("Campaign has not reached its funding goal")]
+    FundingGoalNotReached,
+    /// 6017 0x1781
+    #[msg("Donations can only be refunded once a campaign that missed its goal expires")]
+    RefundUnavailable,
+    /// 6018 0x1782
+    #[msg("Campaign deadline has passed")]
+    CampaignEnded,
+    /// 6019 0x1783
+    #[msg("Campaign deadline has not passed yet")]
+    CampaignNotExpired,
+    /// 6020 0x1784
+    #[msg("Milestones must have positive shares summing up to 10000 basis points")]
+    InvalidMilestones,
+    /// 6021 0x1785
+    #[msg("Campaign has locked milestones")]
+    MilestonesLocked,
+    /// 6022 0x1786
+    #[msg("All the milestones are already unlocked")]
+    AllMilestonesUnlocked,
+    /// 6023 0x1787
+    #[msg("Token donations are not available for campaigns with a funding goal")]
+    TokenDonationsUnavailable,
+    /// 6024 0x1788
+    #[msg("Campaign has open token vaults")]
+    TokenVaultsOpen,
+    /// 6025 0x1789
+    #[msg("Reward procedure has timed out and must be restarted by recording donors")]
+    RewardProcedureTimedOut,
+    /// 6026 0x178a
+    #[msg("Reward procedure is not in process")]
+    RewardProcedureNotInProcess,
+    /// 6027 0x178b
+    #[msg("Reward tiers must cover increasing ranks within the seasonal top")]
+    InvalidRewardTiers,
+    /// 6028 0x178c
+    #[msg("Merkle proof does not match the season rewards")]
+    InvalidMerkleProof,
+    /// 6029 0x178d
+    #[msg("Rewards of the season are being distributed in the other mode")]
+    RewardModeMismatch,
+    /// 6030 0x178e
+    #[msg("Rewards are being distributed and the procedure can no longer be aborted")]
+    RewardDistributionInProgress,
+    /// 6031 0x178f
+    #[msg("Seasonal top capacity must be between 1 and MAX_SEASONAL_TOP_CAPACITY")]
+    InvalidSeasonalTopCapacity,
+    /// 6032 0x1790
+    #[msg("Referral code must be 1 to 16 lowercase ASCII letters, digits, `-` or `_`")]
+    InvalidReferralCode,
+    /// 6033 0x1791
+    #[msg("Referral code belongs to another donor")]
+    ReferralCodeMismatch,
+    /// 6034 0x1792
+    #[msg("Stake is smaller than the amount to unstake")]
+    InsufficientStake,
+    /// 6035 0x1793
+    #[msg("Unstake cooldown time has not passed")]
+    UnstakeCooldown,
+    /// 6036 0x1794
+    #[msg("There is no requested unstake to withdraw")]
+    NothingToUnstake,
+    /// 6037 0x1795
+    #[msg("CHRT deposits can be withdrawn only after the campaign is stopped")]
+    CampaignIsActive,
+    /// 6038 0x1796
+    #[msg("Campaign missed its funding goal and its donations can only be refunded")]
+    CampaignMissedGoal,
+    /// 6039 0x1797
+    #[msg("Campaign's limit of open token vaults is reached")]
+    TokenVaultsLimit,
+    /// 6040 0x1798
+    #[msg("Donor has already registered a referral code")]
+    ReferralCodeAlreadyRegistered,
+    /// 6041 0x1799
+    #[msg("Referred donations must be credited with the referer they were made with")]
+    RefererMismatch,
+    /// 6042 0x179a
+    #[msg("Campaign authority and beneficiary cannot vote on the campaign's milestones")]
+    MilestoneSelfVote,
+    /// 6043 0x179b
+    #[msg("CHRT deposit must be greater than zero")]
+    ZeroDeposit,
 }
@@ -0,0 +1,336 @@
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct PlatformInitialized {
+    pub authority: Pubkey,
+    pub seasonal_top_capacity: u16,
+    pub reward_cooldown: u32,
+    pub reward_amount: u64,
+    pub reward_distribution: u8,
+    pub reward_tiers: Vec<RewardTier>,
+    pub fee_basis_points: u16,
+    pub fee_exemption_limit: u64,
+    pub liquidation_limit: u64,
+}
+
+#[event]
+pub struct PlatformConfigUpdated {
+    pub reward_cooldown: u32,
+    pub reward_procedure_timeout: u32,
+    pub reward_amount: u64,
+    pub reward_distribution: u8,
+    pub reward_tiers: Vec<RewardTier>,
+    pub fee_basis_points: u16,
+    pub fee_exemption_limit: u64,
+    pub liquidation_limit: u64,
+    pub record_donor_bounty: u64,
+    pub drop_rewards_bounty: u64,
+    pub referral_rate: u64,
+    pub referral_donation_cap: u64,
+    pub referral_season_cap: u64,
+    pub unstake_cooldown: u32,
+}
+
+#[event]
+pub struct PlatformAuthorityProposed {
+    pub authority: Pubkey,
+    pub pending_authority: Pubkey,
+}
+
+#[event]
+pub struct PlatformAuthorityAccepted {
+    pub previous_authority: Pubkey,
+    pub authority: Pubkey,
+}
+
+#[event]
+pub struct PlatformAuthorityCancelled {
+    pub authority: Pubkey,
+    pub pending_authority: Pubkey,
+}
+
+#[event]
+pub struct DonorRegistered {
+    pub donor: Pubkey,
+}
+
+#[event]
+pub struct CampaignStarted {
+    pub campaign_id: u16,
+    pub authority: Pubkey,
+    pub beneficiary: Pubkey,
+    pub goal: u64,
+    pub deadline: u32,
+    pub milestones_count: u8,
+}
+
+#[event]
+pub struct CampaignMetadataUpdated {
+    pub campaign_id: u16,
+}
+
+#[event]
+pub struct CampaignAuthorityProposed {
+    pub campaign_id: u16,
+    pub authority: Pubkey,
+    pub pending_authority: Pubkey,
+}
+
+#[event]
+pub struct CampaignAuthorityAccepted {
+    pub campaign_id: u16,
+    pub previous_authority: Pubkey,
+    pub authority: Pubkey,
+}
+
+#[event]
+pub struct CampaignAuthorityCancelled {
+    pub campaign_id: u16,
+    pub authority: Pubkey,
+    pub pending_authority: Pubkey,
+}
+
+#[event]
+pub struct DonationMade {
+    pub donor: Pubkey,
+    pub campaign_id: u16,
+    pub amount: u64,
+    pub fee: u64,
+    pub fee_exempted: bool,
+}
+
+#[event]
+pub struct TokenDonationMade {
+    pub donor: Pubkey,
+    pub campaign_id: u16,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub fee: u64,
+    pub fee_exempted: bool,
+}
+
+#[event]
+pub struct ReferralRewardMinted {
+    pub referer: Pubkey,
+    pub donor: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct ReferralCodeRegistered {
+    pub donor: Pubkey,
+    pub code: String,
+}
+
+#[event]
+pub struct Staked {
+    pub staker: Pubkey,
+    pub campaign_id: u16,
+    pub amount: u64,
+    pub staked_sum: u64,
+}
+
+#[event]
+pub struct UnstakeRequested {
+    pub staker: Pubkey,
+    pub campaign_id: u16,
+    pub amount: u64,
+    pub unlock_ts: u32,
+    pub staked_sum: u64,
+}
+
+#[event]
+pub struct Unstaked {
+    pub staker: Pubkey,
+    pub campaign_id: u16,
+    pub amount: u64,
+}
+
+#[event]
+pub struct CampaignSupported {
+    pub supporter: Pubkey,
+    pub campaign_id: u16,
+    pub amount: u64,
+    pub deposit: u64,
+    pub fee_exemption_deposits_sum: u64,
+}
+
+#[event]
+pub struct CampaignFlagged {
+    pub flagger: Pubkey,
+    pub campaign_id: u16,
+    pub amount: u64,
+    pub deposit: u64,
+    pub liquidation_deposits_sum: u64,
+}
+
+#[event]
+pub struct ChrtDepositWithdrawn {
+    pub depositor: Pubkey,
+    pub campaign_id: u16,
+    pub vault: u8,
+    pub amount: u64,
+}
+
+#[event]
+pub struct DonationsCredited {
+    pub donor: Pubkey,
+    pub campaign_id: u16,
+    pub amount: u64,
+}
+
+#[event]
+pub struct DonationsRefunded {
+    pub donor: Pubkey,
+    pub campaign_id: u16,
+    pub amount: u64,
+}
+
+#[event]
+pub struct MilestoneVoted {
+    pub donor: Pubkey,
+    pub campaign_id: u16,
+    pub milestone: u8,
+    pub weight: u64,
+}
+
+#[event]
+pub struct MilestoneUnlocked {
+    pub campaign_id: u16,
+    pub milestone: u8,
+}
+
+#[event]
+pub struct DonationsWithdrawn {
+    pub campaign_id: u16,
+    pub beneficiary: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct TokenDonationsWithdrawn {
+    pub campaign_id: u16,
+    pub mint: Pubkey,
+    pub beneficiary: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct TokenVaultOpened {
+    pub campaign_id: u16,
+    pub mint: Pubkey,
+}
+
+#[event]
+pub struct TokenVaultClosed {
+    pub campaign_id: u16,
+    pub mint: Pubkey,
+    pub beneficiary: Pubkey,
+    pub amount: u64,
+    pub refundable_amount: u64,
+}
+
+#[event]
+pub struct TokenDonationsRefunded {
+    pub donor: Pubkey,
+    pub campaign_id: u16,
+    pub mint: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct TokenVaultLiquidated {
+    pub campaign_id: u16,
+    pub mint: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct CampaignStopped {
+    pub campaign_id: u16,
+    pub beneficiary: Pubkey,
+    pub amount: u64,
+    pub refundable_amount: u64,
+    pub expired: bool,
+}
+
+#[event]
+pub struct CampaignLiquidated {
+    pub campaign_id: u16,
+    pub amount: u64,
+    pub distributed_amount: u64,
+}
+
+#[event]
+pub struct DonorsRecorded {
+    pub season: u32,
+    pub donors_recorded: u32,
+    pub donors_count: u32,
+}
+
+#[event]
+pub struct RewardDropped {
+    pub donor: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct RewardsDropped {
+    pub season: u32,
+    pub donors_rewarded: u16,
+    pub total_amount: u64,
+}
+
+#[event]
+pub struct RewardCommitted {
+    pub season: u32,
+    pub donor: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct RewardsCommitted {
+    pub season: u32,
+    pub merkle_root: [u8; 32],
+    pub donors_rewarded: u16,
+    pub total_amount: u64,
+}
+
+#[event]
+pub struct RewardClaimed {
+    pub season: u32,
+    pub donor: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct SeasonalTopResized {
+    pub authority: Pubkey,
+    pub capacity: u16,
+}
+
+#[event]
+pub struct RewardProcedureAborted {
+    pub authority: Pubkey,
+    pub donors_recorded: u32,
+}
+
+#[event]
+pub struct BountyPaid {
+    pub keeper: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct FeesWithdrawn {
+    pub authority: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct TokenFeesWithdrawn {
+    pub authority: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+}
@@ -0,0 +1,34 @@
+use crate::{error::*, events::*, state::*, utils::*};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct AbortRewardProcedure<'info> {
+    #[account(mut, seeds = [b"platform"], bump)]
+    platform: AccountLoader<'info, Platform>,
+    #[account(mut, seeds = [b"seasonal_top"], bump)]
+    seasonal_top: AccountLoader<'info, SeasonalTop>,
+    #[account(address = platform.load()?.authority)]
+    platform_authority: Signer<'info>,
+}
+
+pub fn abort_reward_procedure(ctx: Context<AbortRewardProcedure>) -> Result<()> {
+    let platform = &mut ctx.accounts.platform.load_mut()?;
+    if !platform.reward_procedure_is_in_process {
+        return err!(CrowdfundingError::RewardProcedureNotInProcess);
+    }
+
+    let (seasonal_top, mut records) =
+        load_with_records::<SeasonalTop, SeasonalTopRecord>(ctx.accounts.seasonal_top.as_ref())?;
+    if seasonal_top.cursor != 0 {
+        return err!(CrowdfundingError::RewardDistributionInProgress);
+    }
+
+    emit!(RewardProcedureAborted {
+        authority: ctx.accounts.platform_authority.key(),
+        donors_recorded: platform.donors_recorded,
+    });
+
+    reset_reward_procedure(platform, &mut records);
+
+    Ok(())
+}
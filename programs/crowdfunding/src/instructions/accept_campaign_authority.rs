@@ -0,0 +1,29 @@
+use crate::{events::*, state::*};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct AcceptCampaignAuthority<'info> {
+    #[account(
+        mut,
+        seeds = [b"campaign", campaign.load()?.id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    campaign: AccountLoader<'info, Campaign>,
+    #[account(address = campaign.load()?.pending_authority)]
+    new_authority: Signer<'info>,
+}
+
+pub fn accept_campaign_authority(ctx: Context<AcceptCampaignAuthority>) -> Result<()> {
+    let campaign = &mut ctx.accounts.campaign.load_mut()?;
+    let previous_authority = campaign.authority;
+    campaign.authority = campaign.pending_authority;
+    campaign.pending_authority = Pubkey::default();
+
+    emit!(CampaignAuthorityAccepted {
+        campaign_id: campaign.id,
+        previous_authority,
+        authority: campaign.authority,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,24 @@
+use crate::{events::*, state::*};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct AcceptPlatformAuthority<'info> {
+    #[account(mut, seeds = [b"platform"], bump)]
+    platform: AccountLoader<'info, Platform>,
+    #[account(address = platform.load()?.pending_authority)]
+    new_authority: Signer<'info>,
+}
+
+pub fn accept_platform_authority(ctx: Context<AcceptPlatformAuthority>) -> Result<()> {
+    let platform = &mut ctx.accounts.platform.load_mut()?;
+    let previous_authority = platform.authority;
+    platform.authority = platform.pending_authority;
+    platform.pending_authority = Pubkey::default();
+
+    emit!(PlatformAuthorityAccepted {
+        previous_authority,
+        authority: platform.authority,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,31 @@
+use crate::{error::*, events::*, state::*};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct CancelCampaignAuthority<'info> {
+    #[account(
+        mut,
+        seeds = [b"campaign", campaign.load()?.id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    campaign: AccountLoader<'info, Campaign>,
+    #[account(address = campaign.load()?.authority)]
+    campaign_authority: Signer<'info>,
+}
+
+pub fn cancel_campaign_authority(ctx: Context<CancelCampaignAuthority>) -> Result<()> {
+    let campaign = &mut ctx.accounts.campaign.load_mut()?;
+    if campaign.pending_authority == Pubkey::default() {
+        return err!(CrowdfundingError::NoPendingAuthority);
+    }
+    let pending_authority = campaign.pending_authority;
+    campaign.pending_authority = Pubkey::default();
+
+    emit!(CampaignAuthorityCancelled {
+        campaign_id: campaign.id,
+        authority: campaign.authority,
+        pending_authority,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,26 @@
+use crate::{error::*, events::*, state::*};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct CancelPlatformAuthority<'info> {
+    #[account(mut, seeds = [b"platform"], bump)]
+    platform: AccountLoader<'info, Platform>,
+    #[account(address = platform.load()?.authority)]
+    platform_authority: Signer<'info>,
+}
+
+pub fn cancel_platform_authority(ctx: Context<CancelPlatformAuthority>) -> Result<()> {
+    let platform = &mut ctx.accounts.platform.load_mut()?;
+    if platform.pending_authority == Pubkey::default() {
+        return err!(CrowdfundingError::NoPendingAuthority);
+    }
+    let pending_authority = platform.pending_authority;
+    platform.pending_authority = Pubkey::default();
+
+    emit!(PlatformAuthorityCancelled {
+        authority: platform.authority,
+        pending_authority,
+    });
+
+    Ok(())
+}
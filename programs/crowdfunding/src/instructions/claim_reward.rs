@@ -0,0 +1,79 @@
+use crate::{error::*, events::*, merkle::*, state::*};
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{self, Mint, MintTo, Token, TokenAccount},
+};
+use core::mem::size_of;
+
+#[derive(Accounts)]
+#[instruction(season_id: u32)]
+pub struct ClaimReward<'info> {
+    #[account(seeds = [b"platform"], bump)]
+    platform: AccountLoader<'info, Platform>,
+    #[account(mut, seeds = [b"season", season_id.to_le_bytes().as_ref()], bump)]
+    season: AccountLoader<'info, Season>,
+    #[account(
+        init,
+        payer = donor_authority,
+        seeds = [b"reward_claim", season_id.to_le_bytes().as_ref(), donor_authority.key().as_ref()],
+        bump,
+        space = 8 + size_of::<RewardClaim>(),
+    )]
+    reward_claim: AccountLoader<'info, RewardClaim>,
+    #[account(mut)]
+    donor_authority: Signer<'info>,
+    #[account(
+        init_if_needed,
+        payer = donor_authority,
+        associated_token::mint = chrt_mint,
+        associated_token::authority = donor_authority,
+    )]
+    donor_chrt: Account<'info, TokenAccount>,
+    #[account(mut, seeds = [b"chrt_mint"], bump)]
+    chrt_mint: Account<'info, Mint>,
+    rent: Sysvar<'info, Rent>,
+    token_program: Program<'info, Token>,
+    associated_token_program: Program<'info, AssociatedToken>,
+    system_program: Program<'info, System>,
+}
+
+pub fn claim_reward(
+    ctx: Context<ClaimReward>,
+    season_id: u32,
+    amount: u64,
+    proof: Vec<[u8; 32]>,
+) -> Result<()> {
+    let donor = ctx.accounts.donor_authority.key();
+    {
+        let season = &mut ctx.accounts.season.load_mut()?;
+        if !verify_proof(&proof, &{ season.merkle_root }, reward_leaf(&donor, amount)) {
+            return err!(CrowdfundingError::InvalidMerkleProof);
+        }
+        season.claimed_rewards_sum += amount;
+    }
+
+    let reward_claim = &mut ctx.accounts.reward_claim.load_init()?;
+    reward_claim.amount = amount;
+
+    token::mint_to(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            MintTo {
+                mint: ctx.accounts.chrt_mint.to_account_info(),
+                to: ctx.accounts.donor_chrt.to_account_info(),
+                authority: ctx.accounts.platform.to_account_info(),
+            },
+            &[&[b"platform", &[*ctx.bumps.get("platform").unwrap()]]],
+        ),
+        amount,
+    )?;
+
+    emit!(RewardClaimed {
+        season: season_id,
+        donor,
+        amount,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,94 @@
+use crate::{error::*, events::*, state::*, utils::*};
+use anchor_lang::{prelude::*, AccountsClose};
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+#[derive(Accounts)]
+pub struct CloseTokenVault<'info> {
+    #[account(seeds = [b"platform"], bump)]
+    platform: AccountLoader<'info, Platform>,
+    #[account(
+        mut,
+        seeds = [b"campaign", campaign.load()?.id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    campaign: AccountLoader<'info, Campaign>,
+    /// Campaign authority, or anyone once the campaign deadline has passed.
+    #[account(mut)]
+    authority: Signer<'info>,
+    #[account(mut, address = campaign.load()?.beneficiary)]
+    beneficiary: UncheckedAccount<'info>,
+    mint: Account<'info, Mint>,
+    #[account(
+        mut,
+        seeds = [b"token_vault", campaign.load()?.id.to_le_bytes().as_ref(), mint.key().as_ref()],
+        bump,
+    )]
+    token_vault: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [b"token_donations", campaign.load()?.id.to_le_bytes().as_ref(), mint.key().as_ref()],
+        bump,
+    )]
+    token_donations: AccountLoader<'info, TokenDonations>,
+    #[account(mut, token::mint = mint, token::authority = campaign.load()?.beneficiary)]
+    beneficiary_token_account: Account<'info, TokenAccount>,
+    token_program: Program<'info, Token>,
+}
+
+pub fn close_token_vault(ctx: Context<CloseTokenVault>) -> Result<()> {
+    let (amount, refundable_amount) = {
+        let campaign = &mut ctx.accounts.campaign.load_mut()?;
+        let expired =
+            campaign.deadline != 0 && Clock::get()?.unix_timestamp as u32 > campaign.deadline;
+        if ctx.accounts.authority.key() != campaign.authority && !expired {
+            return err!(CrowdfundingError::CampaignNotExpired);
+        }
+        campaign.token_vaults_count -= 1;
+
+        // the share of locked milestones stays in the vault for donors to refund, as when a
+        // campaign is stopped, and the beneficiary takes the rest
+        let token_donations = &mut ctx.accounts.token_donations.load_mut()?;
+        let (_, locked_amount) = release_donations(
+            campaign,
+            token_donations.donations_sum,
+            token_donations.withdrawn_sum,
+        );
+        let vault_amount = ctx.accounts.token_vault.amount;
+        let refundable_amount = locked_amount.min(vault_amount);
+        token_donations.refundable_sum = refundable_amount;
+        token_donations.beneficiary = campaign.beneficiary;
+        (vault_amount - refundable_amount, refundable_amount)
+    };
+
+    let platform_bump = *ctx.bumps.get("platform").unwrap();
+    transfer_tokens(
+        &ctx.accounts.token_program,
+        &ctx.accounts.platform,
+        platform_bump,
+        &ctx.accounts.token_vault,
+        &ctx.accounts.beneficiary_token_account,
+        amount,
+    )?;
+    if refundable_amount == 0 {
+        close_token_account(
+            &ctx.accounts.token_program,
+            &ctx.accounts.platform,
+            platform_bump,
+            &ctx.accounts.token_vault,
+            &ctx.accounts.beneficiary.to_account_info(),
+        )?;
+        ctx.accounts
+            .token_donations
+            .close(ctx.accounts.beneficiary.to_account_info())?;
+    }
+
+    emit!(TokenVaultClosed {
+        campaign_id: ctx.accounts.campaign.load()?.id,
+        mint: ctx.accounts.mint.key(),
+        beneficiary: ctx.accounts.beneficiary.key(),
+        amount,
+        refundable_amount,
+    });
+
+    Ok(())
+}
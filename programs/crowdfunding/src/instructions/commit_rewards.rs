@@ -0,0 +1,78 @@
+use crate::{events::*, state::*, utils::*};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct CommitRewards<'info> {
+    #[account(mut, seeds = [b"platform"], bump)]
+    platform: AccountLoader<'info, Platform>,
+    #[account(mut, seeds = [b"seasonal_top"], bump)]
+    seasonal_top: AccountLoader<'info, SeasonalTop>,
+    #[account(mut, seeds = [b"fee_vault"], bump)]
+    fee_vault: AccountLoader<'info, Vault>,
+    #[account(mut)]
+    keeper: Signer<'info>,
+    #[account(
+        init_if_needed,
+        payer = keeper,
+        seeds = [b"season", platform.load()?.season.to_le_bytes().as_ref()],
+        bump,
+        space = Season::space(seasonal_top.load()?.capacity),
+    )]
+    season: AccountLoader<'info, Season>,
+    system_program: Program<'info, System>,
+}
+
+/// Remaining accounts are the `Donor` accounts of the next page of winners.
+pub fn commit_rewards<'info>(ctx: Context<'_, '_, '_, 'info, CommitRewards<'info>>) -> Result<()> {
+    let (season, ended, bounty, page) = {
+        let platform = &mut ctx.accounts.platform.load_mut()?;
+        let donors = ctx.remaining_accounts.iter().collect::<Vec<_>>();
+        let page = reward_seasonal_top_page(
+            platform,
+            ctx.accounts.seasonal_top.as_ref(),
+            ctx.accounts.season.as_ref(),
+            &donors,
+            true,
+            Clock::get()?.unix_timestamp as _,
+        )?;
+
+        (
+            platform.season,
+            !platform.reward_procedure_is_in_process,
+            platform.drop_rewards_bounty,
+            page,
+        )
+    };
+
+    for record in page.iter().filter(|r| r.reward != 0) {
+        emit!(RewardCommitted {
+            season,
+            donor: record.donor,
+            amount: record.reward,
+        });
+    }
+
+    if !ended {
+        return Ok(());
+    }
+
+    let Season {
+        merkle_root,
+        donors_rewarded,
+        rewards_sum,
+        ..
+    } = finish_reward_distribution(
+        &ctx.accounts.season,
+        &ctx.accounts.fee_vault.to_account_info(),
+        &ctx.accounts.keeper.to_account_info(),
+        bounty,
+    )?;
+    emit!(RewardsCommitted {
+        season,
+        merkle_root,
+        donors_rewarded,
+        total_amount: rewards_sum,
+    });
+
+    Ok(())
+}
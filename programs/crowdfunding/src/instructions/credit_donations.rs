@@ -0,0 +1,158 @@
+use crate::{error::*, events::*, state::*, utils::*};
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::{AssociatedToken, Create},
+    token::{self, Mint, MintTo, Token},
+};
+use core::ops::Deref;
+
+#[derive(Accounts)]
+#[instruction(campaign_id: u16)]
+pub struct CreditDonations<'info> {
+    #[account(mut, seeds = [b"platform"], bump)]
+    platform: AccountLoader<'info, Platform>,
+    // outlives the campaign, so that donations are credited after it is stopped
+    #[account(
+        seeds = [b"donations", campaign_id.to_le_bytes().as_ref()],
+        bump,
+        constraint = total_donations_to_campaign.load()?.goal_reached
+            @ CrowdfundingError::FundingGoalNotReached,
+    )]
+    total_donations_to_campaign: AccountLoader<'info, Donations>,
+    #[account(mut, seeds = [b"donor", donor_authority.key().as_ref()], bump)]
+    donor: AccountLoader<'info, Donor>,
+    #[account(mut)]
+    donor_authority: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"donations", donor_authority.key().as_ref(), campaign_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    donor_donations_to_campaign: AccountLoader<'info, Donations>,
+}
+
+#[derive(Accounts)]
+#[instruction(campaign_id: u16)]
+pub struct CreditDonationsWithReferer<'info> {
+    credit: CreditDonations<'info>,
+    #[account(mut, seeds = [b"chrt_mint"], bump)]
+    chrt_mint: Account<'info, Mint>,
+    #[account(
+        mut,
+        seeds = [b"donor", referer_authority.key().as_ref()],
+        bump,
+        constraint = referer.key() != credit.donor.key() @ CrowdfundingError::CannotReferYourself,
+    )]
+    referer: AccountLoader<'info, Donor>,
+    #[account(
+        constraint = referer_authority.key() == credit.donor_donations_to_campaign.load()?.referer
+            @ CrowdfundingError::RefererMismatch,
+    )]
+    referer_authority: UncheckedAccount<'info>,
+    // any CHRT account of the referer, or its associated one created at the donor's expense
+    #[account(mut)]
+    referer_chrt: UncheckedAccount<'info>,
+    rent: Sysvar<'info, Rent>,
+    token_program: Program<'info, Token>,
+    associated_token_program: Program<'info, AssociatedToken>,
+    system_program: Program<'info, System>,
+}
+
+/// Returns the referred donations to reward.
+fn credit_common(accounts: &CreditDonations, campaign_id: u16, with_referer: bool) -> Result<u64> {
+    let platform = &mut accounts.platform.load_mut()?;
+    let donor = &mut accounts.donor.load_mut()?;
+    let donor_donations_to_campaign = &mut accounts.donor_donations_to_campaign.load_mut()?;
+    let amount = donor_donations_to_campaign.uncredited_sum;
+    let referred_sum = credit_donor(platform, donor, donor_donations_to_campaign, with_referer);
+
+    emit!(DonationsCredited {
+        donor: accounts.donor_authority.key(),
+        campaign_id,
+        amount,
+    });
+
+    add_to_top(
+        &mut platform.top,
+        DonorRecord {
+            donor: accounts.donor_authority.key(),
+            donations_sum: donor.donations_sum,
+        },
+    );
+
+    Ok(referred_sum)
+}
+
+pub fn credit_donations(ctx: Context<CreditDonations>, campaign_id: u16) -> Result<()> {
+    // the referer they were made with is owed a reward for referred donations
+    if ctx
+        .accounts
+        .donor_donations_to_campaign
+        .load()?
+        .uncredited_referred_sum
+        != 0
+    {
+        return err!(CrowdfundingError::RefererMismatch);
+    }
+    credit_common(ctx.accounts, campaign_id, false)?;
+
+    Ok(())
+}
+
+pub fn credit_donations_with_referer(
+    ctx: Context<CreditDonationsWithReferer>,
+    campaign_id: u16,
+) -> Result<()> {
+    init_referer_chrt_if_needed(
+        &ctx.accounts.associated_token_program,
+        Create {
+            payer: ctx.accounts.credit.donor_authority.to_account_info(),
+            associated_token: ctx.accounts.referer_chrt.to_account_info(),
+            authority: ctx.accounts.referer_authority.to_account_info(),
+            mint: ctx.accounts.chrt_mint.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+            token_program: ctx.accounts.token_program.to_account_info(),
+            rent: ctx.accounts.rent.to_account_info(),
+        },
+    )?;
+    let referred_sum = credit_common(&ctx.accounts.credit, campaign_id, true)?;
+    if referred_sum == 0 {
+        return Ok(());
+    }
+
+    let donor_authority = ctx.accounts.credit.donor_authority.key();
+    let referer_authority = ctx.accounts.referer_authority.key();
+    let amount = {
+        let donor = &mut ctx.accounts.credit.donor.load_mut()?;
+        let referer = &mut ctx.accounts.referer.load_mut()?;
+        record_referral(
+            ctx.accounts.credit.platform.load()?.deref(),
+            donor,
+            referer,
+            referer_authority,
+            referred_sum,
+        )
+    };
+    if amount == 0 {
+        return Ok(());
+    }
+
+    emit!(ReferralRewardMinted {
+        referer: referer_authority,
+        donor: donor_authority,
+        amount,
+    });
+    let signer: &[&[&[u8]]] = &[&[b"platform", &[*ctx.bumps.get("platform").unwrap()]]];
+    token::mint_to(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            MintTo {
+                mint: ctx.accounts.chrt_mint.to_account_info(),
+                to: ctx.accounts.referer_chrt.to_account_info(),
+                authority: ctx.accounts.credit.platform.to_account_info(),
+            },
+            signer,
+        ),
+        amount,
+    )
+}
@@ -1,10 +1,11 @@
-use crate::{config::*, error::*, state::*, utils::*};
+use crate::{error::*, events::*, state::*, utils::*};
 use anchor_lang::{
     prelude::*,
     solana_program::{program::invoke, system_instruction},
 };
-use anchor_spl::token::{
-    self, spl_token::native_mint::DECIMALS, Mint, MintTo, Token, TokenAccount,
+use anchor_spl::{
+    associated_token::{AssociatedToken, Create},
+    token::{self, Mint, MintTo, Token},
 };
 use core::{mem::size_of, ops::Deref};
 
@@ -28,11 +29,8 @@ pub struct Donate<'info> {
         bump,
     )]
     total_donations_to_campaign: AccountLoader<'info, Donations>,
-    #[account(
-        seeds = [b"fee_exemption_vault", campaign.load()?.id.to_le_bytes().as_ref()],
-        bump,
-    )]
-    fee_exemption_vault: Account<'info, TokenAccount>,
+    #[account(seeds = [b"stake_pool", campaign.load()?.id.to_le_bytes().as_ref()], bump)]
+    stake_pool: AccountLoader<'info, StakePool>,
     #[account(mut, seeds = [b"donor", donor_authority.key().as_ref()], bump)]
     donor: AccountLoader<'info, Donor>,
     #[account(mut)]
@@ -54,28 +52,56 @@ pub struct DonateWithReferer<'info> {
     #[account(mut, seeds = [b"chrt_mint"], bump)]
     chrt_mint: Account<'info, Mint>,
     #[account(
+        mut,
         seeds = [b"donor", referer_authority.key().as_ref()],
         bump,
         constraint = referer.key() != donate.donor.key() @ CrowdfundingError::CannotReferYourself,
     )]
     referer: AccountLoader<'info, Donor>,
     referer_authority: UncheckedAccount<'info>,
-    #[account(mut, token::authority = referer_authority)]
-    referer_chrt: Account<'info, TokenAccount>,
+    // any CHRT account of the referer, or its associated one created at the donor's expense
+    // if missing, so that a referral never fails the donation
+    #[account(mut)]
+    referer_chrt: UncheckedAccount<'info>,
+    rent: Sysvar<'info, Rent>,
     token_program: Program<'info, Token>,
+    associated_token_program: Program<'info, AssociatedToken>,
+    system_program: Program<'info, System>,
 }
 
-fn transfer_to_campaign(accounts: &mut Donate, lamports: u64) -> Result<()> {
+#[derive(Accounts)]
+#[instruction(code: String)]
+pub struct DonateWithReferralCode<'info> {
+    donate_with_referer: DonateWithReferer<'info>,
+    #[account(
+        seeds = [b"referral_code", referral_code_seed(&code)?],
+        bump,
+        constraint = referral_code.load()?.donor == donate_with_referer.referer_authority.key()
+            @ CrowdfundingError::ReferralCodeMismatch,
+    )]
+    referral_code: AccountLoader<'info, ReferralCode>,
+}
+
+/// Returns the referred donations to reward, see `credit_donor`.
+fn transfer_to_campaign(
+    accounts: &mut Donate,
+    lamports: u64,
+    referer: Option<Pubkey>,
+    referred_lamports: u64,
+) -> Result<u64> {
     let platform = &mut accounts.platform.load_mut()?;
-    let id = accounts.campaign.load()?.id;
+    let &Campaign { id, goal, .. } = accounts.campaign.load()?.deref();
     let i = platform.active_campaigns[..platform.active_campaigns_count as usize]
         .binary_search_by_key(&id, |c| c.id)
         .unwrap();
     platform.active_campaigns[i].donations_sum += lamports;
     platform.sum_of_all_donations += lamports;
     platform.sum_of_active_campaign_donations += lamports;
-    accounts.donor.load_mut()?.donations_sum += lamports;
-    (accounts.total_donations_to_campaign.load_mut()?).donations_sum += lamports;
+    let total_donations_to_campaign = &mut accounts.total_donations_to_campaign.load_mut()?;
+    total_donations_to_campaign.donations_sum += lamports;
+    if platform.active_campaigns[i].donations_sum >= goal {
+        total_donations_to_campaign.goal_reached = true;
+    }
     let donor_donations_to_campaign = &mut if accounts
         .donor_donations_to_campaign
         .to_account_info()
@@ -86,7 +112,29 @@ fn transfer_to_campaign(accounts: &mut Donate, lamports: u64) -> Result<()> {
     } else {
         accounts.donor_donations_to_campaign.load_mut()?
     };
+    if let Some(referer) = referer {
+        // pending referred donations are rewarded to the referer they were made with
+        if donor_donations_to_campaign.uncredited_referred_sum != 0
+            && donor_donations_to_campaign.referer != referer
+        {
+            return err!(CrowdfundingError::RefererMismatch);
+        }
+        donor_donations_to_campaign.referer = referer;
+    }
     donor_donations_to_campaign.donations_sum += lamports;
+    donor_donations_to_campaign.uncredited_sum += lamports;
+    donor_donations_to_campaign.uncredited_referred_sum += referred_lamports;
+    let referred_sum = if total_donations_to_campaign.goal_reached {
+        let donor = &mut accounts.donor.load_mut()?;
+        credit_donor(
+            platform,
+            donor,
+            donor_donations_to_campaign,
+            referer.is_some(),
+        )
+    } else {
+        0
+    };
 
     invoke(
         &system_instruction::transfer(
@@ -100,7 +148,7 @@ fn transfer_to_campaign(accounts: &mut Donate, lamports: u64) -> Result<()> {
         ],
     )?;
 
-    Ok(())
+    Ok(referred_sum)
 }
 
 fn transfer_to_platform(accounts: &Donate, lamports: u64) -> Result<()> {
@@ -118,26 +166,43 @@ fn transfer_to_platform(accounts: &Donate, lamports: u64) -> Result<()> {
     Ok(())
 }
 
-fn donate_common(accounts: &mut Donate, lamports: u64) -> Result<()> {
+/// Returns the referred donations to reward if the donation is made with a referer.
+fn donate_common(accounts: &mut Donate, lamports: u64, referer: Option<Pubkey>) -> Result<u64> {
     let &Platform {
-        reward_procedure_is_in_process,
         fee_basis_points,
         fee_exemption_limit,
         ..
     } = accounts.platform.load()?.deref();
 
-    if reward_procedure_is_in_process {
-        return err!(CrowdfundingError::RewardProcedureInProcess);
+    let deadline = accounts.campaign.load()?.deadline;
+    if deadline != 0 && Clock::get()?.unix_timestamp as u32 > deadline {
+        return err!(CrowdfundingError::CampaignEnded);
     }
 
-    let fee = lamports * fee_basis_points as u64 / 10000;
-    if accounts.fee_exemption_vault.amount < fee_exemption_limit {
-        transfer_to_campaign(accounts, lamports - fee)?;
+    let fee = calculate_fee(lamports, fee_basis_points);
+    let fee_exempted = is_fee_exempted(
+        accounts.campaign.load()?.deref(),
+        accounts.stake_pool.load()?.deref(),
+        fee_exemption_limit,
+    );
+    let referred_lamports = if referer.is_some() { lamports } else { 0 };
+    let referred_sum = if !fee_exempted {
+        let referred_sum =
+            transfer_to_campaign(accounts, lamports - fee, referer, referred_lamports)?;
         transfer_to_platform(accounts, fee)?;
+        referred_sum
     } else {
-        transfer_to_campaign(accounts, lamports)?;
         accounts.platform.load_mut()?.avoided_fees_sum += fee;
-    }
+        transfer_to_campaign(accounts, lamports, referer, referred_lamports)?
+    };
+
+    emit!(DonationMade {
+        donor: accounts.donor_authority.key(),
+        campaign_id: accounts.campaign.load()?.id,
+        amount: lamports,
+        fee,
+        fee_exempted,
+    });
 
     add_to_top(
         &mut accounts.platform.load_mut()?.top,
@@ -168,36 +233,106 @@ fn donate_common(accounts: &mut Donate, lamports: u64) -> Result<()> {
         },
     );
 
-    Ok(())
+    Ok(referred_sum)
 }
 
 pub fn donate(ctx: Context<Donate>, lamports: u64) -> Result<()> {
-    donate_common(ctx.accounts, lamports)?;
+    donate_common(ctx.accounts, lamports, None)?;
 
     Ok(())
 }
 
-fn mint_chrt_to_referer(ctx: Context<DonateWithReferer>, amount: u64) -> Result<()> {
-    let signer: &[&[&[u8]]] = &[&[b"platform", &[*ctx.bumps.get("platform").unwrap()]]];
+fn mint_chrt_to_referer(
+    accounts: &DonateWithReferer,
+    platform_bump: u8,
+    amount: u64,
+) -> Result<()> {
+    let signer: &[&[&[u8]]] = &[&[b"platform", &[platform_bump]]];
     let cpi_ctx = CpiContext::new_with_signer(
-        ctx.accounts.token_program.to_account_info(),
+        accounts.token_program.to_account_info(),
         MintTo {
-            mint: ctx.accounts.chrt_mint.to_account_info(),
-            to: ctx.accounts.referer_chrt.to_account_info(),
-            authority: ctx.accounts.donate.platform.to_account_info(),
+            mint: accounts.chrt_mint.to_account_info(),
+            to: accounts.referer_chrt.to_account_info(),
+            authority: accounts.donate.platform.to_account_info(),
         },
         signer,
     );
     token::mint_to(cpi_ctx, amount)
 }
 
-pub fn donate_with_referer(ctx: Context<DonateWithReferer>, lamports: u64) -> Result<()> {
-    donate_common(&mut ctx.accounts.donate, lamports)?;
+fn init_referer_chrt(accounts: &DonateWithReferer) -> Result<()> {
+    init_referer_chrt_if_needed(
+        &accounts.associated_token_program,
+        Create {
+            payer: accounts.donate.donor_authority.to_account_info(),
+            associated_token: accounts.referer_chrt.to_account_info(),
+            authority: accounts.referer_authority.to_account_info(),
+            mint: accounts.chrt_mint.to_account_info(),
+            system_program: accounts.system_program.to_account_info(),
+            token_program: accounts.token_program.to_account_info(),
+            rent: accounts.rent.to_account_info(),
+        },
+    )
+}
 
-    mint_chrt_to_referer(
-        ctx,
-        101 * lamports / 10u64.pow((DECIMALS - CHRT_DECIMALS) as _),
+fn reward_referer(
+    accounts: &mut DonateWithReferer,
+    platform_bump: u8,
+    lamports: u64,
+) -> Result<()> {
+    let donor_authority = accounts.donate.donor_authority.key();
+    let referer_authority = accounts.referer_authority.key();
+    // donations to a campaign that has not reached its goal are rewarded once it does
+    if lamports == 0 {
+        return Ok(());
+    }
+    let amount = {
+        let donor = &mut accounts.donate.donor.load_mut()?;
+        let referer = &mut accounts.referer.load_mut()?;
+        record_referral(
+            accounts.donate.platform.load()?.deref(),
+            donor,
+            referer,
+            referer_authority,
+            lamports,
+        )
+    };
+    // the donation still goes through once the referer reached a cap
+    if amount == 0 {
+        return Ok(());
+    }
+
+    emit!(ReferralRewardMinted {
+        referer: referer_authority,
+        donor: donor_authority,
+        amount,
+    });
+    mint_chrt_to_referer(accounts, platform_bump, amount)
+}
+
+pub fn donate_with_referer(ctx: Context<DonateWithReferer>, lamports: u64) -> Result<()> {
+    init_referer_chrt(ctx.accounts)?;
+    let referer = Some(ctx.accounts.referer_authority.key());
+    let referred_sum = donate_common(&mut ctx.accounts.donate, lamports, referer)?;
+    reward_referer(
+        ctx.accounts,
+        *ctx.bumps.get("platform").unwrap(),
+        referred_sum,
     )?;
 
     Ok(())
 }
+
+pub fn donate_with_referral_code(
+    ctx: Context<DonateWithReferralCode>,
+    _code: String,
+    lamports: u64,
+) -> Result<()> {
+    let accounts = &mut ctx.accounts.donate_with_referer;
+    init_referer_chrt(accounts)?;
+    let referer = Some(accounts.referer_authority.key());
+    let referred_sum = donate_common(&mut accounts.donate, lamports, referer)?;
+    reward_referer(accounts, *ctx.bumps.get("platform").unwrap(), referred_sum)?;
+
+    Ok(())
+}
@@ -0,0 +1,140 @@
+use crate::{error::*, events::*, state::*, utils::*};
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+use core::{mem::size_of, ops::Deref};
+
+#[derive(Accounts)]
+pub struct DonateToken<'info> {
+    #[account(seeds = [b"platform"], bump)]
+    platform: AccountLoader<'info, Platform>,
+    #[account(
+        seeds = [b"campaign", campaign.load()?.id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    campaign: AccountLoader<'info, Campaign>,
+    #[account(seeds = [b"stake_pool", campaign.load()?.id.to_le_bytes().as_ref()], bump)]
+    stake_pool: AccountLoader<'info, StakePool>,
+    mint: Account<'info, Mint>,
+    #[account(
+        mut,
+        seeds = [b"token_vault", campaign.load()?.id.to_le_bytes().as_ref(), mint.key().as_ref()],
+        bump,
+    )]
+    token_vault: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [b"token_donations", campaign.load()?.id.to_le_bytes().as_ref(), mint.key().as_ref()],
+        bump,
+    )]
+    token_donations: AccountLoader<'info, TokenDonations>,
+    #[account(
+        init_if_needed,
+        payer = donor_authority,
+        seeds = [b"token_fee_vault", mint.key().as_ref()],
+        bump,
+        token::authority = platform,
+        token::mint = mint,
+    )]
+    token_fee_vault: Account<'info, TokenAccount>,
+    #[account(mut)]
+    donor_authority: Signer<'info>,
+    #[account(mut, token::mint = mint, token::authority = donor_authority)]
+    donor_token_account: Account<'info, TokenAccount>,
+    #[account(
+        init_if_needed,
+        payer = donor_authority,
+        seeds = [
+            b"token_donations",
+            donor_authority.key().as_ref(),
+            campaign.load()?.id.to_le_bytes().as_ref(),
+            mint.key().as_ref(),
+        ],
+        bump,
+        space = 8 + size_of::<TokenDonations>(),
+    )]
+    donor_token_donations: AccountLoader<'info, TokenDonations>,
+    rent: Sysvar<'info, Rent>,
+    token_program: Program<'info, Token>,
+    system_program: Program<'info, System>,
+}
+
+fn transfer_from_donor<'info>(
+    accounts: &DonateToken<'info>,
+    to: &Account<'info, TokenAccount>,
+    amount: u64,
+) -> Result<()> {
+    let cpi_ctx = CpiContext::new(
+        accounts.token_program.to_account_info(),
+        Transfer {
+            from: accounts.donor_token_account.to_account_info(),
+            to: to.to_account_info(),
+            authority: accounts.donor_authority.to_account_info(),
+        },
+    );
+    token::transfer(cpi_ctx, amount)
+}
+
+/// Keeps the donor's share of the token donations, refundable if the vault is closed with locked
+/// milestones.
+fn record_token_donation(accounts: &DonateToken, amount: u64) -> Result<()> {
+    let donor_token_donations = &mut if accounts
+        .donor_token_donations
+        .to_account_info()
+        .try_borrow_data()?
+        .starts_with(&[0; 8])
+    {
+        let mut donor_token_donations = accounts.donor_token_donations.load_init()?;
+        donor_token_donations.mint = accounts.mint.key();
+        donor_token_donations
+    } else {
+        accounts.donor_token_donations.load_mut()?
+    };
+    donor_token_donations.donations_sum += amount;
+    Ok(())
+}
+
+pub fn donate_token(ctx: Context<DonateToken>, amount: u64) -> Result<()> {
+    let &Platform {
+        fee_basis_points,
+        fee_exemption_limit,
+        ..
+    } = ctx.accounts.platform.load()?.deref();
+
+    let &Campaign { goal, deadline, .. } = ctx.accounts.campaign.load()?.deref();
+    if goal != 0 {
+        return err!(CrowdfundingError::TokenDonationsUnavailable);
+    }
+    if deadline != 0 && Clock::get()?.unix_timestamp as u32 > deadline {
+        return err!(CrowdfundingError::CampaignEnded);
+    }
+
+    let fee = calculate_fee(amount, fee_basis_points);
+    let fee_exempted = is_fee_exempted(
+        ctx.accounts.campaign.load()?.deref(),
+        ctx.accounts.stake_pool.load()?.deref(),
+        fee_exemption_limit,
+    );
+    let token_donations = &mut ctx.accounts.token_donations.load_mut()?;
+    if !fee_exempted {
+        transfer_from_donor(ctx.accounts, &ctx.accounts.token_vault, amount - fee)?;
+        transfer_from_donor(ctx.accounts, &ctx.accounts.token_fee_vault, fee)?;
+        token_donations.donations_sum += amount - fee;
+        record_token_donation(ctx.accounts, amount - fee)?;
+    } else {
+        transfer_from_donor(ctx.accounts, &ctx.accounts.token_vault, amount)?;
+        token_donations.donations_sum += amount;
+        record_token_donation(ctx.accounts, amount)?;
+        token_donations.avoided_fees_sum += fee;
+    }
+
+    emit!(TokenDonationMade {
+        donor: ctx.accounts.donor_authority.key(),
+        campaign_id: ctx.accounts.campaign.load()?.id,
+        mint: ctx.accounts.mint.key(),
+        amount,
+        fee,
+        fee_exempted,
+    });
+
+    Ok(())
+}
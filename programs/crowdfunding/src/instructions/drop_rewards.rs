@@ -1,4 +1,4 @@
-use crate::{config::*, error::*, state::*};
+use crate::{events::*, state::*, utils::*};
 use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Mint, MintTo, Token, TokenAccount};
 
@@ -6,9 +6,24 @@ use anchor_spl::token::{self, Mint, MintTo, Token, TokenAccount};
 pub struct DropRewards<'info> {
     #[account(mut, seeds = [b"platform"], bump)]
     platform: AccountLoader<'info, Platform>,
+    #[account(mut, seeds = [b"seasonal_top"], bump)]
+    seasonal_top: AccountLoader<'info, SeasonalTop>,
+    #[account(mut, seeds = [b"fee_vault"], bump)]
+    fee_vault: AccountLoader<'info, Vault>,
+    #[account(mut)]
+    keeper: Signer<'info>,
+    #[account(
+        init_if_needed,
+        payer = keeper,
+        seeds = [b"season", platform.load()?.season.to_le_bytes().as_ref()],
+        bump,
+        space = Season::space(seasonal_top.load()?.capacity),
+    )]
+    season: AccountLoader<'info, Season>,
     #[account(mut, seeds = [b"chrt_mint"], bump)]
     chrt_mint: Account<'info, Mint>,
     token_program: Program<'info, Token>,
+    system_program: Program<'info, System>,
 }
 
 fn mint_chrt<'info>(
@@ -30,51 +45,67 @@ fn mint_chrt<'info>(
     )
 }
 
+/// Remaining accounts are `Donor` and CHRT token account pairs of the next page of winners.
 pub fn drop_rewards<'info>(ctx: Context<'_, '_, '_, 'info, DropRewards<'info>>) -> Result<()> {
-    let (reward_amount, seasonal_top, seasonal_top_len) = {
+    let (season, ended, bounty, page) = {
         let platform = &mut ctx.accounts.platform.load_mut()?;
+        let donors = ctx.remaining_accounts.iter().step_by(2).collect::<Vec<_>>();
+        let page = reward_seasonal_top_page(
+            platform,
+            ctx.accounts.seasonal_top.as_ref(),
+            ctx.accounts.season.as_ref(),
+            &donors,
+            false,
+            Clock::get()?.unix_timestamp as _,
+        )?;
 
-        if !platform.reward_procedure_is_in_process
-            || platform.donors_recorded != platform.donors_count
-        {
-            return err!(CrowdfundingError::NotAllDonorsRecorded);
-        }
-
-        if ctx.remaining_accounts.len() != 2 * platform.seasonal_top.len() {
-            return err!(CrowdfundingError::IncorrectSeasonalTop);
-        }
-
-        let reward_amount = platform.reward_amount;
-        let seasonal_top = platform.seasonal_top;
-        let seasonal_top_len = seasonal_top
-            .iter()
-            .position(|d| d.donations_sum == 0)
-            .unwrap_or(seasonal_top.len());
-
-        platform.reward_procedure_is_in_process = false;
-        platform.donors_recorded = 0;
-        platform.seasonal_top = [DonorRecord {
-            ..Default::default()
-        }; SEASONAL_TOP_CAPACITY];
-
-        (reward_amount, seasonal_top, seasonal_top_len)
+        (
+            platform.season,
+            !platform.reward_procedure_is_in_process,
+            platform.drop_rewards_bounty,
+            page,
+        )
     };
-    let seasonal_top = &seasonal_top[..seasonal_top_len];
 
-    for (pair, seasonal_top_donor) in ctx.remaining_accounts.chunks_exact(2).zip(seasonal_top) {
-        let donor = AccountLoader::<Donor>::try_from(&pair[0])?;
-        let donor = &mut donor.load_mut()?;
-        if donor.authority != seasonal_top_donor.donor {
-            return err!(CrowdfundingError::IncorrectSeasonalTop);
-        }
-        donor.rewarded_donations_sum = donor.donations_sum;
+    if ctx.remaining_accounts.len() != 2 * page.len() {
+        return err!(AccountNotEnoughKeys);
+    }
 
+    for (pair, record) in ctx.remaining_accounts.chunks_exact(2).zip(&page) {
+        if record.reward == 0 {
+            continue;
+        }
         let donor_chrt = Account::<TokenAccount>::try_from(&pair[1])?;
-        if donor.authority != donor_chrt.owner {
+        if record.donor != donor_chrt.owner {
             return err!(ConstraintTokenOwner);
         }
-        mint_chrt(&ctx, &donor_chrt, reward_amount)?;
+        mint_chrt(&ctx, &donor_chrt, record.reward)?;
+
+        emit!(RewardDropped {
+            donor: record.donor,
+            amount: record.reward,
+        });
     }
 
+    if !ended {
+        return Ok(());
+    }
+
+    let Season {
+        donors_rewarded,
+        rewards_sum,
+        ..
+    } = finish_reward_distribution(
+        &ctx.accounts.season,
+        &ctx.accounts.fee_vault.to_account_info(),
+        &ctx.accounts.keeper.to_account_info(),
+        bounty,
+    )?;
+    emit!(RewardsDropped {
+        season,
+        donors_rewarded,
+        total_amount: rewards_sum,
+    });
+
     Ok(())
 }
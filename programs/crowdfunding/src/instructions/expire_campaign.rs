@@ -0,0 +1,117 @@
+use crate::{error::*, events::*, state::*, utils::*};
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+use core::ops::Deref;
+
+#[derive(Accounts)]
+pub struct ExpireCampaign<'info> {
+    #[account(mut, seeds = [b"platform"], bump)]
+    platform: AccountLoader<'info, Platform>,
+    #[account(mut, seeds = [b"sol_vault"], bump)]
+    sol_vault: AccountLoader<'info, Vault>,
+    #[account(mut, seeds = [b"chrt_mint"], bump)]
+    chrt_mint: Account<'info, Mint>,
+    #[account(
+        mut,
+        close = beneficiary,
+        seeds = [b"campaign", campaign.load()?.id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    campaign: AccountLoader<'info, Campaign>,
+    #[account(mut, address = campaign.load()?.beneficiary)]
+    beneficiary: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"donations", campaign.load()?.id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    total_donations_to_campaign: AccountLoader<'info, Donations>,
+    #[account(
+        mut,
+        seeds = [b"fee_exemption_vault", campaign.load()?.id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    fee_exemption_vault: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [b"liquidation_vault", campaign.load()?.id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    liquidation_vault: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [b"chrt_vaults", campaign.load()?.id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    chrt_vaults: AccountLoader<'info, ChrtVaults>,
+    #[account(mut, address = chrt_vaults.load()?.payer)]
+    chrt_vaults_payer: UncheckedAccount<'info>,
+    token_program: Program<'info, Token>,
+    system_program: Program<'info, System>,
+}
+
+pub fn expire_campaign(ctx: Context<ExpireCampaign>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp as u32;
+    let deadline = ctx.accounts.campaign.load()?.deadline;
+    if deadline == 0 || now <= deadline {
+        return err!(CrowdfundingError::CampaignNotExpired);
+    }
+
+    end_chrt_vaults(
+        &ctx.accounts.token_program,
+        &ctx.accounts.chrt_mint,
+        &ctx.accounts.platform,
+        *ctx.bumps.get("platform").unwrap(),
+        ctx.accounts.campaign.load()?.deref(),
+        &ctx.accounts.chrt_vaults,
+        [
+            &ctx.accounts.fee_exemption_vault,
+            &ctx.accounts.liquidation_vault,
+        ],
+        &ctx.accounts.chrt_vaults_payer.to_account_info(),
+    )?;
+
+    let platform = &mut ctx.accounts.platform.load_mut()?;
+    let campaign = remove_active_campaign(platform, ctx.accounts.campaign.load()?.id)?;
+    check_token_vaults_closed(ctx.accounts.campaign.load()?.deref())?;
+    platform.sum_of_active_campaign_donations -= campaign.donations_sum;
+    let total_donations_to_campaign = &mut ctx.accounts.total_donations_to_campaign.load_mut()?;
+    // donations of a campaign that missed its goal, or the share of its locked milestones, stay in
+    // the vault for donors to refund
+    let (amount, refundable_amount) = if ctx
+        .accounts
+        .campaign
+        .load()?
+        .missed_goal(campaign.donations_sum, now)
+    {
+        (0, total_donations_to_campaign.donations_sum)
+    } else {
+        mark_goal_reached(
+            ctx.accounts.campaign.load()?.deref(),
+            &campaign,
+            total_donations_to_campaign,
+        );
+        release_donations(
+            ctx.accounts.campaign.load()?.deref(),
+            campaign.donations_sum,
+            campaign.withdrawn_sum,
+        )
+    };
+    total_donations_to_campaign.refundable_sum = refundable_amount;
+
+    transfer(
+        &ctx.accounts.sol_vault.to_account_info(),
+        &ctx.accounts.beneficiary.to_account_info(),
+        amount,
+    )?;
+
+    emit!(CampaignStopped {
+        campaign_id: campaign.id,
+        beneficiary: ctx.accounts.beneficiary.key(),
+        amount,
+        refundable_amount,
+        expired: true,
+    });
+
+    Ok(())
+}
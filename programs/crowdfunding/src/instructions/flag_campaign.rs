@@ -0,0 +1,71 @@
+use crate::{error::*, events::*, state::*, utils::*};
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use core::mem::size_of;
+
+#[derive(Accounts)]
+#[instruction(campaign_id: u16)]
+pub struct FlagCampaign<'info> {
+    // only active campaigns can be flagged
+    #[account(mut, seeds = [b"campaign", campaign_id.to_le_bytes().as_ref()], bump)]
+    campaign: AccountLoader<'info, Campaign>,
+    #[account(
+        mut,
+        seeds = [ChrtVault::Liquidation.seed(), campaign_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    liquidation_vault: Account<'info, TokenAccount>,
+    #[account(
+        init_if_needed,
+        payer = flagger,
+        seeds = [
+            ChrtVault::Liquidation.deposit_seed(),
+            campaign_id.to_le_bytes().as_ref(),
+            flagger.key().as_ref(),
+        ],
+        bump,
+        space = 8 + size_of::<ChrtDeposit>(),
+    )]
+    chrt_deposit: AccountLoader<'info, ChrtDeposit>,
+    #[account(mut)]
+    flagger: Signer<'info>,
+    #[account(mut, token::mint = liquidation_vault.mint, token::authority = flagger)]
+    flagger_chrt: Account<'info, TokenAccount>,
+    token_program: Program<'info, Token>,
+    system_program: Program<'info, System>,
+}
+
+pub fn flag_campaign(ctx: Context<FlagCampaign>, campaign_id: u16, amount: u64) -> Result<()> {
+    if amount == 0 {
+        return err!(CrowdfundingError::ZeroDeposit);
+    }
+
+    let cpi_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.flagger_chrt.to_account_info(),
+            to: ctx.accounts.liquidation_vault.to_account_info(),
+            authority: ctx.accounts.flagger.to_account_info(),
+        },
+    );
+    token::transfer(cpi_ctx, amount)?;
+
+    let campaign = &mut ctx.accounts.campaign.load_mut()?;
+    let deposit = record_chrt_deposit(
+        &ctx.accounts.chrt_deposit,
+        campaign,
+        ctx.accounts.flagger.key(),
+        ChrtVault::Liquidation,
+        amount,
+    )?;
+
+    emit!(CampaignFlagged {
+        flagger: ctx.accounts.flagger.key(),
+        campaign_id,
+        amount,
+        deposit,
+        liquidation_deposits_sum: campaign.liquidation_deposits_sum,
+    });
+
+    Ok(())
+}
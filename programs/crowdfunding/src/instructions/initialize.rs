@@ -1,9 +1,10 @@
-use crate::{config::*, state::*};
+use crate::{config::*, events::*, state::*, utils::*};
 use anchor_lang::prelude::*;
 use anchor_spl::token::{Mint, Token};
 use core::mem::size_of;
 
 #[derive(Accounts)]
+#[instruction(seasonal_top_capacity: u16)]
 pub struct Initialize<'info> {
     #[account(
         init,
@@ -15,6 +16,14 @@ pub struct Initialize<'info> {
     platform: AccountLoader<'info, Platform>,
     #[account(mut)]
     platform_authority: Signer<'info>,
+    #[account(
+        init,
+        payer = platform_authority,
+        seeds = [b"seasonal_top"],
+        bump,
+        space = SeasonalTop::space(seasonal_top_capacity),
+    )]
+    seasonal_top: AccountLoader<'info, SeasonalTop>,
     #[account(
         init,
         payer = platform_authority,
@@ -45,21 +54,47 @@ pub struct Initialize<'info> {
     system_program: Program<'info, System>,
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn initialize(
     ctx: Context<Initialize>,
+    seasonal_top_capacity: u16,
     reward_cooldown: u32,
     reward_amount: u64,
+    reward_distribution: RewardDistribution,
+    reward_tiers: Vec<RewardTier>,
     fee_basis_points: u16,
     fee_exemption_limit: u64,
     liquidation_limit: u64,
 ) -> Result<()> {
+    check_fee_basis_points(fee_basis_points)?;
+    check_liquidation_limit(liquidation_limit)?;
+    check_seasonal_top_capacity(seasonal_top_capacity)?;
+
+    ctx.accounts.seasonal_top.load_init()?.capacity = seasonal_top_capacity;
+
     let platform = &mut ctx.accounts.platform.load_init()?;
     platform.authority = ctx.accounts.platform_authority.key();
     platform.reward_cooldown = reward_cooldown;
     platform.reward_amount = reward_amount;
+    platform.reward_distribution = reward_distribution as u8;
+    set_reward_tiers(platform, &reward_tiers)?;
     platform.fee_basis_points = fee_basis_points;
     platform.fee_exemption_limit = fee_exemption_limit;
     platform.liquidation_limit = liquidation_limit;
+    platform.unstake_cooldown = DEFAULT_UNSTAKE_COOLDOWN;
+    platform.referral_rate = DEFAULT_REFERRAL_RATE;
+
+    emit!(PlatformInitialized {
+        authority: platform.authority,
+        seasonal_top_capacity,
+        reward_cooldown,
+        reward_amount,
+        reward_distribution: reward_distribution as u8,
+        reward_tiers,
+        fee_basis_points,
+        fee_exemption_limit,
+        liquidation_limit,
+    });
 
     Ok(())
 }
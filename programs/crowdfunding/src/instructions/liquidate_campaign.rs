@@ -1,6 +1,7 @@
-use crate::{error::*, state::*, utils::*};
+use crate::{error::*, events::*, state::*, utils::*};
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Burn, CloseAccount, Mint, Token, TokenAccount};
+use anchor_spl::token::{Mint, Token, TokenAccount};
+use core::ops::Deref;
 
 #[derive(Accounts)]
 pub struct LiquidateCampaign<'info> {
@@ -14,13 +15,13 @@ pub struct LiquidateCampaign<'info> {
     chrt_mint: Account<'info, Mint>,
     #[account(
         mut,
-        close = campaign_authority,
+        close = beneficiary,
         seeds = [b"campaign", campaign.load()?.id.to_le_bytes().as_ref()],
         bump,
     )]
     campaign: AccountLoader<'info, Campaign>,
-    #[account(mut, address = campaign.load()?.authority)]
-    campaign_authority: UncheckedAccount<'info>,
+    #[account(mut, address = campaign.load()?.beneficiary)]
+    beneficiary: UncheckedAccount<'info>,
     #[account(
         mut,
         seeds = [b"fee_exemption_vault", campaign.load()?.id.to_le_bytes().as_ref()],
@@ -33,68 +34,74 @@ pub struct LiquidateCampaign<'info> {
         bump,
     )]
     liquidation_vault: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        close = chrt_vaults_payer,
+        seeds = [b"chrt_vaults", campaign.load()?.id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    chrt_vaults: AccountLoader<'info, ChrtVaults>,
+    #[account(mut, address = chrt_vaults.load()?.payer)]
+    chrt_vaults_payer: UncheckedAccount<'info>,
     token_program: Program<'info, Token>,
     system_program: Program<'info, System>,
 }
 
-fn close_chrt_vaults(ctx: &Context<LiquidateCampaign>) -> Result<()> {
-    let signer: &[&[&[u8]]] = &[&[b"platform", &[*ctx.bumps.get("platform").unwrap()]]];
-
-    for vault in [
-        &ctx.accounts.fee_exemption_vault,
-        &ctx.accounts.liquidation_vault,
-    ] {
-        let cpi_ctx = CpiContext::new_with_signer(
-            ctx.accounts.token_program.to_account_info(),
-            Burn {
-                mint: ctx.accounts.chrt_mint.to_account_info(),
-                from: vault.to_account_info(),
-                authority: ctx.accounts.platform.to_account_info(),
-            },
-            signer,
-        );
-        token::burn(cpi_ctx, vault.amount)?;
-
-        let cpi_ctx = CpiContext::new_with_signer(
-            ctx.accounts.token_program.to_account_info(),
-            CloseAccount {
-                account: vault.to_account_info(),
-                destination: ctx.accounts.campaign_authority.to_account_info(),
-                authority: ctx.accounts.platform.to_account_info(),
-            },
-            signer,
-        );
-        token::close_account(cpi_ctx)?;
-    }
-
-    Ok(())
-}
-
 pub fn liquidate_campaign(ctx: Context<LiquidateCampaign>) -> Result<()> {
-    if ctx.accounts.liquidation_vault.amount < ctx.accounts.platform.load()?.liquidation_limit {
+    let now = Clock::get()?.unix_timestamp as u32;
+    {
+        let platform = ctx.accounts.platform.load()?;
+        let campaign = ctx.accounts.campaign.load()?;
+        let i = platform.active_campaigns[..platform.active_campaigns_count as usize]
+            .binary_search_by_key(&{ campaign.id }, |c| c.id)
+            .map_err(|_| CrowdfundingError::CampaignInactive)?;
+        // refundable donations are not the campaign's to lose
+        if campaign.missed_goal(platform.active_campaigns[i].donations_sum, now) {
+            return err!(CrowdfundingError::CampaignMissedGoal);
+        }
+    }
+    if ctx.accounts.campaign.load()?.liquidation_deposits_sum
+        < ctx.accounts.platform.load()?.liquidation_limit
+    {
         return err!(CrowdfundingError::NotEnoughCHRTInVault);
     }
-    close_chrt_vaults(&ctx)?;
+    check_token_vaults_closed(ctx.accounts.campaign.load()?.deref())?;
+    close_chrt_vaults(
+        &ctx.accounts.token_program,
+        &ctx.accounts.chrt_mint,
+        &ctx.accounts.platform,
+        *ctx.bumps.get("platform").unwrap(),
+        [
+            &ctx.accounts.fee_exemption_vault,
+            &ctx.accounts.liquidation_vault,
+        ],
+        &ctx.accounts.chrt_vaults_payer.to_account_info(),
+    )?;
 
     let platform = &mut ctx.accounts.platform.load_mut()?;
-    let len = platform.active_campaigns_count as usize;
-    let id = ctx.accounts.campaign.load()?.id;
-    let i = platform.active_campaigns[..len]
-        .binary_search_by_key(&id, |c| c.id)
-        .map_err(|_| CrowdfundingError::CampaignInactive)?;
-    let campaign = platform.active_campaigns[i];
-    platform.active_campaigns[i] = Default::default();
-    platform.active_campaigns[i..len].rotate_left(1);
-    platform.active_campaigns_count -= 1;
+    let campaign = remove_active_campaign(platform, ctx.accounts.campaign.load()?.id)?;
     let liquidation_amount = campaign.donations_sum - campaign.withdrawn_sum;
     platform.liquidations_sum += liquidation_amount;
 
-    let remaining_sum = platform.sum_of_active_campaign_donations - campaign.donations_sum;
+    let len = platform.active_campaigns_count as usize;
+    let active_campaigns = &mut platform.active_campaigns[..len];
+    let remaining_sum: u64 = active_campaigns
+        .iter()
+        .filter(|c| c.takes_liquidation_share())
+        .map(|c| c.donations_sum)
+        .sum();
     let mut distributed_sum = 0;
-    for active_campaign in &mut platform.active_campaigns {
-        let share = liquidation_amount * active_campaign.donations_sum / remaining_sum;
-        active_campaign.donations_sum += share;
-        distributed_sum += share;
+    // without campaigns to take a share, the whole amount goes to the fee vault
+    if remaining_sum != 0 {
+        for active_campaign in active_campaigns
+            .iter_mut()
+            .filter(|c| c.takes_liquidation_share())
+        {
+            let share = (liquidation_amount as u128 * active_campaign.donations_sum as u128
+                / remaining_sum as u128) as u64;
+            active_campaign.donations_sum += share;
+            distributed_sum += share;
+        }
     }
 
     let not_distributed = liquidation_amount - distributed_sum;
@@ -105,5 +112,11 @@ pub fn liquidate_campaign(ctx: Context<LiquidateCampaign>) -> Result<()> {
         not_distributed,
     )?;
 
+    emit!(CampaignLiquidated {
+        campaign_id: campaign.id,
+        amount: liquidation_amount,
+        distributed_amount: distributed_sum,
+    });
+
     Ok(())
 }
@@ -0,0 +1,72 @@
+use crate::{error::*, events::*, state::*, utils::*};
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+#[derive(Accounts)]
+pub struct LiquidateTokenVault<'info> {
+    #[account(seeds = [b"platform"], bump)]
+    platform: AccountLoader<'info, Platform>,
+    #[account(
+        mut,
+        seeds = [b"campaign", campaign.load()?.id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    campaign: AccountLoader<'info, Campaign>,
+    #[account(mut, address = campaign.load()?.beneficiary)]
+    beneficiary: UncheckedAccount<'info>,
+    mint: Account<'info, Mint>,
+    #[account(
+        mut,
+        seeds = [b"token_vault", campaign.load()?.id.to_le_bytes().as_ref(), mint.key().as_ref()],
+        bump,
+    )]
+    token_vault: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        close = beneficiary,
+        seeds = [b"token_donations", campaign.load()?.id.to_le_bytes().as_ref(), mint.key().as_ref()],
+        bump,
+    )]
+    token_donations: AccountLoader<'info, TokenDonations>,
+    #[account(mut, seeds = [b"token_fee_vault", mint.key().as_ref()], bump)]
+    token_fee_vault: Account<'info, TokenAccount>,
+    token_program: Program<'info, Token>,
+}
+
+/// Unlike SOL donations of a liquidated campaign, which are redistributed among the active
+/// campaigns, token donations go to the token fee vault: the other campaigns may have no vault
+/// for the mint to take their share in.
+pub fn liquidate_token_vault(ctx: Context<LiquidateTokenVault>) -> Result<()> {
+    if ctx.accounts.campaign.load()?.liquidation_deposits_sum
+        < ctx.accounts.platform.load()?.liquidation_limit
+    {
+        return err!(CrowdfundingError::NotEnoughCHRTInVault);
+    }
+    ctx.accounts.campaign.load_mut()?.token_vaults_count -= 1;
+
+    let amount = ctx.accounts.token_vault.amount;
+    let platform_bump = *ctx.bumps.get("platform").unwrap();
+    transfer_tokens(
+        &ctx.accounts.token_program,
+        &ctx.accounts.platform,
+        platform_bump,
+        &ctx.accounts.token_vault,
+        &ctx.accounts.token_fee_vault,
+        amount,
+    )?;
+    close_token_account(
+        &ctx.accounts.token_program,
+        &ctx.accounts.platform,
+        platform_bump,
+        &ctx.accounts.token_vault,
+        &ctx.accounts.beneficiary.to_account_info(),
+    )?;
+
+    emit!(TokenVaultLiquidated {
+        campaign_id: ctx.accounts.campaign.load()?.id,
+        mint: ctx.accounts.mint.key(),
+        amount,
+    });
+
+    Ok(())
+}
@@ -1,16 +1,54 @@
 pub use crate::instructions::{
-    donate::*, drop_rewards::*, initialize::*, liquidate_campaign::*, record_donors::*,
-    register_donor::*, start_campaign::*, stop_campaign::*, withdraw_donations::*,
-    withdraw_fees::*,
+    abort_reward_procedure::*, accept_campaign_authority::*, accept_platform_authority::*,
+    cancel_campaign_authority::*, cancel_platform_authority::*, claim_reward::*,
+    close_token_vault::*, commit_rewards::*, credit_donations::*, donate::*, donate_token::*,
+    drop_rewards::*, expire_campaign::*, flag_campaign::*, initialize::*, liquidate_campaign::*,
+    liquidate_token_vault::*, open_token_vault::*, propose_campaign_authority::*,
+    propose_platform_authority::*, record_donors::*, refund_donations::*,
+    refund_token_donations::*, register_donor::*, register_referral_code::*, request_unstake::*,
+    resize_seasonal_top::*, stake::*, start_campaign::*, stop_campaign::*, support_campaign::*,
+    unstake::*, update_campaign_metadata::*, update_platform_config::*, vote_milestone::*,
+    withdraw_chrt_deposit::*, withdraw_donations::*, withdraw_fees::*, withdraw_token_donations::*,
+    withdraw_token_fees::*,
 };
 
+pub mod abort_reward_procedure;
+pub mod accept_campaign_authority;
+pub mod accept_platform_authority;
+pub mod cancel_campaign_authority;
+pub mod cancel_platform_authority;
+pub mod claim_reward;
+pub mod close_token_vault;
+pub mod commit_rewards;
+pub mod credit_donations;
 pub mod donate;
+pub mod donate_token;
 pub mod drop_rewards;
+pub mod expire_campaign;
+pub mod flag_campaign;
 pub mod initialize;
 pub mod liquidate_campaign;
+pub mod liquidate_token_vault;
+pub mod open_token_vault;
+pub mod propose_campaign_authority;
+pub mod propose_platform_authority;
 pub mod record_donors;
+pub mod refund_donations;
+pub mod refund_token_donations;
 pub mod register_donor;
+pub mod register_referral_code;
+pub mod request_unstake;
+pub mod resize_seasonal_top;
+pub mod stake;
 pub mod start_campaign;
 pub mod stop_campaign;
+pub mod support_campaign;
+pub mod unstake;
+pub mod update_campaign_metadata;
+pub mod update_platform_config;
+pub mod vote_milestone;
+pub mod withdraw_chrt_deposit;
 pub mod withdraw_donations;
 pub mod withdraw_fees;
+pub mod withdraw_token_donations;
+pub mod withdraw_token_fees;
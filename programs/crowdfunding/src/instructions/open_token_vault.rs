@@ -0,0 +1,55 @@
+use crate::{config::*, error::*, events::*, state::*};
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+use core::mem::size_of;
+
+#[derive(Accounts)]
+pub struct OpenTokenVault<'info> {
+    #[account(seeds = [b"platform"], bump)]
+    platform: AccountLoader<'info, Platform>,
+    #[account(
+        mut,
+        seeds = [b"campaign", campaign.load()?.id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    campaign: AccountLoader<'info, Campaign>,
+    #[account(mut, address = campaign.load()?.authority)]
+    campaign_authority: Signer<'info>,
+    mint: Account<'info, Mint>,
+    #[account(
+        init,
+        payer = campaign_authority,
+        seeds = [b"token_vault", campaign.load()?.id.to_le_bytes().as_ref(), mint.key().as_ref()],
+        bump,
+        token::authority = platform,
+        token::mint = mint,
+    )]
+    token_vault: Account<'info, TokenAccount>,
+    #[account(
+        init,
+        payer = campaign_authority,
+        seeds = [b"token_donations", campaign.load()?.id.to_le_bytes().as_ref(), mint.key().as_ref()],
+        bump,
+        space = 8 + size_of::<TokenDonations>(),
+    )]
+    token_donations: AccountLoader<'info, TokenDonations>,
+    rent: Sysvar<'info, Rent>,
+    token_program: Program<'info, Token>,
+    system_program: Program<'info, System>,
+}
+
+pub fn open_token_vault(ctx: Context<OpenTokenVault>) -> Result<()> {
+    let campaign = &mut ctx.accounts.campaign.load_mut()?;
+    if campaign.token_vaults_count == TOKEN_VAULTS_CAPACITY {
+        return err!(CrowdfundingError::TokenVaultsLimit);
+    }
+    campaign.token_vaults_count += 1;
+    ctx.accounts.token_donations.load_init()?.mint = ctx.accounts.mint.key();
+
+    emit!(TokenVaultOpened {
+        campaign_id: campaign.id,
+        mint: ctx.accounts.mint.key(),
+    });
+
+    Ok(())
+}
@@ -0,0 +1,30 @@
+use crate::{events::*, state::*};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct ProposeCampaignAuthority<'info> {
+    #[account(
+        mut,
+        seeds = [b"campaign", campaign.load()?.id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    campaign: AccountLoader<'info, Campaign>,
+    #[account(address = campaign.load()?.authority)]
+    campaign_authority: Signer<'info>,
+}
+
+pub fn propose_campaign_authority(
+    ctx: Context<ProposeCampaignAuthority>,
+    new_authority: Pubkey,
+) -> Result<()> {
+    let campaign = &mut ctx.accounts.campaign.load_mut()?;
+    campaign.pending_authority = new_authority;
+
+    emit!(CampaignAuthorityProposed {
+        campaign_id: campaign.id,
+        authority: campaign.authority,
+        pending_authority: new_authority,
+    });
+
+    Ok(())
+}
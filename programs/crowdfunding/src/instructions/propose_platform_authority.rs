@@ -0,0 +1,25 @@
+use crate::{events::*, state::*};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct ProposePlatformAuthority<'info> {
+    #[account(mut, seeds = [b"platform"], bump)]
+    platform: AccountLoader<'info, Platform>,
+    #[account(address = platform.load()?.authority)]
+    platform_authority: Signer<'info>,
+}
+
+pub fn propose_platform_authority(
+    ctx: Context<ProposePlatformAuthority>,
+    new_authority: Pubkey,
+) -> Result<()> {
+    let platform = &mut ctx.accounts.platform.load_mut()?;
+    platform.pending_authority = new_authority;
+
+    emit!(PlatformAuthorityProposed {
+        authority: platform.authority,
+        pending_authority: new_authority,
+    });
+
+    Ok(())
+}
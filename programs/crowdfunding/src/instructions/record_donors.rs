@@ -1,21 +1,42 @@
-use crate::{error::*, state::*, utils::*};
+use crate::{error::*, events::*, state::*, utils::*};
 use anchor_lang::prelude::*;
 
 #[derive(Accounts)]
 pub struct RecordDonors<'info> {
     #[account(mut, seeds = [b"platform"], bump)]
     platform: AccountLoader<'info, Platform>,
+    #[account(mut, seeds = [b"seasonal_top"], bump)]
+    seasonal_top: AccountLoader<'info, SeasonalTop>,
+    #[account(mut, seeds = [b"fee_vault"], bump)]
+    fee_vault: AccountLoader<'info, Vault>,
+    #[account(mut)]
+    keeper: Signer<'info>,
 }
 
 pub fn record_donors(ctx: Context<RecordDonors>) -> Result<()> {
     let now = Clock::get()?.unix_timestamp as _;
     let platform = &mut ctx.accounts.platform.load_mut()?;
+    let (header, mut seasonal_top) =
+        load_with_records::<SeasonalTop, SeasonalTopRecord>(ctx.accounts.seasonal_top.as_ref())?;
+    // a procedure stuck past its timeout is restarted by the next keeper, skipping the cooldown
+    if platform.reward_procedure_is_in_process
+        && platform.reward_procedure_timed_out(now)
+        && header.cursor == 0
+    {
+        emit!(RewardProcedureAborted {
+            authority: ctx.accounts.keeper.key(),
+            donors_recorded: platform.donors_recorded,
+        });
+        reset_reward_procedure(platform, &mut seasonal_top);
+    } else if !platform.reward_procedure_is_in_process
+        && now - platform.last_reward_procedure_ts < platform.reward_cooldown
+    {
+        return err!(CrowdfundingError::RewardCooldown);
+    }
     if !platform.reward_procedure_is_in_process {
-        if now - platform.last_reward_procedure_ts < platform.reward_cooldown {
-            return err!(CrowdfundingError::RewardCooldown);
-        }
         platform.reward_procedure_is_in_process = true;
         platform.last_reward_procedure_ts = now;
+        platform.season += 1;
     }
 
     for i in 0..ctx.remaining_accounts.len() {
@@ -26,20 +47,34 @@ pub fn record_donors(ctx: Context<RecordDonors>) -> Result<()> {
         }
     }
 
+    if header.cursor != 0 {
+        return err!(CrowdfundingError::RewardDistributionInProgress);
+    }
+    // only donors whose donations this season paid fees covering the bounty are paid for, or
+    // keepers would farm it with dust donors
+    let mut paid_donors = 0;
     for donor in ctx.remaining_accounts {
-        let donor = AccountLoader::<Donor>::try_from(&donor)?;
+        let donor = AccountLoader::<Donor>::try_from(donor)?;
         let donor = &mut donor.load_mut()?;
         if donor.last_record_ts >= platform.last_reward_procedure_ts {
             return err!(CrowdfundingError::CannotRecordTwice);
         }
         donor.last_record_ts = now;
+        snapshot_donations(platform, donor);
 
-        if donor.donations_sum != donor.rewarded_donations_sum {
+        if calculate_fee(donor.season_donations_sum, platform.fee_basis_points)
+            >= platform.record_donor_bounty
+            && donor.season_donations_sum != 0
+        {
+            paid_donors += 1;
+        }
+        if donor.recorded_donations_sum != donor.rewarded_donations_sum {
             add_to_top(
-                &mut platform.seasonal_top,
-                DonorRecord {
+                &mut seasonal_top,
+                SeasonalTopRecord {
                     donor: donor.authority,
-                    donations_sum: donor.donations_sum,
+                    donations_sum: donor.recorded_donations_sum,
+                    unrewarded_sum: donor.recorded_donations_sum - donor.rewarded_donations_sum,
                 },
             );
         }
@@ -47,5 +82,21 @@ pub fn record_donors(ctx: Context<RecordDonors>) -> Result<()> {
 
     platform.donors_recorded += ctx.remaining_accounts.len() as u32;
 
+    emit!(DonorsRecorded {
+        season: platform.season,
+        donors_recorded: platform.donors_recorded,
+        donors_count: platform.donors_count,
+    });
+
+    let bounty = pay_bounty(
+        &ctx.accounts.fee_vault.to_account_info(),
+        &ctx.accounts.keeper.to_account_info(),
+        platform.record_donor_bounty * paid_donors,
+    )?;
+    emit!(BountyPaid {
+        keeper: ctx.accounts.keeper.key(),
+        amount: bounty,
+    });
+
     Ok(())
 }
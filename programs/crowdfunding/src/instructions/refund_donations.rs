@@ -0,0 +1,61 @@
+use crate::{error::*, events::*, state::*, utils::*};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(campaign_id: u16)]
+pub struct RefundDonations<'info> {
+    #[account(mut, seeds = [b"platform"], bump)]
+    platform: AccountLoader<'info, Platform>,
+    #[account(mut, seeds = [b"sol_vault"], bump)]
+    sol_vault: AccountLoader<'info, Vault>,
+    // outlives the campaign, so that donations are refunded after it expires
+    #[account(
+        mut,
+        seeds = [b"donations", campaign_id.to_le_bytes().as_ref()],
+        bump,
+        constraint = total_donations_to_campaign.load()?.refundable_sum != 0
+            @ CrowdfundingError::RefundUnavailable,
+    )]
+    total_donations_to_campaign: AccountLoader<'info, Donations>,
+    #[account(mut)]
+    donor_authority: Signer<'info>,
+    #[account(
+        mut,
+        close = donor_authority,
+        seeds = [b"donations", donor_authority.key().as_ref(), campaign_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    donor_donations_to_campaign: AccountLoader<'info, Donations>,
+}
+
+pub fn refund_donations(ctx: Context<RefundDonations>, campaign_id: u16) -> Result<()> {
+    let donations_sum = ctx
+        .accounts
+        .donor_donations_to_campaign
+        .load()?
+        .donations_sum;
+    let total_donations_to_campaign = &mut ctx.accounts.total_donations_to_campaign.load_mut()?;
+    let lamports = refund_share(
+        donations_sum,
+        total_donations_to_campaign.refundable_sum,
+        total_donations_to_campaign.donations_sum,
+    );
+    total_donations_to_campaign.refundable_sum -= lamports;
+    total_donations_to_campaign.donations_sum -= donations_sum;
+    ctx.accounts.platform.load_mut()?.sum_of_all_donations -= lamports;
+    // refunds never touch the donor's recorded sums, which seasons rely on to only grow
+
+    transfer(
+        &ctx.accounts.sol_vault.to_account_info(),
+        &ctx.accounts.donor_authority.to_account_info(),
+        lamports,
+    )?;
+
+    emit!(DonationsRefunded {
+        donor: ctx.accounts.donor_authority.key(),
+        campaign_id,
+        amount: lamports,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,92 @@
+use crate::{error::*, events::*, state::*, utils::*};
+use anchor_lang::{prelude::*, AccountsClose};
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+#[derive(Accounts)]
+#[instruction(campaign_id: u16)]
+pub struct RefundTokenDonations<'info> {
+    #[account(seeds = [b"platform"], bump)]
+    platform: AccountLoader<'info, Platform>,
+    mint: Account<'info, Mint>,
+    // outlives the campaign, so that donations are refunded after it is stopped
+    #[account(
+        mut,
+        seeds = [b"token_vault", campaign_id.to_le_bytes().as_ref(), mint.key().as_ref()],
+        bump,
+    )]
+    token_vault: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [b"token_donations", campaign_id.to_le_bytes().as_ref(), mint.key().as_ref()],
+        bump,
+        constraint = token_donations.load()?.refundable_sum != 0
+            @ CrowdfundingError::RefundUnavailable,
+    )]
+    token_donations: AccountLoader<'info, TokenDonations>,
+    #[account(mut, address = token_donations.load()?.beneficiary)]
+    beneficiary: UncheckedAccount<'info>,
+    #[account(mut)]
+    donor_authority: Signer<'info>,
+    #[account(
+        mut,
+        close = donor_authority,
+        seeds = [
+            b"token_donations",
+            donor_authority.key().as_ref(),
+            campaign_id.to_le_bytes().as_ref(),
+            mint.key().as_ref(),
+        ],
+        bump,
+    )]
+    donor_token_donations: AccountLoader<'info, TokenDonations>,
+    #[account(mut, token::mint = mint, token::authority = donor_authority)]
+    donor_token_account: Account<'info, TokenAccount>,
+    token_program: Program<'info, Token>,
+}
+
+pub fn refund_token_donations(ctx: Context<RefundTokenDonations>, campaign_id: u16) -> Result<()> {
+    let donations_sum = ctx.accounts.donor_token_donations.load()?.donations_sum;
+    let (amount, refunds_left) = {
+        let token_donations = &mut ctx.accounts.token_donations.load_mut()?;
+        let amount = refund_share(
+            donations_sum,
+            token_donations.refundable_sum,
+            token_donations.donations_sum,
+        );
+        token_donations.refundable_sum -= amount;
+        token_donations.donations_sum -= donations_sum;
+        (amount, token_donations.refundable_sum)
+    };
+
+    let platform_bump = *ctx.bumps.get("platform").unwrap();
+    transfer_tokens(
+        &ctx.accounts.token_program,
+        &ctx.accounts.platform,
+        platform_bump,
+        &ctx.accounts.token_vault,
+        &ctx.accounts.donor_token_account,
+        amount,
+    )?;
+    // the last refund empties the vault left open for refunds, unless tokens were sent to it directly
+    if refunds_left == 0 && ctx.accounts.token_vault.amount == amount {
+        close_token_account(
+            &ctx.accounts.token_program,
+            &ctx.accounts.platform,
+            platform_bump,
+            &ctx.accounts.token_vault,
+            &ctx.accounts.beneficiary.to_account_info(),
+        )?;
+        ctx.accounts
+            .token_donations
+            .close(ctx.accounts.beneficiary.to_account_info())?;
+    }
+
+    emit!(TokenDonationsRefunded {
+        donor: ctx.accounts.donor_authority.key(),
+        campaign_id,
+        mint: ctx.accounts.mint.key(),
+        amount,
+    });
+
+    Ok(())
+}
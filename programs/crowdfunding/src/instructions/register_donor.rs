@@ -1,4 +1,4 @@
-use crate::state::*;
+use crate::{events::*, state::*};
 use anchor_lang::prelude::*;
 use core::mem::size_of;
 
@@ -26,5 +26,9 @@ pub fn register_donor(ctx: Context<RegisterDonor>) -> Result<()> {
     let donor = &mut ctx.accounts.donor.load_init()?;
     donor.authority = ctx.accounts.donor_authority.key();
 
+    emit!(DonorRegistered {
+        donor: donor.authority,
+    });
+
     Ok(())
 }
@@ -0,0 +1,45 @@
+use crate::{error::*, events::*, state::*, utils::*};
+use anchor_lang::prelude::*;
+use core::mem::size_of;
+
+#[derive(Accounts)]
+#[instruction(code: String)]
+pub struct RegisterReferralCode<'info> {
+    #[account(
+        mut,
+        seeds = [b"donor", donor_authority.key().as_ref()],
+        bump,
+        constraint = donor.load()?.referral_code[0] == 0
+            @ CrowdfundingError::ReferralCodeAlreadyRegistered,
+    )]
+    donor: AccountLoader<'info, Donor>,
+    #[account(
+        init,
+        payer = donor_authority,
+        seeds = [b"referral_code", referral_code_seed(&code)?],
+        bump,
+        space = 8 + size_of::<ReferralCode>(),
+    )]
+    referral_code: AccountLoader<'info, ReferralCode>,
+    #[account(mut)]
+    donor_authority: Signer<'info>,
+    system_program: Program<'info, System>,
+}
+
+pub fn register_referral_code(ctx: Context<RegisterReferralCode>, code: String) -> Result<()> {
+    let donor = &mut ctx.accounts.donor.load_mut()?;
+    copy_str(
+        &mut donor.referral_code,
+        &code,
+        CrowdfundingError::InvalidReferralCode,
+    )?;
+    let referral_code = &mut ctx.accounts.referral_code.load_init()?;
+    referral_code.donor = donor.authority;
+
+    emit!(ReferralCodeRegistered {
+        donor: referral_code.donor,
+        code,
+    });
+
+    Ok(())
+}
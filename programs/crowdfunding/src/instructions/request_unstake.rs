@@ -0,0 +1,44 @@
+use crate::{error::*, events::*, state::*};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(campaign_id: u16)]
+pub struct RequestUnstake<'info> {
+    #[account(seeds = [b"platform"], bump)]
+    platform: AccountLoader<'info, Platform>,
+    // outlives the campaign, so that stakes can be withdrawn after it stops
+    #[account(mut, seeds = [b"stake_pool", campaign_id.to_le_bytes().as_ref()], bump)]
+    stake_pool: AccountLoader<'info, StakePool>,
+    #[account(
+        mut,
+        seeds = [b"stake", campaign_id.to_le_bytes().as_ref(), staker.key().as_ref()],
+        bump,
+    )]
+    stake_position: AccountLoader<'info, StakePosition>,
+    staker: Signer<'info>,
+}
+
+pub fn request_unstake(ctx: Context<RequestUnstake>, campaign_id: u16, amount: u64) -> Result<()> {
+    let stake_position = &mut ctx.accounts.stake_position.load_mut()?;
+    if amount > stake_position.amount {
+        return err!(CrowdfundingError::InsufficientStake);
+    }
+
+    // the whole unstaking amount waits for the cooldown of the latest request
+    stake_position.amount -= amount;
+    stake_position.unstaking_amount += amount;
+    stake_position.unlock_ts =
+        Clock::get()?.unix_timestamp as u32 + ctx.accounts.platform.load()?.unstake_cooldown;
+    let stake_pool = &mut ctx.accounts.stake_pool.load_mut()?;
+    stake_pool.staked_sum -= amount;
+
+    emit!(UnstakeRequested {
+        staker: stake_position.staker,
+        campaign_id,
+        amount,
+        unlock_ts: stake_position.unlock_ts,
+        staked_sum: stake_pool.staked_sum,
+    });
+
+    Ok(())
+}
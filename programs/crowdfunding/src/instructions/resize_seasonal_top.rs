@@ -0,0 +1,37 @@
+use crate::{error::*, events::*, state::*, utils::*};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(capacity: u16)]
+pub struct ResizeSeasonalTop<'info> {
+    #[account(seeds = [b"platform"], bump)]
+    platform: AccountLoader<'info, Platform>,
+    #[account(
+        mut,
+        seeds = [b"seasonal_top"],
+        bump,
+        realloc = SeasonalTop::space(capacity),
+        realloc::payer = platform_authority,
+        realloc::zero = true,
+    )]
+    seasonal_top: AccountLoader<'info, SeasonalTop>,
+    #[account(mut, address = platform.load()?.authority)]
+    platform_authority: Signer<'info>,
+    system_program: Program<'info, System>,
+}
+
+pub fn resize_seasonal_top(ctx: Context<ResizeSeasonalTop>, capacity: u16) -> Result<()> {
+    check_seasonal_top_capacity(capacity)?;
+    if ctx.accounts.platform.load()?.reward_procedure_is_in_process {
+        return err!(CrowdfundingError::RewardProcedureInProcess);
+    }
+
+    ctx.accounts.seasonal_top.load_mut()?.capacity = capacity;
+
+    emit!(SeasonalTopResized {
+        authority: ctx.accounts.platform_authority.key(),
+        capacity,
+    });
+
+    Ok(())
+}
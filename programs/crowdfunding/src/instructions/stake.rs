@@ -0,0 +1,81 @@
+use crate::{events::*, state::*};
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+use core::mem::size_of;
+
+#[derive(Accounts)]
+#[instruction(campaign_id: u16)]
+pub struct Stake<'info> {
+    #[account(seeds = [b"platform"], bump)]
+    platform: AccountLoader<'info, Platform>,
+    // only active campaigns can be staked toward
+    #[account(seeds = [b"campaign", campaign_id.to_le_bytes().as_ref()], bump)]
+    campaign: AccountLoader<'info, Campaign>,
+    #[account(mut, seeds = [b"stake_pool", campaign_id.to_le_bytes().as_ref()], bump)]
+    stake_pool: AccountLoader<'info, StakePool>,
+    #[account(
+        init_if_needed,
+        payer = staker,
+        seeds = [b"stake", campaign_id.to_le_bytes().as_ref(), staker.key().as_ref()],
+        bump,
+        space = 8 + size_of::<StakePosition>(),
+    )]
+    stake_position: AccountLoader<'info, StakePosition>,
+    #[account(
+        init_if_needed,
+        payer = staker,
+        seeds = [b"stake_vault"],
+        bump,
+        token::authority = platform,
+        token::mint = chrt_mint,
+    )]
+    stake_vault: Account<'info, TokenAccount>,
+    #[account(seeds = [b"chrt_mint"], bump)]
+    chrt_mint: Account<'info, Mint>,
+    #[account(mut)]
+    staker: Signer<'info>,
+    #[account(mut, token::mint = chrt_mint, token::authority = staker)]
+    staker_chrt: Account<'info, TokenAccount>,
+    rent: Sysvar<'info, Rent>,
+    token_program: Program<'info, Token>,
+    system_program: Program<'info, System>,
+}
+
+pub fn stake(ctx: Context<Stake>, campaign_id: u16, amount: u64) -> Result<()> {
+    let cpi_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.staker_chrt.to_account_info(),
+            to: ctx.accounts.stake_vault.to_account_info(),
+            authority: ctx.accounts.staker.to_account_info(),
+        },
+    );
+    token::transfer(cpi_ctx, amount)?;
+
+    let stake_position = &mut if ctx
+        .accounts
+        .stake_position
+        .to_account_info()
+        .try_borrow_data()?
+        .starts_with(&[0; 8])
+    {
+        let mut stake_position = ctx.accounts.stake_position.load_init()?;
+        stake_position.staker = ctx.accounts.staker.key();
+        stake_position.campaign_id = campaign_id;
+        stake_position
+    } else {
+        ctx.accounts.stake_position.load_mut()?
+    };
+    stake_position.amount += amount;
+    let stake_pool = &mut ctx.accounts.stake_pool.load_mut()?;
+    stake_pool.staked_sum += amount;
+
+    emit!(Staked {
+        staker: stake_position.staker,
+        campaign_id,
+        amount,
+        staked_sum: stake_pool.staked_sum,
+    });
+
+    Ok(())
+}
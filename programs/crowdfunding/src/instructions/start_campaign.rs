@@ -1,4 +1,4 @@
-use crate::{config::*, error::*, state::*};
+use crate::{config::*, error::*, events::*, state::*, utils::*};
 use anchor_lang::prelude::*;
 use anchor_spl::token::{Mint, Token, TokenAccount};
 use core::mem::size_of;
@@ -45,12 +45,54 @@ pub struct StartCampaign<'info> {
         token::mint = chrt_mint,
     )]
     liquidation_vault: Account<'info, TokenAccount>,
+    #[account(
+        init,
+        payer = campaign_authority,
+        seeds = [b"chrt_vaults", platform.load()?.campaigns_count.to_le_bytes().as_ref()],
+        bump,
+        space = 8 + size_of::<ChrtVaults>(),
+    )]
+    chrt_vaults: AccountLoader<'info, ChrtVaults>,
+    #[account(
+        init,
+        payer = campaign_authority,
+        seeds = [b"stake_pool", platform.load()?.campaigns_count.to_le_bytes().as_ref()],
+        bump,
+        space = 8 + size_of::<StakePool>(),
+    )]
+    stake_pool: AccountLoader<'info, StakePool>,
     rent: Sysvar<'info, Rent>,
     token_program: Program<'info, Token>,
     system_program: Program<'info, System>,
 }
 
-pub fn start_campaign(ctx: Context<StartCampaign>) -> Result<()> {
+#[allow(clippy::too_many_arguments)]
+pub fn start_campaign(
+    ctx: Context<StartCampaign>,
+    beneficiary: Pubkey,
+    title: String,
+    uri: String,
+    category: CampaignCategory,
+    goal: Option<u64>,
+    deadline: Option<u32>,
+    milestones: Vec<u16>,
+) -> Result<()> {
+    if milestones.len() > MILESTONES_CAPACITY
+        || milestones.contains(&0)
+        || !milestones.is_empty() && milestones.iter().map(|&m| m as u64).sum::<u64>() != 10000
+    {
+        return err!(CrowdfundingError::InvalidMilestones);
+    }
+    let (goal, deadline) = match (goal, deadline) {
+        (None, None) => (0, 0),
+        (None, Some(deadline)) => (0, deadline),
+        (Some(goal), Some(deadline)) if goal > 0 => (goal, deadline),
+        _ => return err!(CrowdfundingError::InvalidFundingGoal),
+    };
+    if deadline != 0 && deadline <= Clock::get()?.unix_timestamp as u32 {
+        return err!(CrowdfundingError::InvalidDeadline);
+    }
+
     let platform = &mut ctx.accounts.platform.load_mut()?;
     if ACTIVE_CAMPAIGNS_CAPACITY as u16 <= platform.active_campaigns_count {
         return err!(CrowdfundingError::ActiveCampaignsLimit);
@@ -59,6 +101,7 @@ pub fn start_campaign(ctx: Context<StartCampaign>) -> Result<()> {
     let len = platform.active_campaigns_count as usize;
     platform.active_campaigns[len] = CampaignRecord {
         id,
+        goal,
         ..Default::default()
     };
     platform.campaigns_count += 1;
@@ -66,7 +109,27 @@ pub fn start_campaign(ctx: Context<StartCampaign>) -> Result<()> {
 
     let campaign = &mut ctx.accounts.campaign.load_init()?;
     campaign.authority = ctx.accounts.campaign_authority.key();
+    campaign.beneficiary = beneficiary;
+    copy_str(&mut campaign.title, &title, CrowdfundingError::TitleTooLong)?;
+    copy_str(&mut campaign.uri, &uri, CrowdfundingError::UriTooLong)?;
+    campaign.category = category as u8;
+    campaign.goal = goal;
+    campaign.deadline = deadline;
+    campaign.milestones_count = milestones.len() as u8;
+    for (milestone, basis_points) in campaign.milestones.iter_mut().zip(milestones) {
+        milestone.basis_points = basis_points;
+    }
     campaign.id = id;
+    ctx.accounts.chrt_vaults.load_init()?.payer = campaign.authority;
+
+    emit!(CampaignStarted {
+        campaign_id: id,
+        authority: campaign.authority,
+        beneficiary,
+        goal: campaign.goal,
+        deadline: campaign.deadline,
+        milestones_count: campaign.milestones_count,
+    });
 
     Ok(())
 }
@@ -1,6 +1,7 @@
-use crate::{error::*, state::*, utils::*};
+use crate::{events::*, state::*, utils::*};
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Burn, CloseAccount, Mint, Token, TokenAccount};
+use anchor_spl::token::{Mint, Token, TokenAccount};
+use core::ops::Deref;
 
 #[derive(Accounts)]
 pub struct StopCampaign<'info> {
@@ -12,13 +13,21 @@ pub struct StopCampaign<'info> {
     chrt_mint: Account<'info, Mint>,
     #[account(
         mut,
-        close = campaign_authority,
+        close = beneficiary,
         seeds = [b"campaign", campaign.load()?.id.to_le_bytes().as_ref()],
         bump,
     )]
     campaign: AccountLoader<'info, Campaign>,
-    #[account(mut, address = campaign.load()?.authority)]
+    #[account(address = campaign.load()?.authority)]
     campaign_authority: Signer<'info>,
+    #[account(mut, address = campaign.load()?.beneficiary)]
+    beneficiary: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"donations", campaign.load()?.id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    total_donations_to_campaign: AccountLoader<'info, Donations>,
     #[account(
         mut,
         seeds = [b"fee_exemption_vault", campaign.load()?.id.to_le_bytes().as_ref()],
@@ -31,63 +40,69 @@ pub struct StopCampaign<'info> {
         bump,
     )]
     liquidation_vault: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [b"chrt_vaults", campaign.load()?.id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    chrt_vaults: AccountLoader<'info, ChrtVaults>,
+    #[account(mut, address = chrt_vaults.load()?.payer)]
+    chrt_vaults_payer: UncheckedAccount<'info>,
     token_program: Program<'info, Token>,
     system_program: Program<'info, System>,
 }
 
-fn close_chrt_vaults(ctx: &Context<StopCampaign>) -> Result<()> {
-    let signer: &[&[&[u8]]] = &[&[b"platform", &[*ctx.bumps.get("platform").unwrap()]]];
-
-    for vault in [
-        &ctx.accounts.fee_exemption_vault,
-        &ctx.accounts.liquidation_vault,
-    ] {
-        let cpi_ctx = CpiContext::new_with_signer(
-            ctx.accounts.token_program.to_account_info(),
-            Burn {
-                mint: ctx.accounts.chrt_mint.to_account_info(),
-                from: vault.to_account_info(),
-                authority: ctx.accounts.platform.to_account_info(),
-            },
-            signer,
-        );
-        token::burn(cpi_ctx, vault.amount)?;
-
-        let cpi_ctx = CpiContext::new_with_signer(
-            ctx.accounts.token_program.to_account_info(),
-            CloseAccount {
-                account: vault.to_account_info(),
-                destination: ctx.accounts.campaign_authority.to_account_info(),
-                authority: ctx.accounts.platform.to_account_info(),
-            },
-            signer,
-        );
-        token::close_account(cpi_ctx)?;
-    }
-
-    Ok(())
-}
-
 pub fn stop_campaign(ctx: Context<StopCampaign>) -> Result<()> {
-    close_chrt_vaults(&ctx)?;
+    end_chrt_vaults(
+        &ctx.accounts.token_program,
+        &ctx.accounts.chrt_mint,
+        &ctx.accounts.platform,
+        *ctx.bumps.get("platform").unwrap(),
+        ctx.accounts.campaign.load()?.deref(),
+        &ctx.accounts.chrt_vaults,
+        [
+            &ctx.accounts.fee_exemption_vault,
+            &ctx.accounts.liquidation_vault,
+        ],
+        &ctx.accounts.chrt_vaults_payer.to_account_info(),
+    )?;
 
     let platform = &mut ctx.accounts.platform.load_mut()?;
-    let len = platform.active_campaigns_count as usize;
-    let id = ctx.accounts.campaign.load()?.id;
-    let i = platform.active_campaigns[..len]
-        .binary_search_by_key(&id, |c| c.id)
-        .map_err(|_| CrowdfundingError::CampaignInactive)?;
-    let campaign = platform.active_campaigns[i];
-    platform.active_campaigns[i] = Default::default();
-    platform.active_campaigns[i..len].rotate_left(1);
-    platform.active_campaigns_count -= 1;
+    let campaign = remove_active_campaign(platform, ctx.accounts.campaign.load()?.id)?;
+    check_token_vaults_closed(ctx.accounts.campaign.load()?.deref())?;
+    check_refunds_settled(
+        ctx.accounts.campaign.load()?.deref(),
+        &campaign,
+        ctx.accounts.total_donations_to_campaign.load()?.deref(),
+    )?;
+    let total_donations_to_campaign = &mut ctx.accounts.total_donations_to_campaign.load_mut()?;
+    mark_goal_reached(
+        ctx.accounts.campaign.load()?.deref(),
+        &campaign,
+        total_donations_to_campaign,
+    );
 
+    let (amount, refundable_amount) = release_donations(
+        ctx.accounts.campaign.load()?.deref(),
+        campaign.donations_sum,
+        campaign.withdrawn_sum,
+    );
+    // the share of locked milestones stays in the vault for donors to refund
+    total_donations_to_campaign.refundable_sum = refundable_amount;
     transfer(
         &ctx.accounts.sol_vault.to_account_info(),
-        &ctx.accounts.campaign_authority.to_account_info(),
-        campaign.donations_sum - campaign.withdrawn_sum,
+        &ctx.accounts.beneficiary.to_account_info(),
+        amount,
     )?;
 
+    emit!(CampaignStopped {
+        campaign_id: campaign.id,
+        beneficiary: ctx.accounts.beneficiary.key(),
+        amount,
+        refundable_amount,
+        expired: false,
+    });
+
     platform.sum_of_active_campaign_donations -= campaign.donations_sum;
 
     Ok(())
@@ -0,0 +1,75 @@
+use crate::{error::*, events::*, state::*, utils::*};
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use core::mem::size_of;
+
+#[derive(Accounts)]
+#[instruction(campaign_id: u16)]
+pub struct SupportCampaign<'info> {
+    // only active campaigns can be supported
+    #[account(mut, seeds = [b"campaign", campaign_id.to_le_bytes().as_ref()], bump)]
+    campaign: AccountLoader<'info, Campaign>,
+    #[account(
+        mut,
+        seeds = [ChrtVault::FeeExemption.seed(), campaign_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    fee_exemption_vault: Account<'info, TokenAccount>,
+    #[account(
+        init_if_needed,
+        payer = supporter,
+        seeds = [
+            ChrtVault::FeeExemption.deposit_seed(),
+            campaign_id.to_le_bytes().as_ref(),
+            supporter.key().as_ref(),
+        ],
+        bump,
+        space = 8 + size_of::<ChrtDeposit>(),
+    )]
+    chrt_deposit: AccountLoader<'info, ChrtDeposit>,
+    #[account(mut)]
+    supporter: Signer<'info>,
+    #[account(mut, token::mint = fee_exemption_vault.mint, token::authority = supporter)]
+    supporter_chrt: Account<'info, TokenAccount>,
+    token_program: Program<'info, Token>,
+    system_program: Program<'info, System>,
+}
+
+pub fn support_campaign(
+    ctx: Context<SupportCampaign>,
+    campaign_id: u16,
+    amount: u64,
+) -> Result<()> {
+    if amount == 0 {
+        return err!(CrowdfundingError::ZeroDeposit);
+    }
+
+    let cpi_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.supporter_chrt.to_account_info(),
+            to: ctx.accounts.fee_exemption_vault.to_account_info(),
+            authority: ctx.accounts.supporter.to_account_info(),
+        },
+    );
+    token::transfer(cpi_ctx, amount)?;
+
+    let campaign = &mut ctx.accounts.campaign.load_mut()?;
+    let deposit = record_chrt_deposit(
+        &ctx.accounts.chrt_deposit,
+        campaign,
+        ctx.accounts.supporter.key(),
+        ChrtVault::FeeExemption,
+        amount,
+    )?;
+
+    emit!(CampaignSupported {
+        supporter: ctx.accounts.supporter.key(),
+        campaign_id,
+        amount,
+        deposit,
+        fee_exemption_deposits_sum: campaign.fee_exemption_deposits_sum,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,54 @@
+use crate::{error::*, events::*, state::*, utils::*};
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount};
+
+#[derive(Accounts)]
+#[instruction(campaign_id: u16)]
+pub struct Unstake<'info> {
+    #[account(seeds = [b"platform"], bump)]
+    platform: AccountLoader<'info, Platform>,
+    #[account(
+        mut,
+        seeds = [b"stake", campaign_id.to_le_bytes().as_ref(), staker.key().as_ref()],
+        bump,
+    )]
+    stake_position: AccountLoader<'info, StakePosition>,
+    #[account(mut, seeds = [b"stake_vault"], bump)]
+    stake_vault: Account<'info, TokenAccount>,
+    staker: Signer<'info>,
+    #[account(mut, token::authority = staker)]
+    staker_chrt: Account<'info, TokenAccount>,
+    token_program: Program<'info, Token>,
+}
+
+pub fn unstake(ctx: Context<Unstake>, campaign_id: u16) -> Result<()> {
+    let amount = {
+        let stake_position = &mut ctx.accounts.stake_position.load_mut()?;
+        if stake_position.unstaking_amount == 0 {
+            return err!(CrowdfundingError::NothingToUnstake);
+        }
+        if (Clock::get()?.unix_timestamp as u32) < stake_position.unlock_ts {
+            return err!(CrowdfundingError::UnstakeCooldown);
+        }
+        let amount = stake_position.unstaking_amount;
+        stake_position.unstaking_amount = 0;
+        amount
+    };
+
+    transfer_tokens(
+        &ctx.accounts.token_program,
+        &ctx.accounts.platform,
+        *ctx.bumps.get("platform").unwrap(),
+        &ctx.accounts.stake_vault,
+        &ctx.accounts.staker_chrt,
+        amount,
+    )?;
+
+    emit!(Unstaked {
+        staker: ctx.accounts.staker.key(),
+        campaign_id,
+        amount,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,39 @@
+use crate::{error::*, events::*, state::*, utils::*};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct UpdateCampaignMetadata<'info> {
+    #[account(
+        mut,
+        seeds = [b"campaign", campaign.load()?.id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    campaign: AccountLoader<'info, Campaign>,
+    #[account(address = campaign.load()?.authority)]
+    campaign_authority: Signer<'info>,
+}
+
+pub fn update_campaign_metadata(
+    ctx: Context<UpdateCampaignMetadata>,
+    title: Option<String>,
+    uri: Option<String>,
+    category: Option<CampaignCategory>,
+) -> Result<()> {
+    let campaign = &mut ctx.accounts.campaign.load_mut()?;
+
+    if let Some(title) = title {
+        copy_str(&mut campaign.title, &title, CrowdfundingError::TitleTooLong)?;
+    }
+    if let Some(uri) = uri {
+        copy_str(&mut campaign.uri, &uri, CrowdfundingError::UriTooLong)?;
+    }
+    if let Some(category) = category {
+        campaign.category = category as u8;
+    }
+
+    emit!(CampaignMetadataUpdated {
+        campaign_id: campaign.id,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,101 @@
+use crate::{error::*, events::*, state::*, utils::*};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct UpdatePlatformConfig<'info> {
+    #[account(mut, seeds = [b"platform"], bump)]
+    platform: AccountLoader<'info, Platform>,
+    #[account(address = platform.load()?.authority)]
+    platform_authority: Signer<'info>,
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn update_platform_config(
+    ctx: Context<UpdatePlatformConfig>,
+    reward_cooldown: Option<u32>,
+    reward_procedure_timeout: Option<u32>,
+    reward_amount: Option<u64>,
+    reward_distribution: Option<RewardDistribution>,
+    reward_tiers: Option<Vec<RewardTier>>,
+    fee_basis_points: Option<u16>,
+    fee_exemption_limit: Option<u64>,
+    liquidation_limit: Option<u64>,
+    record_donor_bounty: Option<u64>,
+    drop_rewards_bounty: Option<u64>,
+    referral_rate: Option<u64>,
+    referral_donation_cap: Option<u64>,
+    referral_season_cap: Option<u64>,
+    unstake_cooldown: Option<u32>,
+) -> Result<()> {
+    let platform = &mut ctx.accounts.platform.load_mut()?;
+    // rewards are calculated page by page, so they must not change mid-procedure
+    if platform.reward_procedure_is_in_process
+        && (reward_amount.is_some() || reward_distribution.is_some() || reward_tiers.is_some())
+    {
+        return err!(CrowdfundingError::RewardProcedureInProcess);
+    }
+
+    if let Some(reward_cooldown) = reward_cooldown {
+        platform.reward_cooldown = reward_cooldown;
+    }
+    if let Some(reward_procedure_timeout) = reward_procedure_timeout {
+        platform.reward_procedure_timeout = reward_procedure_timeout;
+    }
+    if let Some(reward_amount) = reward_amount {
+        platform.reward_amount = reward_amount;
+    }
+    if let Some(reward_distribution) = reward_distribution {
+        platform.reward_distribution = reward_distribution as u8;
+    }
+    if let Some(reward_tiers) = reward_tiers {
+        set_reward_tiers(platform, &reward_tiers)?;
+    }
+    if let Some(fee_basis_points) = fee_basis_points {
+        check_fee_basis_points(fee_basis_points)?;
+        platform.fee_basis_points = fee_basis_points;
+    }
+    if let Some(fee_exemption_limit) = fee_exemption_limit {
+        platform.fee_exemption_limit = fee_exemption_limit;
+    }
+    if let Some(liquidation_limit) = liquidation_limit {
+        check_liquidation_limit(liquidation_limit)?;
+        platform.liquidation_limit = liquidation_limit;
+    }
+    if let Some(record_donor_bounty) = record_donor_bounty {
+        platform.record_donor_bounty = record_donor_bounty;
+    }
+    if let Some(drop_rewards_bounty) = drop_rewards_bounty {
+        platform.drop_rewards_bounty = drop_rewards_bounty;
+    }
+    if let Some(referral_rate) = referral_rate {
+        platform.referral_rate = referral_rate;
+    }
+    if let Some(referral_donation_cap) = referral_donation_cap {
+        platform.referral_donation_cap = referral_donation_cap;
+    }
+    if let Some(referral_season_cap) = referral_season_cap {
+        platform.referral_season_cap = referral_season_cap;
+    }
+    if let Some(unstake_cooldown) = unstake_cooldown {
+        platform.unstake_cooldown = unstake_cooldown;
+    }
+
+    emit!(PlatformConfigUpdated {
+        reward_cooldown: platform.reward_cooldown,
+        reward_procedure_timeout: platform.reward_procedure_timeout,
+        reward_amount: platform.reward_amount,
+        reward_distribution: platform.reward_distribution,
+        reward_tiers: { platform.reward_tiers }[..platform.reward_tiers_count as usize].to_vec(),
+        fee_basis_points: platform.fee_basis_points,
+        fee_exemption_limit: platform.fee_exemption_limit,
+        liquidation_limit: platform.liquidation_limit,
+        record_donor_bounty: platform.record_donor_bounty,
+        drop_rewards_bounty: platform.drop_rewards_bounty,
+        referral_rate: platform.referral_rate,
+        referral_donation_cap: platform.referral_donation_cap,
+        referral_season_cap: platform.referral_season_cap,
+        unstake_cooldown: platform.unstake_cooldown,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,81 @@
+use crate::{error::*, events::*, state::*};
+use anchor_lang::prelude::*;
+use core::mem::size_of;
+
+#[derive(Accounts)]
+pub struct VoteMilestone<'info> {
+    #[account(
+        mut,
+        seeds = [b"campaign", campaign.load()?.id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    campaign: AccountLoader<'info, Campaign>,
+    #[account(
+        seeds = [b"donations", campaign.load()?.id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    total_donations_to_campaign: AccountLoader<'info, Donations>,
+    /// Any donor but the campaign authority and beneficiary, who would vote to pay themselves.
+    #[account(
+        mut,
+        constraint = donor_authority.key() != campaign.load()?.authority
+            && donor_authority.key() != campaign.load()?.beneficiary
+            @ CrowdfundingError::MilestoneSelfVote,
+    )]
+    donor_authority: Signer<'info>,
+    #[account(
+        seeds = [b"donations", donor_authority.key().as_ref(), campaign.load()?.id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    donor_donations_to_campaign: AccountLoader<'info, Donations>,
+    #[account(
+        init,
+        payer = donor_authority,
+        seeds = [
+            b"milestone_vote",
+            donor_authority.key().as_ref(),
+            campaign.load()?.id.to_le_bytes().as_ref(),
+            &[campaign.load()?.milestones_unlocked],
+        ],
+        bump,
+        space = 8 + size_of::<MilestoneVote>(),
+    )]
+    milestone_vote: AccountLoader<'info, MilestoneVote>,
+    system_program: Program<'info, System>,
+}
+
+pub fn vote_milestone(ctx: Context<VoteMilestone>) -> Result<()> {
+    let weight = ctx
+        .accounts
+        .donor_donations_to_campaign
+        .load()?
+        .donations_sum;
+    ctx.accounts.milestone_vote.load_init()?.weight = weight;
+
+    let total_donations = ctx
+        .accounts
+        .total_donations_to_campaign
+        .load()?
+        .donations_sum;
+    let campaign = &mut ctx.accounts.campaign.load_mut()?;
+    let i = campaign.milestones_unlocked as usize;
+    if i == campaign.milestones_count as usize {
+        return err!(CrowdfundingError::AllMilestonesUnlocked);
+    }
+    campaign.milestones[i].votes += weight;
+    emit!(MilestoneVoted {
+        donor: ctx.accounts.donor_authority.key(),
+        campaign_id: campaign.id,
+        milestone: i as u8,
+        weight,
+    });
+    if campaign.milestones[i].votes as u128 * 2 > total_donations as u128 {
+        campaign.milestones_unlocked += 1;
+        emit!(MilestoneUnlocked {
+            campaign_id: campaign.id,
+            milestone: i as u8,
+        });
+    }
+
+    Ok(())
+}
@@ -0,0 +1,94 @@
+use crate::{error::*, events::*, state::*, utils::*};
+use anchor_lang::{prelude::*, AccountsClose};
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+#[derive(Accounts)]
+#[instruction(campaign_id: u16, vault: ChrtVault)]
+pub struct WithdrawChrtDeposit<'info> {
+    #[account(seeds = [b"platform"], bump)]
+    platform: AccountLoader<'info, Platform>,
+    #[account(mut, seeds = [b"chrt_mint"], bump)]
+    chrt_mint: Account<'info, Mint>,
+    // liquidated campaigns burn their vaults,
+    // so only deposits to stopped and expired campaigns are left to withdraw
+    #[account(
+        seeds = [b"campaign", campaign_id.to_le_bytes().as_ref()],
+        bump,
+        constraint = campaign.data_is_empty() @ CrowdfundingError::CampaignIsActive,
+    )]
+    campaign: UncheckedAccount<'info>,
+    #[account(mut, seeds = [vault.seed(), campaign_id.to_le_bytes().as_ref()], bump)]
+    chrt_vault: Account<'info, TokenAccount>,
+    #[account(mut, seeds = [b"chrt_vaults", campaign_id.to_le_bytes().as_ref()], bump)]
+    chrt_vaults: AccountLoader<'info, ChrtVaults>,
+    #[account(mut, address = chrt_vaults.load()?.payer)]
+    chrt_vaults_payer: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        close = depositor,
+        seeds = [
+            vault.deposit_seed(),
+            campaign_id.to_le_bytes().as_ref(),
+            depositor.key().as_ref(),
+        ],
+        bump,
+    )]
+    chrt_deposit: AccountLoader<'info, ChrtDeposit>,
+    #[account(mut)]
+    depositor: Signer<'info>,
+    #[account(mut, token::mint = chrt_vault.mint, token::authority = depositor)]
+    depositor_chrt: Account<'info, TokenAccount>,
+    token_program: Program<'info, Token>,
+}
+
+pub fn withdraw_chrt_deposit(
+    ctx: Context<WithdrawChrtDeposit>,
+    campaign_id: u16,
+    vault: ChrtVault,
+) -> Result<()> {
+    let amount = ctx.accounts.chrt_deposit.load()?.amount;
+    let platform_bump = *ctx.bumps.get("platform").unwrap();
+    transfer_tokens(
+        &ctx.accounts.token_program,
+        &ctx.accounts.platform,
+        platform_bump,
+        &ctx.accounts.chrt_vault,
+        &ctx.accounts.depositor_chrt,
+        amount,
+    )?;
+
+    // the vault closes with the last recorded deposit, burning whatever was sent to it directly
+    let (deposits_sum, deposits_sums_left) = {
+        let chrt_vaults = &mut ctx.accounts.chrt_vaults.load_mut()?;
+        *chrt_vaults.deposits_sum_mut(vault) -= amount;
+        (
+            *chrt_vaults.deposits_sum_mut(vault),
+            chrt_vaults.fee_exemption_deposits_sum + chrt_vaults.liquidation_deposits_sum,
+        )
+    };
+    if deposits_sum == 0 {
+        ctx.accounts.chrt_vault.reload()?;
+        close_chrt_vault(
+            &ctx.accounts.token_program,
+            &ctx.accounts.chrt_mint,
+            &ctx.accounts.platform,
+            platform_bump,
+            &ctx.accounts.chrt_vault,
+            &ctx.accounts.chrt_vaults_payer.to_account_info(),
+        )?;
+    }
+    if deposits_sums_left == 0 {
+        ctx.accounts
+            .chrt_vaults
+            .close(ctx.accounts.chrt_vaults_payer.to_account_info())?;
+    }
+
+    emit!(ChrtDepositWithdrawn {
+        depositor: ctx.accounts.depositor.key(),
+        campaign_id,
+        vault: vault as u8,
+        amount,
+    });
+
+    Ok(())
+}
@@ -1,4 +1,4 @@
-use crate::{state::*, utils::*};
+use crate::{error::*, events::*, state::*, utils::*};
 use anchor_lang::prelude::*;
 
 #[derive(Accounts)]
@@ -12,29 +12,41 @@ pub struct WithdrawDonations<'info> {
         bump,
     )]
     campaign: AccountLoader<'info, Campaign>,
-    #[account(mut, address = campaign.load()?.authority)]
+    #[account(address = campaign.load()?.authority)]
     campaign_authority: Signer<'info>,
+    #[account(mut, address = campaign.load()?.beneficiary)]
+    beneficiary: UncheckedAccount<'info>,
 }
 
 pub fn withdraw_donations(ctx: Context<WithdrawDonations>) -> Result<()> {
     let platform = &mut ctx.accounts.platform.load_mut()?;
 
-    let id = ctx.accounts.campaign.load()?.id;
+    let campaign = ctx.accounts.campaign.load()?;
     let i = platform.active_campaigns[..platform.active_campaigns_count as usize]
-        .binary_search_by_key(&id, |c| c.id)
+        .binary_search_by_key(&{ campaign.id }, |c| c.id)
         .unwrap();
+    if platform.active_campaigns[i].donations_sum < campaign.goal {
+        return err!(CrowdfundingError::FundingGoalNotReached);
+    }
     let lamports = {
-        let mut campaign = platform.active_campaigns.get_mut(i).unwrap();
-        let lamports = campaign.donations_sum - campaign.withdrawn_sum;
-        campaign.withdrawn_sum = campaign.donations_sum;
+        let mut campaign_record = platform.active_campaigns.get_mut(i).unwrap();
+        let unlocked_sum = campaign.unlocked_share(campaign_record.donations_sum);
+        let lamports = unlocked_sum.saturating_sub(campaign_record.withdrawn_sum);
+        campaign_record.withdrawn_sum += lamports;
         lamports
     };
 
     transfer(
         &ctx.accounts.sol_vault.to_account_info(),
-        &ctx.accounts.campaign_authority.to_account_info(),
+        &ctx.accounts.beneficiary.to_account_info(),
         lamports,
     )?;
 
+    emit!(DonationsWithdrawn {
+        campaign_id: campaign.id,
+        beneficiary: ctx.accounts.beneficiary.key(),
+        amount: lamports,
+    });
+
     Ok(())
 }
@@ -1,4 +1,4 @@
-use crate::{state::*, utils::*};
+use crate::{events::*, state::*, utils::*};
 use anchor_lang::prelude::*;
 
 #[derive(Accounts)]
@@ -12,10 +12,15 @@ pub struct WithdrawFees<'info> {
 }
 
 pub fn withdraw_fees(ctx: Context<WithdrawFees>) -> Result<()> {
-    transfer_all_but_rent(
+    let amount = transfer_all_but_rent(
         &ctx.accounts.fee_vault.to_account_info(),
         &ctx.accounts.platform_authority.to_account_info(),
     )?;
 
+    emit!(FeesWithdrawn {
+        authority: ctx.accounts.platform_authority.key(),
+        amount,
+    });
+
     Ok(())
 }
@@ -0,0 +1,64 @@
+use crate::{events::*, state::*, utils::*};
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+#[derive(Accounts)]
+pub struct WithdrawTokenDonations<'info> {
+    #[account(seeds = [b"platform"], bump)]
+    platform: AccountLoader<'info, Platform>,
+    #[account(
+        seeds = [b"campaign", campaign.load()?.id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    campaign: AccountLoader<'info, Campaign>,
+    #[account(address = campaign.load()?.authority)]
+    campaign_authority: Signer<'info>,
+    mint: Account<'info, Mint>,
+    #[account(
+        mut,
+        seeds = [b"token_vault", campaign.load()?.id.to_le_bytes().as_ref(), mint.key().as_ref()],
+        bump,
+    )]
+    token_vault: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [b"token_donations", campaign.load()?.id.to_le_bytes().as_ref(), mint.key().as_ref()],
+        bump,
+    )]
+    token_donations: AccountLoader<'info, TokenDonations>,
+    #[account(mut, token::mint = mint, token::authority = campaign.load()?.beneficiary)]
+    beneficiary_token_account: Account<'info, TokenAccount>,
+    token_program: Program<'info, Token>,
+}
+
+pub fn withdraw_token_donations(ctx: Context<WithdrawTokenDonations>) -> Result<()> {
+    let amount = {
+        let token_donations = &mut ctx.accounts.token_donations.load_mut()?;
+        let unlocked_sum = ctx
+            .accounts
+            .campaign
+            .load()?
+            .unlocked_share(token_donations.donations_sum);
+        let amount = unlocked_sum.saturating_sub(token_donations.withdrawn_sum);
+        token_donations.withdrawn_sum += amount;
+        amount
+    };
+
+    transfer_tokens(
+        &ctx.accounts.token_program,
+        &ctx.accounts.platform,
+        *ctx.bumps.get("platform").unwrap(),
+        &ctx.accounts.token_vault,
+        &ctx.accounts.beneficiary_token_account,
+        amount,
+    )?;
+
+    emit!(TokenDonationsWithdrawn {
+        campaign_id: ctx.accounts.campaign.load()?.id,
+        mint: ctx.accounts.mint.key(),
+        beneficiary: ctx.accounts.beneficiary_token_account.owner,
+        amount,
+    });
+
+    Ok(())
+}
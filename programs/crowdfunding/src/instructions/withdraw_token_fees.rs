@@ -0,0 +1,37 @@
+use crate::{events::*, state::*, utils::*};
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+#[derive(Accounts)]
+pub struct WithdrawTokenFees<'info> {
+    #[account(seeds = [b"platform"], bump)]
+    platform: AccountLoader<'info, Platform>,
+    #[account(address = platform.load()?.authority)]
+    platform_authority: Signer<'info>,
+    mint: Account<'info, Mint>,
+    #[account(mut, seeds = [b"token_fee_vault", mint.key().as_ref()], bump)]
+    token_fee_vault: Account<'info, TokenAccount>,
+    #[account(mut, token::mint = mint, token::authority = platform_authority)]
+    platform_authority_token_account: Account<'info, TokenAccount>,
+    token_program: Program<'info, Token>,
+}
+
+pub fn withdraw_token_fees(ctx: Context<WithdrawTokenFees>) -> Result<()> {
+    let amount = ctx.accounts.token_fee_vault.amount;
+    transfer_tokens(
+        &ctx.accounts.token_program,
+        &ctx.accounts.platform,
+        *ctx.bumps.get("platform").unwrap(),
+        &ctx.accounts.token_fee_vault,
+        &ctx.accounts.platform_authority_token_account,
+        amount,
+    )?;
+
+    emit!(TokenFeesWithdrawn {
+        authority: ctx.accounts.platform_authority.key(),
+        mint: ctx.accounts.mint.key(),
+        amount,
+    });
+
+    Ok(())
+}
@@ -1,9 +1,11 @@
-use crate::instructions::*;
+use crate::{instructions::*, state::*};
 use anchor_lang::prelude::*;
 
 pub mod config;
 pub mod error;
+pub mod events;
 mod instructions;
+pub mod merkle;
 pub mod state;
 mod utils;
 
@@ -13,18 +15,25 @@ declare_id!("BkBYehfNc7WBa6MmmFz3mMzwBduQLBTzboA3e6JaBGYR");
 pub mod crowdfunding {
     use super::*;
 
+    #[allow(clippy::too_many_arguments)]
     pub fn initialize(
         ctx: Context<Initialize>,
+        seasonal_top_capacity: u16,
         reward_cooldown: u32,
         reward_amount: u64,
+        reward_distribution: RewardDistribution,
+        reward_tiers: Vec<RewardTier>,
         fee_basis_points: u16,
         fee_exemption_limit: u64,
         liquidation_limit: u64,
     ) -> Result<()> {
         instructions::initialize(
             ctx,
+            seasonal_top_capacity,
             reward_cooldown,
             reward_amount,
+            reward_distribution,
+            reward_tiers,
             fee_basis_points,
             fee_exemption_limit,
             liquidation_limit,
@@ -35,8 +44,31 @@ pub mod crowdfunding {
         instructions::register_donor(ctx)
     }
 
-    pub fn start_campaign(ctx: Context<StartCampaign>) -> Result<()> {
-        instructions::start_campaign(ctx)
+    pub fn register_referral_code(ctx: Context<RegisterReferralCode>, code: String) -> Result<()> {
+        instructions::register_referral_code(ctx, code)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn start_campaign(
+        ctx: Context<StartCampaign>,
+        beneficiary: Pubkey,
+        title: String,
+        uri: String,
+        category: CampaignCategory,
+        goal: Option<u64>,
+        deadline: Option<u32>,
+        milestones: Vec<u16>,
+    ) -> Result<()> {
+        instructions::start_campaign(
+            ctx,
+            beneficiary,
+            title,
+            uri,
+            category,
+            goal,
+            deadline,
+            milestones,
+        )
     }
 
     pub fn donate(ctx: Context<Donate>, amount: u64) -> Result<()> {
@@ -47,6 +79,61 @@ pub mod crowdfunding {
         instructions::donate_with_referer(ctx, amount)
     }
 
+    pub fn donate_with_referral_code(
+        ctx: Context<DonateWithReferralCode>,
+        code: String,
+        amount: u64,
+    ) -> Result<()> {
+        instructions::donate_with_referral_code(ctx, code, amount)
+    }
+
+    pub fn credit_donations(ctx: Context<CreditDonations>, campaign_id: u16) -> Result<()> {
+        instructions::credit_donations(ctx, campaign_id)
+    }
+
+    pub fn credit_donations_with_referer(
+        ctx: Context<CreditDonationsWithReferer>,
+        campaign_id: u16,
+    ) -> Result<()> {
+        instructions::credit_donations_with_referer(ctx, campaign_id)
+    }
+
+    pub fn stake(ctx: Context<Stake>, campaign_id: u16, amount: u64) -> Result<()> {
+        instructions::stake(ctx, campaign_id, amount)
+    }
+
+    pub fn request_unstake(
+        ctx: Context<RequestUnstake>,
+        campaign_id: u16,
+        amount: u64,
+    ) -> Result<()> {
+        instructions::request_unstake(ctx, campaign_id, amount)
+    }
+
+    pub fn unstake(ctx: Context<Unstake>, campaign_id: u16) -> Result<()> {
+        instructions::unstake(ctx, campaign_id)
+    }
+
+    pub fn support_campaign(
+        ctx: Context<SupportCampaign>,
+        campaign_id: u16,
+        amount: u64,
+    ) -> Result<()> {
+        instructions::support_campaign(ctx, campaign_id, amount)
+    }
+
+    pub fn flag_campaign(ctx: Context<FlagCampaign>, campaign_id: u16, amount: u64) -> Result<()> {
+        instructions::flag_campaign(ctx, campaign_id, amount)
+    }
+
+    pub fn withdraw_chrt_deposit(
+        ctx: Context<WithdrawChrtDeposit>,
+        campaign_id: u16,
+        vault: ChrtVault,
+    ) -> Result<()> {
+        instructions::withdraw_chrt_deposit(ctx, campaign_id, vault)
+    }
+
     pub fn record_donors(ctx: Context<RecordDonors>) -> Result<()> {
         instructions::record_donors(ctx)
     }
@@ -55,6 +142,29 @@ pub mod crowdfunding {
         instructions::drop_rewards(ctx)
     }
 
+    pub fn resize_seasonal_top(ctx: Context<ResizeSeasonalTop>, capacity: u16) -> Result<()> {
+        instructions::resize_seasonal_top(ctx, capacity)
+    }
+
+    pub fn commit_rewards<'info>(
+        ctx: Context<'_, '_, '_, 'info, CommitRewards<'info>>,
+    ) -> Result<()> {
+        instructions::commit_rewards(ctx)
+    }
+
+    pub fn claim_reward(
+        ctx: Context<ClaimReward>,
+        season_id: u32,
+        amount: u64,
+        proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        instructions::claim_reward(ctx, season_id, amount, proof)
+    }
+
+    pub fn abort_reward_procedure(ctx: Context<AbortRewardProcedure>) -> Result<()> {
+        instructions::abort_reward_procedure(ctx)
+    }
+
     pub fn withdraw_donations(ctx: Context<WithdrawDonations>) -> Result<()> {
         instructions::withdraw_donations(ctx)
     }
@@ -70,4 +180,123 @@ pub mod crowdfunding {
     pub fn withdraw_fees(ctx: Context<WithdrawFees>) -> Result<()> {
         instructions::withdraw_fees(ctx)
     }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn update_platform_config(
+        ctx: Context<UpdatePlatformConfig>,
+        reward_cooldown: Option<u32>,
+        reward_procedure_timeout: Option<u32>,
+        reward_amount: Option<u64>,
+        reward_distribution: Option<RewardDistribution>,
+        reward_tiers: Option<Vec<RewardTier>>,
+        fee_basis_points: Option<u16>,
+        fee_exemption_limit: Option<u64>,
+        liquidation_limit: Option<u64>,
+        record_donor_bounty: Option<u64>,
+        drop_rewards_bounty: Option<u64>,
+        referral_rate: Option<u64>,
+        referral_donation_cap: Option<u64>,
+        referral_season_cap: Option<u64>,
+        unstake_cooldown: Option<u32>,
+    ) -> Result<()> {
+        instructions::update_platform_config(
+            ctx,
+            reward_cooldown,
+            reward_procedure_timeout,
+            reward_amount,
+            reward_distribution,
+            reward_tiers,
+            fee_basis_points,
+            fee_exemption_limit,
+            liquidation_limit,
+            record_donor_bounty,
+            drop_rewards_bounty,
+            referral_rate,
+            referral_donation_cap,
+            referral_season_cap,
+            unstake_cooldown,
+        )
+    }
+
+    pub fn propose_platform_authority(
+        ctx: Context<ProposePlatformAuthority>,
+        new_authority: Pubkey,
+    ) -> Result<()> {
+        instructions::propose_platform_authority(ctx, new_authority)
+    }
+
+    pub fn accept_platform_authority(ctx: Context<AcceptPlatformAuthority>) -> Result<()> {
+        instructions::accept_platform_authority(ctx)
+    }
+
+    pub fn cancel_platform_authority(ctx: Context<CancelPlatformAuthority>) -> Result<()> {
+        instructions::cancel_platform_authority(ctx)
+    }
+
+    pub fn propose_campaign_authority(
+        ctx: Context<ProposeCampaignAuthority>,
+        new_authority: Pubkey,
+    ) -> Result<()> {
+        instructions::propose_campaign_authority(ctx, new_authority)
+    }
+
+    pub fn accept_campaign_authority(ctx: Context<AcceptCampaignAuthority>) -> Result<()> {
+        instructions::accept_campaign_authority(ctx)
+    }
+
+    pub fn cancel_campaign_authority(ctx: Context<CancelCampaignAuthority>) -> Result<()> {
+        instructions::cancel_campaign_authority(ctx)
+    }
+
+    pub fn update_campaign_metadata(
+        ctx: Context<UpdateCampaignMetadata>,
+        title: Option<String>,
+        uri: Option<String>,
+        category: Option<CampaignCategory>,
+    ) -> Result<()> {
+        instructions::update_campaign_metadata(ctx, title, uri, category)
+    }
+
+    pub fn refund_donations(ctx: Context<RefundDonations>, campaign_id: u16) -> Result<()> {
+        instructions::refund_donations(ctx, campaign_id)
+    }
+
+    pub fn expire_campaign(ctx: Context<ExpireCampaign>) -> Result<()> {
+        instructions::expire_campaign(ctx)
+    }
+
+    pub fn vote_milestone(ctx: Context<VoteMilestone>) -> Result<()> {
+        instructions::vote_milestone(ctx)
+    }
+
+    pub fn open_token_vault(ctx: Context<OpenTokenVault>) -> Result<()> {
+        instructions::open_token_vault(ctx)
+    }
+
+    pub fn donate_token(ctx: Context<DonateToken>, amount: u64) -> Result<()> {
+        instructions::donate_token(ctx, amount)
+    }
+
+    pub fn withdraw_token_donations(ctx: Context<WithdrawTokenDonations>) -> Result<()> {
+        instructions::withdraw_token_donations(ctx)
+    }
+
+    pub fn close_token_vault(ctx: Context<CloseTokenVault>) -> Result<()> {
+        instructions::close_token_vault(ctx)
+    }
+
+    pub fn refund_token_donations(
+        ctx: Context<RefundTokenDonations>,
+        campaign_id: u16,
+    ) -> Result<()> {
+        instructions::refund_token_donations(ctx, campaign_id)
+    }
+
+    pub fn liquidate_token_vault(ctx: Context<LiquidateTokenVault>) -> Result<()> {
+        instructions::liquidate_token_vault(ctx)
+    }
+
+    pub fn withdraw_token_fees(ctx: Context<WithdrawTokenFees>) -> Result<()> {
+        instructions::withdraw_token_fees(ctx)
+    }
 }
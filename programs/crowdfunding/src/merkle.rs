@@ -0,0 +1,61 @@
+//! Merkle tree over seasonal rewards, shared by the program and its clients.
+//!
+//! Leaves and nodes are domain-separated so that a node can never pass for a leaf,
+//! and pairs are hashed in sorted order so that a proof is just a list of siblings.
+
+use anchor_lang::{prelude::*, solana_program::keccak::hashv};
+
+pub type Hash = [u8; 32];
+
+pub fn reward_leaf(donor: &Pubkey, amount: u64) -> Hash {
+    hashv(&[&[0], donor.as_ref(), &amount.to_le_bytes()]).0
+}
+
+fn hash_pair(a: &Hash, b: &Hash) -> Hash {
+    let (a, b) = if a <= b { (a, b) } else { (b, a) };
+    hashv(&[&[1], a, b]).0
+}
+
+fn next_level(level: &[Hash]) -> Vec<Hash> {
+    level
+        .chunks(2)
+        .map(|pair| match pair {
+            [a, b] => hash_pair(a, b),
+            // the odd node is promoted as is
+            [a] => *a,
+            _ => unreachable!(),
+        })
+        .collect()
+}
+
+/// Root of an empty tree is all zeroes.
+pub fn merkle_root(leaves: &[Hash]) -> Hash {
+    let mut level = leaves.to_vec();
+    if level.is_empty() {
+        return Hash::default();
+    }
+    while level.len() > 1 {
+        level = next_level(&level);
+    }
+    level[0]
+}
+
+pub fn merkle_proof(leaves: &[Hash], mut index: usize) -> Vec<Hash> {
+    let mut proof = Vec::new();
+    let mut level = leaves.to_vec();
+    while level.len() > 1 {
+        if let Some(sibling) = level.get(index ^ 1) {
+            proof.push(*sibling);
+        }
+        level = next_level(&level);
+        index /= 2;
+    }
+    proof
+}
+
+pub fn verify_proof(proof: &[Hash], root: &Hash, leaf: Hash) -> bool {
+    proof
+        .iter()
+        .fold(leaf, |node, sibling| hash_pair(&node, sibling))
+        == *root
+}
@@ -1,5 +1,20 @@
 use crate::config::*;
-use anchor_lang::prelude::*;
+use anchor_lang::{prelude::*, solana_program::native_token::LAMPORTS_PER_SOL};
+use bytemuck::{Pod, Zeroable};
+use core::mem::size_of;
+
+/// Entry of a top sorted by `donations_sum` in descending order.
+pub trait TopRecord: Copy {
+    fn donor(&self) -> Pubkey;
+    fn donations_sum(&self) -> u64;
+}
+
+/// Number of records in a top, whose unused slots are left zeroed after the last one.
+pub fn top_len<T: TopRecord>(top: &[T]) -> usize {
+    top.iter()
+        .position(|d| d.donor().to_bytes() == [0; 32])
+        .unwrap_or(top.len())
+}
 
 #[derive(AnchorDeserialize, Copy, Clone, Debug, Default, PartialEq, Eq)]
 #[repr(packed)]
@@ -8,34 +23,182 @@ pub struct DonorRecord {
     pub donations_sum: u64,
 }
 
+impl TopRecord for DonorRecord {
+    fn donor(&self) -> Pubkey {
+        self.donor
+    }
+
+    fn donations_sum(&self) -> u64 {
+        self.donations_sum
+    }
+}
+
+#[derive(AnchorDeserialize, Copy, Clone, Debug, Default, PartialEq, Eq)]
+#[repr(packed)]
+pub struct SeasonalTopRecord {
+    pub donor: Pubkey,
+    pub donations_sum: u64,
+    /// Donations the reward is calculated from.
+    pub unrewarded_sum: u64,
+}
+
+impl TopRecord for SeasonalTopRecord {
+    fn donor(&self) -> Pubkey {
+        self.donor
+    }
+
+    fn donations_sum(&self) -> u64 {
+        self.donations_sum
+    }
+}
+
+// SAFETY: packed records of plain integers have no padding and any bit pattern is valid.
+unsafe impl Zeroable for SeasonalTopRecord {}
+unsafe impl Pod for SeasonalTopRecord {}
+
 #[derive(AnchorDeserialize, Copy, Clone, Debug, Default, PartialEq, Eq)]
 #[repr(packed)]
 pub struct CampaignRecord {
     pub id: u16,
     pub donations_sum: u64,
     pub withdrawn_sum: u64,
+    /// Funding goal of the campaign, 0 for none.
+    pub goal: u64,
+}
+
+impl CampaignRecord {
+    /// Campaigns below their funding goal may end up refunding their donors, who would not get
+    /// a liquidation share back, so they take none.
+    pub fn takes_liquidation_share(&self) -> bool {
+        self.goal == 0 || self.donations_sum >= self.goal
+    }
+}
+
+#[derive(AnchorDeserialize, Copy, Clone, Debug, Default, PartialEq, Eq)]
+#[repr(packed)]
+pub struct Milestone {
+    pub basis_points: u16,
+    pub votes: u64,
+}
+
+#[derive(AnchorDeserialize, Copy, Clone, Debug, Default, PartialEq, Eq)]
+#[repr(packed)]
+pub struct SeasonRecord {
+    pub donor: Pubkey,
+    pub donations_sum: u64,
+    pub reward: u64,
+}
+
+impl TopRecord for SeasonRecord {
+    fn donor(&self) -> Pubkey {
+        self.donor
+    }
+
+    fn donations_sum(&self) -> u64 {
+        self.donations_sum
+    }
+}
+
+// SAFETY: see `SeasonalTopRecord`.
+unsafe impl Zeroable for SeasonRecord {}
+unsafe impl Pod for SeasonRecord {}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct RewardTier {
+    /// Rank of the last seasonal top donor in the tier, counting from 1.
+    pub last_rank: u16,
+    pub amount: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Copy, Clone, Debug, PartialEq, Eq)]
+pub enum RewardDistribution {
+    /// Each rank gets the amount of the tier it falls into.
+    Tiers,
+    /// `reward_amount` is split in proportion to unrewarded donations.
+    Proportional,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Copy, Clone, Debug, PartialEq, Eq)]
+pub enum CampaignCategory {
+    Other,
+    Charity,
+    Education,
+    Health,
+    Environment,
+    Animals,
+    Community,
+    Technology,
+    Art,
+}
+
+/// Campaign vault collecting CHRT from donors.
+#[derive(AnchorSerialize, AnchorDeserialize, Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ChrtVault {
+    /// CHRT exempting the campaign from fees.
+    FeeExemption,
+    /// CHRT voting for the campaign liquidation.
+    Liquidation,
+}
+
+impl ChrtVault {
+    pub fn seed(&self) -> &'static [u8] {
+        match self {
+            ChrtVault::FeeExemption => b"fee_exemption_vault",
+            ChrtVault::Liquidation => b"liquidation_vault",
+        }
+    }
+
+    pub fn deposit_seed(&self) -> &'static [u8] {
+        match self {
+            ChrtVault::FeeExemption => b"fee_exemption_deposit",
+            ChrtVault::Liquidation => b"liquidation_deposit",
+        }
+    }
 }
 
 #[account(zero_copy)]
 #[repr(packed)]
 pub struct Platform {
     pub authority: Pubkey,
+    pub pending_authority: Pubkey,
     pub reward_amount: u64,
+    pub reward_distribution: u8,
+    /// Without tiers, every seasonal top rank gets `reward_amount`.
+    pub reward_tiers_count: u8,
+    pub reward_tiers: [RewardTier; REWARD_TIERS_CAPACITY],
     pub reward_cooldown: u32,
+    pub reward_procedure_timeout: u32,
     pub campaigns_count: u16,
     pub fee_basis_points: u16,
     pub fee_exemption_limit: u64,
+    /// Seconds requested unstakes stay locked.
+    pub unstake_cooldown: u32,
     pub liquidation_limit: u64,
+    /// Lamports paid to the keeper per recorded donor whose donations this season paid at least as
+    /// much in fees.
+    pub record_donor_bounty: u64,
+    pub drop_rewards_bounty: u64,
+    /// CHRT base units minted to a referer per donated SOL.
+    pub referral_rate: u64,
+    /// Most CHRT base units a referer earns from one donation, 0 for no cap.
+    pub referral_donation_cap: u64,
+    /// Most CHRT base units a referer earns in a season, 0 for no cap.
+    pub referral_season_cap: u64,
     pub reward_procedure_is_in_process: bool,
+    pub season: u32,
     pub last_reward_procedure_ts: u32,
     pub donors_recorded: u32,
     pub donors_count: u32,
     pub sum_of_all_donations: u64,
+    /// Sum of `Donor::recorded_donations_sum` over all donors. Never decreases, since only
+    /// uncredited donations are refunded.
+    pub recorded_donations_sum: u64,
+    /// Part of `recorded_donations_sum` already credited to ended seasons.
+    pub ended_seasons_donations_sum: u64,
     pub sum_of_active_campaign_donations: u64,
     pub avoided_fees_sum: u64,
     pub liquidations_sum: u64,
     pub top: [DonorRecord; PLATFORM_TOP_CAPACITY],
-    pub seasonal_top: [DonorRecord; SEASONAL_TOP_CAPACITY],
     pub active_campaigns_count: u16,
     pub active_campaigns: [CampaignRecord; ACTIVE_CAMPAIGNS_CAPACITY],
 }
@@ -45,10 +208,94 @@ pub struct Platform {
 #[repr(packed)]
 pub struct Campaign {
     pub authority: Pubkey,
+    pub pending_authority: Pubkey,
+    pub beneficiary: Pubkey,
     pub id: u16,
+    pub title: [u8; CAMPAIGN_TITLE_CAPACITY],
+    pub uri: [u8; CAMPAIGN_URI_CAPACITY],
+    pub category: u8,
+    pub goal: u64,
+    pub deadline: u32,
+    pub milestones_count: u8,
+    pub milestones_unlocked: u8,
+    pub milestones: [Milestone; MILESTONES_CAPACITY],
+    pub token_vaults_count: u8,
+    /// CHRT deposited through `support_campaign` and `flag_campaign`, which unlike the vault
+    /// balances can't be inflated by sending tokens to the vaults directly.
+    pub fee_exemption_deposits_sum: u64,
+    pub liquidation_deposits_sum: u64,
     pub top: [DonorRecord; CAMPAIGN_TOP_CAPACITY],
 }
 
+impl Platform {
+    /// Zero timeout lets a reward procedure run for as long as it takes.
+    pub fn reward_procedure_timed_out(&self, now: u32) -> bool {
+        self.reward_procedure_timeout != 0
+            && now - self.last_reward_procedure_ts >= self.reward_procedure_timeout
+    }
+
+    /// CHRT a referer earns for a donation of `lamports`, before the season cap.
+    pub fn referral_reward(&self, lamports: u64) -> u64 {
+        let reward =
+            (self.referral_rate as u128 * lamports as u128 / LAMPORTS_PER_SOL as u128) as u64;
+        if self.referral_donation_cap != 0 {
+            return reward.min(self.referral_donation_cap);
+        }
+        reward
+    }
+
+    /// Rewards of the seasonal top donors given in rank order with their unrewarded donations.
+    pub fn calculate_rewards(&self, unrewarded_sums: &[u64]) -> Vec<u64> {
+        if self.reward_distribution == RewardDistribution::Proportional as u8 {
+            let total: u128 = unrewarded_sums.iter().map(|&s| s as u128).sum();
+            return unrewarded_sums
+                .iter()
+                .map(|&s| {
+                    if total == 0 {
+                        return 0;
+                    }
+                    (self.reward_amount as u128 * s as u128 / total) as u64
+                })
+                .collect();
+        }
+        let reward_tiers = { self.reward_tiers };
+        let reward_tiers = &reward_tiers[..self.reward_tiers_count as usize];
+        if reward_tiers.is_empty() {
+            return vec![self.reward_amount; unrewarded_sums.len()];
+        }
+        (1..=unrewarded_sums.len())
+            .map(|rank| {
+                reward_tiers
+                    .iter()
+                    .find(|t| t.last_rank as usize >= rank)
+                    .map_or(0, |t| t.amount)
+            })
+            .collect()
+    }
+}
+
+impl Campaign {
+    /// Donations of a campaign that missed its funding goal belong to the donors until refunded.
+    pub fn missed_goal(&self, donations_sum: u64, now: u32) -> bool {
+        self.goal != 0 && now > self.deadline && donations_sum < self.goal
+    }
+
+    pub fn unlocked_basis_points(&self) -> u64 {
+        if self.milestones_count == 0 {
+            return 10000;
+        }
+        self.milestones[..self.milestones_unlocked as usize]
+            .iter()
+            .map(|m| m.basis_points as u64)
+            .sum()
+    }
+
+    /// Share of `donations_sum` the unlocked milestones release to the beneficiary.
+    pub fn unlocked_share(&self, donations_sum: u64) -> u64 {
+        (donations_sum as u128 * self.unlocked_basis_points() as u128 / 10000) as u64
+    }
+}
+
 #[account(zero_copy)]
 #[derive(Debug)]
 #[repr(packed)]
@@ -56,13 +303,171 @@ pub struct Donor {
     pub authority: Pubkey,
     pub donations_sum: u64,
     pub rewarded_donations_sum: u64,
+    pub recorded_donations_sum: u64,
     pub last_record_ts: u32,
+    pub season: u32,
+    /// Donations `season` added to `recorded_donations_sum`.
+    pub season_donations_sum: u64,
+    /// Season `season_referral_rewards` were earned in.
+    pub referral_season: u32,
+    pub season_referral_rewards: u64,
+    /// Authority of the donor that referred this donor first, default if none did.
+    pub referer: Pubkey,
+    /// Donors this donor referred first.
+    pub referred_donors: u32,
+    /// Lamports donated with this donor as the referer.
+    pub referred_volume: u64,
+    /// CHRT base units minted to this donor for referrals.
+    pub referral_rewards_sum: u64,
+    /// The only referral code this donor can register, empty until it does.
+    pub referral_code: [u8; REFERRAL_CODE_CAPACITY],
 }
 
 #[account(zero_copy)]
 #[derive(Debug)]
+#[repr(packed)]
+pub struct Season {
+    pub id: u32,
+    /// When the reward procedure of the season started and ended.
+    pub start_ts: u32,
+    pub end_ts: u32,
+    /// Donations recorded during the season.
+    pub donations_sum: u64,
+    pub donors_recorded: u32,
+    pub donors_rewarded: u16,
+    pub rewards_sum: u64,
+    /// Whether the rewards are claimed against `merkle_root`, the root of the Merkle tree over
+    /// `merkle::reward_leaf(donor, amount)` in rank order, instead of being dropped.
+    pub rewards_committed: bool,
+    pub merkle_root: [u8; 32],
+    pub claimed_rewards_sum: u64,
+    // followed by the `SeasonRecord`s of the seasonal top
+}
+
+impl Season {
+    pub fn space(seasonal_top_capacity: u16) -> usize {
+        8 + size_of::<Season>() + seasonal_top_capacity as usize * size_of::<SeasonRecord>()
+    }
+}
+
+/// Leaderboard of the current reward procedure, resizable between procedures.
+#[account(zero_copy)]
+#[derive(Debug)]
+#[repr(packed)]
+pub struct SeasonalTop {
+    pub capacity: u16,
+    /// Rank `drop_rewards` or `commit_rewards` continue from, counting from 0.
+    pub cursor: u16,
+    // followed by `capacity` `SeasonalTopRecord`s
+}
+
+impl SeasonalTop {
+    pub fn space(capacity: u16) -> usize {
+        8 + size_of::<SeasonalTop>() + capacity as usize * size_of::<SeasonalTopRecord>()
+    }
+}
+
+#[account(zero_copy)]
+#[derive(Debug)]
+pub struct StakePool {
+    /// CHRT staked toward the campaign, excluding requested unstakes.
+    pub staked_sum: u64,
+}
+
+#[account(zero_copy)]
+#[derive(Debug)]
+#[repr(packed)]
+pub struct StakePosition {
+    pub staker: Pubkey,
+    pub campaign_id: u16,
+    pub amount: u64,
+    /// CHRT `unstake` withdraws once `unlock_ts` is reached.
+    pub unstaking_amount: u64,
+    pub unlock_ts: u32,
+}
+
+#[account(zero_copy)]
+#[derive(Debug)]
+#[repr(packed)]
+pub struct ChrtDeposit {
+    pub depositor: Pubkey,
+    pub campaign_id: u16,
+    pub vault: u8,
+    /// CHRT the depositor withdraws after the campaign is stopped.
+    pub amount: u64,
+}
+
+/// Outlives the campaign, so that its CHRT vaults are closed once the deposits are withdrawn.
+#[account(zero_copy)]
+#[derive(Debug)]
+pub struct ChrtVaults {
+    /// Campaign authority that paid for the vaults in `start_campaign` and gets their rent back.
+    pub payer: Pubkey,
+    /// Deposits of the ended campaign left to withdraw, whatever was sent to the vaults directly.
+    pub fee_exemption_deposits_sum: u64,
+    pub liquidation_deposits_sum: u64,
+}
+
+impl ChrtVaults {
+    pub fn deposits_sum_mut(&mut self, vault: ChrtVault) -> &mut u64 {
+        match vault {
+            ChrtVault::FeeExemption => &mut self.fee_exemption_deposits_sum,
+            ChrtVault::Liquidation => &mut self.liquidation_deposits_sum,
+        }
+    }
+}
+
+#[account(zero_copy)]
+#[derive(Debug)]
+pub struct ReferralCode {
+    /// Authority of the donor the code refers to.
+    pub donor: Pubkey,
+}
+
+#[account(zero_copy)]
+#[derive(Debug)]
+pub struct RewardClaim {
+    pub amount: u64,
+}
+
+#[account(zero_copy)]
+#[derive(Debug)]
+#[repr(packed)]
 pub struct Donations {
     pub donations_sum: u64,
+    /// Donations made before the funding goal was reached. They can still be refunded, so they
+    /// count toward the donor's stats and referral rewards only once the goal is reached.
+    pub uncredited_sum: u64,
+    /// Donations in `uncredited_sum` made with a referer, fee included.
+    pub uncredited_referred_sum: u64,
+    /// Authority of the referer the donations in `uncredited_referred_sum` were made with.
+    pub referer: Pubkey,
+    /// Set on the total donations to a campaign once its funding goal is reached.
+    pub goal_reached: bool,
+    /// Set on the total donations to a campaign that ended with donations owed back to donors: all
+    /// of them if it missed its funding goal, or else the share of its locked milestones. They stay
+    /// in the vault for donors to refund pro rata to their donations.
+    pub refundable_sum: u64,
+}
+
+#[account(zero_copy)]
+#[derive(Debug)]
+pub struct TokenDonations {
+    pub mint: Pubkey,
+    pub donations_sum: u64,
+    pub withdrawn_sum: u64,
+    pub avoided_fees_sum: u64,
+    /// Set on the total token donations to a campaign whose vault was closed with locked
+    /// milestones: their share stays in the vault for donors to refund pro rata to their donations.
+    pub refundable_sum: u64,
+    /// Receives the rent of a vault left open for refunds once they are all made.
+    pub beneficiary: Pubkey,
+}
+
+#[account(zero_copy)]
+#[derive(Debug)]
+pub struct MilestoneVote {
+    pub weight: u64,
 }
 
 #[account(zero_copy)]
@@ -1,5 +1,11 @@
-use crate::state::*;
-use anchor_lang::prelude::*;
+use crate::{config::*, error::*, events::*, merkle::*, state::*};
+use anchor_lang::{prelude::*, AccountsClose};
+use anchor_spl::{
+    associated_token::{self, AssociatedToken, Create},
+    token::{self, Burn, CloseAccount, Mint, Token, TokenAccount, Transfer},
+};
+use bytemuck::Pod;
+use core::{cell::RefMut, mem::size_of};
 
 pub fn transfer(from: &AccountInfo, to: &AccountInfo, lamports: u64) -> Result<()> {
     **from.try_borrow_mut_lamports()? = (from.lamports())
@@ -9,22 +15,536 @@ pub fn transfer(from: &AccountInfo, to: &AccountInfo, lamports: u64) -> Result<(
     Ok(())
 }
 
-pub fn transfer_all_but_rent(from: &AccountInfo, to: &AccountInfo) -> Result<()> {
+pub fn transfer_all_but_rent(from: &AccountInfo, to: &AccountInfo) -> Result<u64> {
     let rent = Rent::get()?.minimum_balance(from.data_len());
-    **to.try_borrow_mut_lamports()? += (from.lamports())
+    let lamports = (from.lamports())
         .checked_sub(rent)
         .ok_or(ProgramError::InsufficientFunds)?;
+    **to.try_borrow_mut_lamports()? += lamports;
     **from.try_borrow_mut_lamports()? = rent;
+    Ok(lamports)
+}
+
+/// Pays the keeper the bounty for a finished reward distribution, once for the whole of it, and
+/// returns the season the distribution finished.
+pub fn finish_reward_distribution(
+    season: &AccountLoader<Season>,
+    fee_vault: &AccountInfo,
+    keeper: &AccountInfo,
+    bounty: u64,
+) -> Result<Season> {
+    let amount = pay_bounty(fee_vault, keeper, bounty)?;
+    emit!(BountyPaid {
+        keeper: keeper.key(),
+        amount,
+    });
+    Ok(*season.load()?)
+}
+
+/// Pays up to `bounty` lamports from the fee vault, never touching its rent.
+pub fn pay_bounty(fee_vault: &AccountInfo, keeper: &AccountInfo, bounty: u64) -> Result<u64> {
+    let available = fee_vault
+        .lamports()
+        .saturating_sub(Rent::get()?.minimum_balance(fee_vault.data_len()));
+    let lamports = bounty.min(available);
+    transfer(fee_vault, keeper, lamports)?;
+    Ok(lamports)
+}
+
+pub fn close_chrt_vaults<'info>(
+    token_program: &Program<'info, Token>,
+    chrt_mint: &Account<'info, Mint>,
+    platform: &AccountLoader<'info, Platform>,
+    platform_bump: u8,
+    vaults: [&Account<'info, TokenAccount>; 2],
+    destination: &AccountInfo<'info>,
+) -> Result<()> {
+    for vault in vaults {
+        close_chrt_vault(
+            token_program,
+            chrt_mint,
+            platform,
+            platform_bump,
+            vault,
+            destination,
+        )?;
+    }
+    Ok(())
+}
+
+/// Burns what is left in the CHRT vault and closes it.
+pub fn close_chrt_vault<'info>(
+    token_program: &Program<'info, Token>,
+    chrt_mint: &Account<'info, Mint>,
+    platform: &AccountLoader<'info, Platform>,
+    platform_bump: u8,
+    vault: &Account<'info, TokenAccount>,
+    destination: &AccountInfo<'info>,
+) -> Result<()> {
+    let signer: &[&[&[u8]]] = &[&[b"platform", &[platform_bump]]];
+    let cpi_ctx = CpiContext::new_with_signer(
+        token_program.to_account_info(),
+        Burn {
+            mint: chrt_mint.to_account_info(),
+            from: vault.to_account_info(),
+            authority: platform.to_account_info(),
+        },
+        signer,
+    );
+    token::burn(cpi_ctx, vault.amount)?;
+    close_token_account(token_program, platform, platform_bump, vault, destination)
+}
+
+/// Adds `amount` to the depositor's record and the campaign's deposits and returns the deposit
+/// after it.
+pub fn record_chrt_deposit(
+    chrt_deposit: &AccountLoader<ChrtDeposit>,
+    campaign: &mut Campaign,
+    depositor: Pubkey,
+    vault: ChrtVault,
+    amount: u64,
+) -> Result<u64> {
+    match vault {
+        ChrtVault::FeeExemption => campaign.fee_exemption_deposits_sum += amount,
+        ChrtVault::Liquidation => campaign.liquidation_deposits_sum += amount,
+    }
+    let record = &mut if chrt_deposit
+        .to_account_info()
+        .try_borrow_data()?
+        .starts_with(&[0; 8])
+    {
+        let mut record = chrt_deposit.load_init()?;
+        record.depositor = depositor;
+        record.campaign_id = campaign.id;
+        record.vault = vault as u8;
+        record
+    } else {
+        chrt_deposit.load_mut()?
+    };
+    record.amount += amount;
+    Ok(record.amount)
+}
+
+pub fn transfer_tokens<'info>(
+    token_program: &Program<'info, Token>,
+    platform: &AccountLoader<'info, Platform>,
+    platform_bump: u8,
+    from: &Account<'info, TokenAccount>,
+    to: &Account<'info, TokenAccount>,
+    amount: u64,
+) -> Result<()> {
+    let signer: &[&[&[u8]]] = &[&[b"platform", &[platform_bump]]];
+    let cpi_ctx = CpiContext::new_with_signer(
+        token_program.to_account_info(),
+        Transfer {
+            from: from.to_account_info(),
+            to: to.to_account_info(),
+            authority: platform.to_account_info(),
+        },
+        signer,
+    );
+    token::transfer(cpi_ctx, amount)
+}
+
+pub fn close_token_account<'info>(
+    token_program: &Program<'info, Token>,
+    platform: &AccountLoader<'info, Platform>,
+    platform_bump: u8,
+    account: &Account<'info, TokenAccount>,
+    destination: &AccountInfo<'info>,
+) -> Result<()> {
+    let signer: &[&[&[u8]]] = &[&[b"platform", &[platform_bump]]];
+    let cpi_ctx = CpiContext::new_with_signer(
+        token_program.to_account_info(),
+        CloseAccount {
+            account: account.to_account_info(),
+            destination: destination.clone(),
+            authority: platform.to_account_info(),
+        },
+        signer,
+    );
+    token::close_account(cpi_ctx)
+}
+
+/// Records the CHRT deposits left in the vaults of an ended campaign for depositors to withdraw,
+/// and closes the vaults without any, along with the record once neither has any.
+#[allow(clippy::too_many_arguments)]
+pub fn end_chrt_vaults<'info>(
+    token_program: &Program<'info, Token>,
+    chrt_mint: &Account<'info, Mint>,
+    platform: &AccountLoader<'info, Platform>,
+    platform_bump: u8,
+    campaign: &Campaign,
+    chrt_vaults: &AccountLoader<'info, ChrtVaults>,
+    vaults: [&Account<'info, TokenAccount>; 2],
+    payer: &AccountInfo<'info>,
+) -> Result<()> {
+    let deposits_sums = {
+        let record = &mut chrt_vaults.load_mut()?;
+        record.fee_exemption_deposits_sum = campaign.fee_exemption_deposits_sum;
+        record.liquidation_deposits_sum = campaign.liquidation_deposits_sum;
+        [
+            record.fee_exemption_deposits_sum,
+            record.liquidation_deposits_sum,
+        ]
+    };
+    for (vault, deposits_sum) in vaults.into_iter().zip(deposits_sums) {
+        if deposits_sum == 0 {
+            close_chrt_vault(
+                token_program,
+                chrt_mint,
+                platform,
+                platform_bump,
+                vault,
+                payer,
+            )?;
+        }
+    }
+    if deposits_sums == [0, 0] {
+        chrt_vaults.close(payer.clone())?;
+    }
     Ok(())
 }
 
-pub fn add_to_top(top: &mut [DonorRecord], donor_record: DonorRecord) {
-    let top_len = top
-        .iter()
-        .position(|d| d.donor.to_bytes() == [0; 32])
-        .unwrap_or(top.len());
+/// Creates the associated CHRT account of the referer if the given one is empty, otherwise
+/// checks that it is a CHRT account the referer owns.
+pub fn init_referer_chrt_if_needed<'info>(
+    associated_token_program: &Program<'info, AssociatedToken>,
+    create: Create<'info>,
+) -> Result<()> {
+    if create.associated_token.data_is_empty() {
+        let cpi_ctx = CpiContext::new(associated_token_program.to_account_info(), create);
+        return associated_token::create(cpi_ctx);
+    }
+    let referer_chrt = Account::<TokenAccount>::try_from(&create.associated_token)?;
+    if referer_chrt.owner != create.authority.key() {
+        return err!(ErrorCode::ConstraintTokenOwner);
+    }
+    if referer_chrt.mint != create.mint.key() {
+        return err!(ErrorCode::ConstraintTokenMint);
+    }
+    Ok(())
+}
+
+pub fn remove_active_campaign(platform: &mut Platform, id: u16) -> Result<CampaignRecord> {
+    let len = platform.active_campaigns_count as usize;
+    let i = platform.active_campaigns[..len]
+        .binary_search_by_key(&id, |c| c.id)
+        .map_err(|_| CrowdfundingError::CampaignInactive)?;
+    let campaign = platform.active_campaigns[i];
+    platform.active_campaigns[i] = Default::default();
+    platform.active_campaigns[i..len].rotate_left(1);
+    platform.active_campaigns_count -= 1;
+    Ok(campaign)
+}
+
+pub fn check_refunds_settled(
+    campaign: &Campaign,
+    campaign_record: &CampaignRecord,
+    total_donations_to_campaign: &Donations,
+) -> Result<()> {
+    // donations of a campaign that missed its goal belong to donors until refunded
+    if campaign_record.donations_sum < campaign.goal
+        && total_donations_to_campaign.donations_sum != 0
+    {
+        return err!(CrowdfundingError::FundingGoalNotReached);
+    }
+    Ok(())
+}
+
+/// Lets donors credit their donations to a campaign that liquidations pushed over its goal.
+pub fn mark_goal_reached(
+    campaign: &Campaign,
+    campaign_record: &CampaignRecord,
+    total_donations_to_campaign: &mut Donations,
+) {
+    if campaign_record.donations_sum >= campaign.goal {
+        total_donations_to_campaign.goal_reached = true;
+    }
+}
+
+/// Splits the donations left in a stopped campaign, or in one of its token vaults, into the
+/// beneficiary's unlocked share and the share of its locked milestones, refundable to donors.
+pub fn release_donations(
+    campaign: &Campaign,
+    donations_sum: u64,
+    withdrawn_sum: u64,
+) -> (u64, u64) {
+    let unlocked_sum = campaign.unlocked_share(donations_sum).max(withdrawn_sum);
+    (unlocked_sum - withdrawn_sum, donations_sum - unlocked_sum)
+}
+
+/// Donor's pro rata share of the donations refundable to all donors.
+pub fn refund_share(donations_sum: u64, refundable_sum: u64, total_donations_sum: u64) -> u64 {
+    (donations_sum as u128 * refundable_sum as u128)
+        .checked_div(total_donations_sum as u128)
+        .unwrap_or(0) as u64
+}
+
+/// Ends the reward procedure without rewarding anyone, forgetting the recorded donors.
+pub fn reset_reward_procedure(platform: &mut Platform, records: &mut [SeasonalTopRecord]) {
+    platform.reward_procedure_is_in_process = false;
+    platform.donors_recorded = 0;
+    records.fill(SeasonalTopRecord::default());
+}
+
+pub fn check_reward_procedure_not_timed_out(platform: &Platform, now: u32) -> Result<()> {
+    if platform.reward_procedure_is_in_process && platform.reward_procedure_timed_out(now) {
+        return err!(CrowdfundingError::RewardProcedureTimedOut);
+    }
+    Ok(())
+}
+
+/// Checks a referral code before it seeds its PDA, where an over-long one would panic. Codes are
+/// lowercase, so that no two of them differ only in case.
+pub fn referral_code_seed(code: &str) -> Result<&[u8]> {
+    if code.is_empty()
+        || code.len() > REFERRAL_CODE_CAPACITY
+        || !code
+            .bytes()
+            .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'-' || b == b'_')
+    {
+        return err!(CrowdfundingError::InvalidReferralCode);
+    }
+    Ok(code.as_bytes())
+}
+
+pub fn check_token_vaults_closed(campaign: &Campaign) -> Result<()> {
+    if campaign.token_vaults_count != 0 {
+        return err!(CrowdfundingError::TokenVaultsOpen);
+    }
+    Ok(())
+}
+
+pub fn calculate_fee(amount: u64, fee_basis_points: u16) -> u64 {
+    (amount as u128 * fee_basis_points as u128 / 10000) as u64
+}
+
+/// Campaigns donate without fees while their fee exemption deposits or their stake reach the
+/// limit.
+pub fn is_fee_exempted(
+    campaign: &Campaign,
+    stake_pool: &StakePool,
+    fee_exemption_limit: u64,
+) -> bool {
+    campaign.fee_exemption_deposits_sum >= fee_exemption_limit
+        || stake_pool.staked_sum >= fee_exemption_limit
+}
+
+pub fn check_fee_basis_points(fee_basis_points: u16) -> Result<()> {
+    if fee_basis_points > 10000 {
+        return err!(CrowdfundingError::InvalidFeeBasisPoints);
+    }
+    Ok(())
+}
+
+pub fn check_liquidation_limit(liquidation_limit: u64) -> Result<()> {
+    if liquidation_limit == 0 {
+        return err!(CrowdfundingError::InvalidLiquidationLimit);
+    }
+    Ok(())
+}
+
+pub fn check_seasonal_top_capacity(capacity: u16) -> Result<()> {
+    if capacity == 0 || capacity > MAX_SEASONAL_TOP_CAPACITY {
+        return err!(CrowdfundingError::InvalidSeasonalTopCapacity);
+    }
+    Ok(())
+}
+
+pub fn set_reward_tiers(platform: &mut Platform, reward_tiers: &[RewardTier]) -> Result<()> {
+    if reward_tiers.len() > REWARD_TIERS_CAPACITY
+        || matches!(reward_tiers.first(), Some(t) if t.last_rank == 0)
+        || reward_tiers
+            .windows(2)
+            .any(|w| w[0].last_rank >= w[1].last_rank)
+        || matches!(reward_tiers.last(), Some(t) if t.last_rank > MAX_SEASONAL_TOP_CAPACITY)
+    {
+        return err!(CrowdfundingError::InvalidRewardTiers);
+    }
+    let mut tiers = [RewardTier::default(); REWARD_TIERS_CAPACITY];
+    tiers[..reward_tiers.len()].copy_from_slice(reward_tiers);
+    platform.reward_tiers = tiers;
+    platform.reward_tiers_count = reward_tiers.len() as u8;
+    Ok(())
+}
+
+/// Borrows an account holding a `T` followed by a list of `R`s.
+pub fn load_with_records<'a, T: Pod, R: Pod>(
+    account: &'a AccountInfo,
+) -> Result<(RefMut<'a, T>, RefMut<'a, [R]>)> {
+    let data = account.try_borrow_mut_data()?;
+    Ok(RefMut::map_split(data, |data| {
+        let (header, records) = data[8..].split_at_mut(size_of::<T>());
+        (
+            bytemuck::from_bytes_mut(header),
+            bytemuck::cast_slice_mut(records),
+        )
+    }))
+}
+
+/// Rewards the page of the seasonal top starting at its cursor, saving it to `season`, and
+/// ends the reward procedure once the whole top is through. `donors` are the `Donor` accounts
+/// of the page; the rewarded ones are marked and the page returned in rank order.
+pub fn reward_seasonal_top_page(
+    platform: &mut Platform,
+    seasonal_top: &AccountInfo,
+    season: &AccountInfo,
+    donors: &[&AccountInfo],
+    commit: bool,
+    now: u32,
+) -> Result<Vec<SeasonRecord>> {
+    let (mut seasonal_top, mut records) =
+        load_with_records::<SeasonalTop, SeasonalTopRecord>(seasonal_top)?;
+    let (mut season, mut season_records) = load_with_records::<Season, SeasonRecord>(season)?;
+    let len = top_len(&records);
+    let cursor = seasonal_top.cursor as usize;
+    let rewards = platform.calculate_rewards(
+        &records[..len]
+            .iter()
+            .map(|r| r.unrewarded_sum)
+            .collect::<Vec<_>>(),
+    );
+
+    // once started, the distribution is finished regardless of the timeout
+    if cursor == 0 {
+        check_reward_procedure_not_timed_out(platform, now)?;
+        if !platform.reward_procedure_is_in_process
+            || platform.donors_recorded != platform.donors_count
+        {
+            return err!(CrowdfundingError::NotAllDonorsRecorded);
+        }
+        season.id = platform.season;
+        season.start_ts = platform.last_reward_procedure_ts;
+        season.donations_sum =
+            platform.recorded_donations_sum - platform.ended_seasons_donations_sum;
+        season.donors_recorded = platform.donors_recorded;
+        season.rewards_committed = commit;
+        if commit {
+            let leaves = records[..len]
+                .iter()
+                .zip(&rewards)
+                .filter(|(_, &reward)| reward != 0)
+                .map(|(record, &reward)| reward_leaf(&{ record.donor }, reward))
+                .collect::<Vec<_>>();
+            season.merkle_root = merkle_root(&leaves);
+        }
+    } else if season.rewards_committed != commit {
+        return err!(CrowdfundingError::RewardModeMismatch);
+    }
+
+    let end = cursor + donors.len();
+    if end > len || (donors.is_empty() && cursor < len) {
+        return err!(CrowdfundingError::IncorrectSeasonalTop);
+    }
+
+    for (i, &donor) in (cursor..end).zip(donors) {
+        let donor = AccountLoader::<Donor>::try_from(donor)?;
+        let donor = &mut donor.load_mut()?;
+        if donor.authority != records[i].donor {
+            return err!(CrowdfundingError::IncorrectSeasonalTop);
+        }
+        season_records[i] = SeasonRecord {
+            donor: records[i].donor,
+            donations_sum: records[i].donations_sum,
+            reward: rewards[i],
+        };
+        // donors without a reward keep their donations for the next season
+        if rewards[i] != 0 {
+            donor.rewarded_donations_sum = donor.recorded_donations_sum;
+            season.donors_rewarded += 1;
+            season.rewards_sum += rewards[i];
+        }
+    }
+    seasonal_top.cursor = end as u16;
+
+    if end == len {
+        season.end_ts = now;
+        platform.ended_seasons_donations_sum = platform.recorded_donations_sum;
+        platform.reward_procedure_is_in_process = false;
+        platform.donors_recorded = 0;
+        seasonal_top.cursor = 0;
+        records.fill(SeasonalTopRecord::default());
+    }
+
+    Ok(season_records[cursor..end].to_vec())
+}
+
+pub fn copy_str(dst: &mut [u8], src: &str, error: CrowdfundingError) -> Result<()> {
+    if src.len() > dst.len() {
+        return Err(error.into());
+    }
+    dst.fill(0);
+    dst[..src.len()].copy_from_slice(src.as_bytes());
+    Ok(())
+}
+
+/// Freezes the donations sum a donor has when the reward procedure first reaches them,
+/// so that later donations count toward the next season.
+pub fn snapshot_donations(platform: &mut Platform, donor: &mut Donor) {
+    if platform.reward_procedure_is_in_process && donor.season != platform.season {
+        donor.season = platform.season;
+        donor.season_donations_sum = donor.donations_sum - donor.recorded_donations_sum;
+        platform.recorded_donations_sum += donor.season_donations_sum;
+        donor.recorded_donations_sum = donor.donations_sum;
+    }
+}
+
+/// Adds the uncredited donations to a campaign that reached its goal to the donor's stats and
+/// returns the referred ones to reward, keeping them if there is no referer to reward.
+pub fn credit_donor(
+    platform: &mut Platform,
+    donor: &mut Donor,
+    donations: &mut Donations,
+    with_referer: bool,
+) -> u64 {
+    snapshot_donations(platform, donor);
+    donor.donations_sum += donations.uncredited_sum;
+    donations.uncredited_sum = 0;
+    if !with_referer {
+        return 0;
+    }
+    let referred_sum = donations.uncredited_referred_sum;
+    donations.uncredited_referred_sum = 0;
+    referred_sum
+}
+
+/// Records donations of `lamports` referred by the referer and returns the referral reward
+/// within the season cap.
+pub fn record_referral(
+    platform: &Platform,
+    donor: &mut Donor,
+    referer: &mut Donor,
+    referer_authority: Pubkey,
+    lamports: u64,
+) -> u64 {
+    if donor.referer == Pubkey::default() {
+        donor.referer = referer_authority;
+        referer.referred_donors += 1;
+    }
+    referer.referred_volume += lamports;
+
+    if referer.referral_season != platform.season {
+        referer.referral_season = platform.season;
+        referer.season_referral_rewards = 0;
+    }
+    let mut amount = platform.referral_reward(lamports);
+    if platform.referral_season_cap != 0 {
+        amount = amount.min(
+            platform
+                .referral_season_cap
+                .saturating_sub(referer.season_referral_rewards),
+        );
+    }
+    referer.season_referral_rewards += amount;
+    referer.referral_rewards_sum += amount;
+    amount
+}
+
+pub fn add_to_top<T: TopRecord>(top: &mut [T], donor_record: T) {
+    let top_len = top_len(top);
 
-    let cur_i = if let Some(cur_i) = top.iter().position(|d| d.donor == donor_record.donor) {
+    let cur_i = if let Some(cur_i) = top.iter().position(|d| d.donor() == donor_record.donor()) {
         // assign new sum
         top[cur_i] = donor_record;
         cur_i
@@ -35,7 +555,7 @@ pub fn add_to_top(top: &mut [DonorRecord], donor_record: DonorRecord) {
     } else {
         // no space to push, so replace with last if eligible
         let last = top.last_mut().unwrap();
-        if last.donations_sum > donor_record.donations_sum {
+        if last.donations_sum() > donor_record.donations_sum() {
             return;
         }
         *last = donor_record;
@@ -43,6 +563,6 @@ pub fn add_to_top(top: &mut [DonorRecord], donor_record: DonorRecord) {
     };
 
     // sort donor
-    let new_i = top[..cur_i].partition_point(|d| d.donations_sum >= donor_record.donations_sum);
+    let new_i = top[..cur_i].partition_point(|d| d.donations_sum() >= donor_record.donations_sum());
     top[new_i..=cur_i].rotate_right(1);
 }
@@ -1,25 +1,52 @@
 use crate::ctx::*;
-use anchor_lang::{prelude::*, InstructionData};
+use anchor_lang::{prelude::*, Discriminator, InstructionData};
 use core::{mem::size_of, result::Result};
 use crowdfunding::{config::*, state::*};
-use solana_program::{instruction::Instruction, system_program, sysvar};
+use crowdfunding_client::{
+    accounts::{decode, decode_with_records},
+    instructions as ix,
+};
+use solana_program::{instruction::Instruction, system_instruction};
 use solana_program_test::{BanksClientError, ProgramTestContext};
-use solana_sdk::{signature::Keypair, signer::Signer, transaction::Transaction};
-use spl_associated_token_account::get_associated_token_address;
+use solana_sdk::{
+    signature::Keypair,
+    signer::Signer,
+    transaction::{Transaction, TransactionError},
+};
+
+pub use crowdfunding_client::pda::*;
+
+pub const CAMPAIGN_TITLE: &str = "Campaign";
+pub const CAMPAIGN_URI: &str = "https://example.com/campaign.json";
+pub const SEASONAL_TOP_CAPACITY: u16 = 10;
+
+async fn get_account_data(
+    ptc: &mut ProgramTestContext,
+    address: Pubkey,
+) -> Result<Vec<u8>, BanksClientError> {
+    Ok(ptc
+        .banks_client
+        .get_account(address)
+        .await?
+        .ok_or(BanksClientError::ClientError("Account not present"))?
+        .data)
+}
 
 pub async fn fetch<T: AccountDeserialize>(
     ptc: &mut ProgramTestContext,
     address: Pubkey,
 ) -> Result<T, BanksClientError> {
-    T::try_deserialize(
-        &mut &*ptc
-            .banks_client
-            .get_account(address)
-            .await?
-            .ok_or(BanksClientError::ClientError("Account not present"))?
-            .data,
-    )
-    .map_err(|_| BanksClientError::ClientError("Failed to deserialize account"))
+    decode(&get_account_data(ptc, address).await?)
+        .map_err(|_| BanksClientError::ClientError("Failed to deserialize account"))
+}
+
+/// Fetches a zero copy account followed by records, like `Season` and `SeasonalTop`.
+pub async fn fetch_with_records<T: AccountDeserialize, R: bytemuck::Pod>(
+    ptc: &mut ProgramTestContext,
+    address: Pubkey,
+) -> Result<(T, Vec<R>), BanksClientError> {
+    decode_with_records(&get_account_data(ptc, address).await?)
+        .map_err(|_| BanksClientError::ClientError("Failed to deserialize account"))
 }
 
 pub async fn fetch_active_campaigns(
@@ -51,16 +78,14 @@ pub async fn fetch_platform_top(
 pub async fn fetch_seasonal_top(
     ptc: &mut ProgramTestContext,
     ctx: &Ctx,
-) -> Result<([DonorRecord; SEASONAL_TOP_CAPACITY], usize), BanksClientError> {
-    let platform: Platform = fetch(ptc, ctx.platform).await?;
-    Ok((
-        platform.seasonal_top,
-        platform
-            .seasonal_top
-            .iter()
-            .position(|d| d.donor.to_bytes() == [0; 32])
-            .unwrap_or(platform.top.len()),
-    ))
+) -> Result<(Vec<SeasonalTopRecord>, usize), BanksClientError> {
+    let (_, seasonal_top) =
+        fetch_with_records::<SeasonalTop, SeasonalTopRecord>(ptc, ctx.seasonal_top).await?;
+    let len = seasonal_top
+        .iter()
+        .position(|d| d.donor.to_bytes() == [0; 32])
+        .unwrap_or(seasonal_top.len());
+    Ok((seasonal_top, len))
 }
 
 pub async fn fetch_campaign_top(
@@ -104,36 +129,82 @@ pub async fn get_fee_vault_balance(
     get_balance_without_rent::<Vault>(ptc, ctx.fee_vault).await
 }
 
-fn find_pda(seeds: &[&[u8]]) -> Pubkey {
-    Pubkey::find_program_address(seeds, &crowdfunding::ID).0
-}
-
-pub fn find_donor(donor_authority: Pubkey) -> Pubkey {
-    find_pda(&[b"donor", &donor_authority.to_bytes()])
-}
-
-pub fn find_donor_donations_to_campaign(donor_authority: Pubkey, campaign_id: u16) -> Pubkey {
-    find_pda(&[
-        b"donations",
-        &donor_authority.to_bytes(),
-        &campaign_id.to_le_bytes(),
-    ])
-}
-
-pub fn find_campaign(campaign_id: u16) -> Pubkey {
-    find_pda(&[b"campaign", &campaign_id.to_le_bytes()])
+/// Sends the instruction paid by the first of the signers.
+async fn process(
+    ptc: &mut ProgramTestContext,
+    instruction: Instruction,
+    signers: &[&Keypair],
+) -> Result<(), BanksClientError> {
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction],
+        Some(&signers[0].pubkey()),
+        &signers.to_vec(),
+        ptc.last_blockhash,
+    );
+    ptc.banks_client.process_transaction(transaction).await
 }
 
-pub fn find_total_donations_to_campaign(campaign_id: u16) -> Pubkey {
-    find_pda(&[b"donations", &campaign_id.to_le_bytes()])
+/// Sends the instruction paid by the test payer, for permissionless instructions
+/// and for signers without lamports.
+async fn process_paid_by_payer(
+    ptc: &mut ProgramTestContext,
+    instruction: Instruction,
+    signers: &[&Keypair],
+) -> Result<(), BanksClientError> {
+    let mut all_signers = vec![&ptc.payer];
+    all_signers.extend(signers);
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction],
+        Some(&ptc.payer.pubkey()),
+        &all_signers,
+        ptc.last_blockhash,
+    );
+    ptc.banks_client.process_transaction(transaction).await
 }
 
-pub fn find_fee_exemption_vault(campaign_id: u16) -> Pubkey {
-    find_pda(&[b"fee_exemption_vault", &campaign_id.to_le_bytes()])
+/// Sends the instruction and returns its logs. The banks client only returns the logs of
+/// failed simulations, so they come from a simulation which a trailing transfer of more
+/// lamports than the payer has makes fail.
+pub async fn process_with_logs(
+    ptc: &mut ProgramTestContext,
+    instruction: Instruction,
+    signers: &[&Keypair],
+) -> Result<Vec<String>, BanksClientError> {
+    let payer = signers[0].pubkey();
+    let simulation = Transaction::new_signed_with_payer(
+        &[
+            instruction.clone(),
+            system_instruction::transfer(&payer, &payer, u64::MAX),
+        ],
+        Some(&payer),
+        &signers.to_vec(),
+        ptc.last_blockhash,
+    );
+    let logs = match ptc
+        .banks_client
+        .process_transaction_with_preflight(simulation)
+        .await
+    {
+        Err(BanksClientError::SimulationError {
+            err: TransactionError::InstructionError(1, _),
+            logs,
+            ..
+        }) => logs,
+        Err(err) => return Err(err),
+        Ok(()) => unreachable!("the trailing transfer always fails"),
+    };
+    process(ptc, instruction, signers).await?;
+    Ok(logs)
 }
 
-pub fn find_liquidation_vault(campaign_id: u16) -> Pubkey {
-    find_pda(&[b"liquidation_vault", &campaign_id.to_le_bytes()])
+/// Events of type `T` that `emit!` logged as base64 program data.
+pub fn events<T: AnchorDeserialize + Discriminator>(logs: &[String]) -> Vec<T> {
+    logs.iter()
+        .filter_map(|log| log.strip_prefix("Program data: "))
+        .filter_map(|data| base64::decode(data).ok())
+        .filter(|data| data.starts_with(&T::discriminator()))
+        .map(|data| T::deserialize(&mut &data[8..]).unwrap())
+        .collect()
 }
 
 pub async fn initialize(
@@ -145,245 +216,261 @@ pub async fn initialize(
     fee_exemption_limit: u64,
     liquidation_limit: u64,
 ) -> Result<(), BanksClientError> {
-    ptc.banks_client
-        .process_transaction(Transaction::new_signed_with_payer(
-            &[Instruction {
-                program_id: crowdfunding::ID,
-                data: crowdfunding::instruction::Initialize {
-                    reward_cooldown,
-                    reward_amount,
-                    fee_basis_points,
-                    fee_exemption_limit,
-                    liquidation_limit,
-                }
-                .data(),
-                accounts: crowdfunding::accounts::Initialize {
-                    platform: ctx.platform,
-                    platform_authority: ctx.platform_authority.pubkey(),
-                    fee_vault: ctx.fee_vault,
-                    sol_vault: ctx.sol_vault,
-                    chrt_mint: ctx.chrt_mint,
-                    rent: sysvar::rent::id(),
-                    token_program: anchor_spl::token::ID,
-                    system_program: system_program::ID,
-                }
-                .to_account_metas(None),
-            }],
-            Some(&ctx.platform_authority.pubkey()),
-            &[&ctx.platform_authority],
-            ptc.last_blockhash,
-        ))
-        .await
+    let instruction = ix::initialize(
+        ctx.platform_authority.pubkey(),
+        SEASONAL_TOP_CAPACITY,
+        reward_cooldown,
+        reward_amount,
+        RewardDistribution::Tiers,
+        vec![RewardTier {
+            last_rank: SEASONAL_TOP_CAPACITY,
+            amount: reward_amount,
+        }],
+        fee_basis_points,
+        fee_exemption_limit,
+        liquidation_limit,
+    );
+    process(ptc, instruction, &[&ctx.platform_authority]).await
 }
 
 pub async fn register_donor(
     ptc: &mut ProgramTestContext,
-    ctx: &Ctx,
     donor_authority: &Keypair,
 ) -> Result<(), BanksClientError> {
-    ptc.banks_client
-        .process_transaction(Transaction::new_signed_with_payer(
-            &[Instruction {
-                program_id: crowdfunding::ID,
-                data: crowdfunding::instruction::RegisterDonor {}.data(),
-                accounts: crowdfunding::accounts::RegisterDonor {
-                    platform: ctx.platform,
-                    donor: find_donor(donor_authority.pubkey()),
-                    donor_authority: donor_authority.pubkey(),
-                    system_program: system_program::ID,
-                }
-                .to_account_metas(None),
-            }],
-            Some(&donor_authority.pubkey()),
-            &[donor_authority],
-            ptc.last_blockhash,
-        ))
-        .await
+    let instruction = ix::register_donor(donor_authority.pubkey());
+    process(ptc, instruction, &[donor_authority]).await
+}
+
+pub async fn register_referral_code(
+    ptc: &mut ProgramTestContext,
+    donor_authority: &Keypair,
+    code: &str,
+) -> Result<(), BanksClientError> {
+    let instruction = ix::register_referral_code(donor_authority.pubkey(), code.to_string());
+    process(ptc, instruction, &[donor_authority]).await
+}
+
+/// Registers `code` as is, which the client would lowercase and could not derive the PDA of if
+/// it was over-long.
+pub async fn register_raw_referral_code(
+    ptc: &mut ProgramTestContext,
+    donor_authority: &Keypair,
+    code: &str,
+) -> Result<(), BanksClientError> {
+    let mut instruction = ix::register_referral_code(donor_authority.pubkey(), "code".to_string());
+    instruction.data = crowdfunding::instruction::RegisterReferralCode {
+        code: code.to_string(),
+    }
+    .data();
+    process(ptc, instruction, &[donor_authority]).await
 }
 
 pub async fn start_campaign(
     ptc: &mut ProgramTestContext,
     ctx: &Ctx,
+) -> Result<(), BanksClientError> {
+    start_campaign_with_options(ptc, ctx, None, None, vec![]).await
+}
+
+pub async fn start_campaign_with_options(
+    ptc: &mut ProgramTestContext,
+    ctx: &Ctx,
+    goal: Option<u64>,
+    deadline: Option<u32>,
+    milestones: Vec<u16>,
 ) -> Result<(), BanksClientError> {
     let platform: Platform = fetch(ptc, ctx.platform).await?;
-    let id = platform.campaigns_count;
-
-    ptc.banks_client
-        .process_transaction(Transaction::new_signed_with_payer(
-            &[Instruction {
-                program_id: crowdfunding::ID,
-                data: crowdfunding::instruction::StartCampaign {}.data(),
-                accounts: crowdfunding::accounts::StartCampaign {
-                    platform: ctx.platform,
-                    chrt_mint: ctx.chrt_mint,
-                    campaign: find_campaign(id),
-                    campaign_authority: ctx.campaign_authority.pubkey(),
-                    total_donations_to_campaign: find_total_donations_to_campaign(id),
-                    fee_exemption_vault: find_fee_exemption_vault(id),
-                    liquidation_vault: find_liquidation_vault(id),
-                    rent: sysvar::rent::id(),
-                    token_program: anchor_spl::token::ID,
-                    system_program: system_program::ID,
-                }
-                .to_account_metas(None),
-            }],
-            Some(&ctx.campaign_authority.pubkey()),
-            &[&ctx.campaign_authority],
-            ptc.last_blockhash,
-        ))
-        .await
+    let instruction = ix::start_campaign(
+        ctx.campaign_authority.pubkey(),
+        platform.campaigns_count,
+        ctx.campaign_beneficiary.pubkey(),
+        CAMPAIGN_TITLE.to_string(),
+        CAMPAIGN_URI.to_string(),
+        CampaignCategory::Charity,
+        goal,
+        deadline,
+        milestones,
+    );
+    process(ptc, instruction, &[&ctx.campaign_authority]).await
 }
 
 pub async fn donate(
     ptc: &mut ProgramTestContext,
-    ctx: &Ctx,
     donor_authority: &Keypair,
     campaign_id: u16,
     amount: u64,
 ) -> Result<(), BanksClientError> {
-    ptc.banks_client
-        .process_transaction(Transaction::new_signed_with_payer(
-            &[Instruction {
-                program_id: crowdfunding::ID,
-                data: crowdfunding::instruction::Donate { amount }.data(),
-                accounts: crowdfunding::accounts::Donate {
-                    platform: ctx.platform,
-                    fee_vault: ctx.fee_vault,
-                    sol_vault: ctx.sol_vault,
-                    campaign: find_campaign(campaign_id),
-                    total_donations_to_campaign: find_total_donations_to_campaign(campaign_id),
-                    fee_exemption_vault: find_fee_exemption_vault(campaign_id),
-                    donor: find_donor(donor_authority.pubkey()),
-                    donor_authority: donor_authority.pubkey(),
-                    donor_donations_to_campaign: find_donor_donations_to_campaign(
-                        donor_authority.pubkey(),
-                        campaign_id,
-                    ),
-                    system_program: system_program::ID,
-                }
-                .to_account_metas(None),
-            }],
-            Some(&donor_authority.pubkey()),
-            &[donor_authority],
-            ptc.last_blockhash,
-        ))
-        .await
+    let instruction = ix::donate(donor_authority.pubkey(), campaign_id, amount);
+    process(ptc, instruction, &[donor_authority]).await
 }
 
 pub async fn donate_with_referer(
     ptc: &mut ProgramTestContext,
-    ctx: &Ctx,
     donor_authority: &Keypair,
     campaign_id: u16,
     amount: u64,
     referer_authority: Pubkey,
 ) -> Result<(), BanksClientError> {
-    ptc.banks_client
-        .process_transaction(Transaction::new_signed_with_payer(
-            &[Instruction {
-                program_id: crowdfunding::ID,
-                data: crowdfunding::instruction::DonateWithReferer { amount }.data(),
-                accounts: crowdfunding::accounts::DonateWithReferer {
-                    donate: crowdfunding::accounts::Donate {
-                        platform: ctx.platform,
-                        fee_vault: ctx.fee_vault,
-                        sol_vault: ctx.sol_vault,
-                        campaign: find_campaign(campaign_id),
-                        total_donations_to_campaign: find_total_donations_to_campaign(campaign_id),
-                        fee_exemption_vault: find_fee_exemption_vault(campaign_id),
-                        donor: find_donor(donor_authority.pubkey()),
-                        donor_authority: donor_authority.pubkey(),
-                        donor_donations_to_campaign: find_donor_donations_to_campaign(
-                            donor_authority.pubkey(),
-                            campaign_id,
-                        ),
-                        system_program: system_program::ID,
-                    },
-                    chrt_mint: ctx.chrt_mint,
-                    referer: find_donor(referer_authority),
-                    referer_authority,
-                    referer_chrt: get_associated_token_address(&referer_authority, &ctx.chrt_mint),
-                    token_program: anchor_spl::token::ID,
-                }
-                .to_account_metas(None),
-            }],
-            Some(&donor_authority.pubkey()),
-            &[donor_authority],
-            ptc.last_blockhash,
-        ))
-        .await
+    let instruction = ix::donate_with_referer(
+        donor_authority.pubkey(),
+        campaign_id,
+        amount,
+        referer_authority,
+    );
+    process(ptc, instruction, &[donor_authority]).await
+}
+
+/// Donates with the referer's given CHRT account instead of its associated one.
+pub async fn donate_with_referer_chrt(
+    ptc: &mut ProgramTestContext,
+    donor_authority: &Keypair,
+    campaign_id: u16,
+    amount: u64,
+    referer_authority: Pubkey,
+    referer_chrt: Pubkey,
+) -> Result<(), BanksClientError> {
+    let mut instruction = ix::donate_with_referer(
+        donor_authority.pubkey(),
+        campaign_id,
+        amount,
+        referer_authority,
+    );
+    let referer_ata = find_chrt_ata(referer_authority);
+    for account in &mut instruction.accounts {
+        if account.pubkey == referer_ata {
+            account.pubkey = referer_chrt;
+        }
+    }
+    process(ptc, instruction, &[donor_authority]).await
+}
+
+pub async fn donate_with_referral_code(
+    ptc: &mut ProgramTestContext,
+    donor_authority: &Keypair,
+    campaign_id: u16,
+    amount: u64,
+    code: &str,
+    referer_authority: Pubkey,
+) -> Result<(), BanksClientError> {
+    let instruction = ix::donate_with_referral_code(
+        donor_authority.pubkey(),
+        campaign_id,
+        amount,
+        code.to_string(),
+        referer_authority,
+    );
+    process(ptc, instruction, &[donor_authority]).await
+}
+
+pub async fn credit_donations(
+    ptc: &mut ProgramTestContext,
+    donor_authority: &Keypair,
+    campaign_id: u16,
+) -> Result<(), BanksClientError> {
+    let instruction = ix::credit_donations(donor_authority.pubkey(), campaign_id);
+    process(ptc, instruction, &[donor_authority]).await
+}
+
+pub async fn credit_donations_with_referer(
+    ptc: &mut ProgramTestContext,
+    donor_authority: &Keypair,
+    campaign_id: u16,
+    referer_authority: Pubkey,
+) -> Result<(), BanksClientError> {
+    let instruction =
+        ix::credit_donations_with_referer(donor_authority.pubkey(), campaign_id, referer_authority);
+    process(ptc, instruction, &[donor_authority]).await
 }
 
 pub async fn record_donors(
     ptc: &mut ProgramTestContext,
     ctx: &Ctx,
+) -> Result<(), BanksClientError> {
+    record_some_donors(ptc, ctx, &ctx.donors).await
+}
+
+pub async fn record_some_donors(
+    ptc: &mut ProgramTestContext,
+    ctx: &Ctx,
+    donors: &[Keypair],
 ) -> Result<(), BanksClientError> {
     let clock: Clock = ptc.banks_client.get_sysvar().await.unwrap();
     ptc.warp_to_slot(clock.slot + 1).unwrap();
 
-    let mut accounts = crowdfunding::accounts::RecordDonors {
-        platform: ctx.platform,
-    }
-    .to_account_metas(None);
-
-    for donor in &ctx.donors {
-        accounts.push(AccountMeta {
-            pubkey: find_donor(donor.pubkey()),
-            is_signer: false,
-            is_writable: true,
-        });
-    }
-
-    ptc.banks_client
-        .process_transaction(Transaction::new_signed_with_payer(
-            &[Instruction {
-                program_id: crowdfunding::ID,
-                data: crowdfunding::instruction::RecordDonors {}.data(),
-                accounts,
-            }],
-            Some(&ctx.platform_authority.pubkey()),
-            &[&ctx.platform_authority],
-            ptc.last_blockhash,
-        ))
-        .await
+    let donors: Vec<_> = donors.iter().map(|d| d.pubkey()).collect();
+    let instruction = ix::record_donors(ctx.platform_authority.pubkey(), &donors);
+    process(ptc, instruction, &[&ctx.platform_authority]).await
 }
 
+/// Drops the rewards of the whole rest of the seasonal top in one page.
 pub async fn drop_rewards(ptc: &mut ProgramTestContext, ctx: &Ctx) -> Result<(), BanksClientError> {
-    let mut accounts = crowdfunding::accounts::DropRewards {
-        platform: ctx.platform,
-        chrt_mint: ctx.chrt_mint,
-        token_program: anchor_spl::token::ID,
-    }
-    .to_account_metas(None);
+    let page = fetch_reward_page(ptc, ctx, usize::MAX).await?;
+    drop_rewards_page(ptc, ctx, &page).await
+}
+
+/// Seasonal top donors from the cursor on, at most `page_size` of them.
+pub async fn fetch_reward_page(
+    ptc: &mut ProgramTestContext,
+    ctx: &Ctx,
+    page_size: usize,
+) -> Result<Vec<Pubkey>, BanksClientError> {
+    let (seasonal_top, records) =
+        fetch_with_records::<SeasonalTop, SeasonalTopRecord>(ptc, ctx.seasonal_top).await?;
+    Ok(records
+        .iter()
+        .skip(seasonal_top.cursor as usize)
+        .take_while(|d| d.donor.to_bytes() != [0; 32])
+        .take(page_size)
+        .map(|d| d.donor)
+        .collect())
+}
 
+pub async fn drop_rewards_page(
+    ptc: &mut ProgramTestContext,
+    ctx: &Ctx,
+    page: &[Pubkey],
+) -> Result<(), BanksClientError> {
     let platform: Platform = fetch(ptc, ctx.platform).await?;
-    let seasonal_top = platform.seasonal_top;
-
-    for donor in seasonal_top {
-        accounts.push(AccountMeta {
-            pubkey: find_donor(donor.donor),
-            is_signer: false,
-            is_writable: true,
-        });
-        accounts.push(AccountMeta {
-            pubkey: get_associated_token_address(&donor.donor, &ctx.chrt_mint),
-            is_signer: false,
-            is_writable: true,
-        });
-    }
+    let instruction = ix::drop_rewards(ctx.platform_authority.pubkey(), platform.season, page);
+    process(ptc, instruction, &[&ctx.platform_authority]).await
+}
 
-    ptc.banks_client
-        .process_transaction(Transaction::new_signed_with_payer(
-            &[Instruction {
-                program_id: crowdfunding::ID,
-                data: crowdfunding::instruction::DropRewards {}.data(),
-                accounts,
-            }],
-            Some(&ctx.platform_authority.pubkey()),
-            &[&ctx.platform_authority],
-            ptc.last_blockhash,
-        ))
-        .await
+pub async fn commit_rewards(
+    ptc: &mut ProgramTestContext,
+    ctx: &Ctx,
+) -> Result<(), BanksClientError> {
+    let platform: Platform = fetch(ptc, ctx.platform).await?;
+    let page = fetch_reward_page(ptc, ctx, usize::MAX).await?;
+    let instruction = ix::commit_rewards(ctx.platform_authority.pubkey(), platform.season, &page);
+    process(ptc, instruction, &[&ctx.platform_authority]).await
+}
+
+pub async fn claim_reward(
+    ptc: &mut ProgramTestContext,
+    donor_authority: &Keypair,
+    season_id: u32,
+    amount: u64,
+    proof: Vec<[u8; 32]>,
+) -> Result<(), BanksClientError> {
+    let instruction = ix::claim_reward(donor_authority.pubkey(), season_id, amount, proof);
+    process(ptc, instruction, &[donor_authority]).await
+}
+
+pub async fn resize_seasonal_top(
+    ptc: &mut ProgramTestContext,
+    ctx: &Ctx,
+    capacity: u16,
+) -> Result<(), BanksClientError> {
+    let instruction = ix::resize_seasonal_top(ctx.platform_authority.pubkey(), capacity);
+    process(ptc, instruction, &[&ctx.platform_authority]).await
+}
+
+pub async fn abort_reward_procedure(
+    ptc: &mut ProgramTestContext,
+    ctx: &Ctx,
+) -> Result<(), BanksClientError> {
+    let instruction = ix::abort_reward_procedure(ctx.platform_authority.pubkey());
+    process(ptc, instruction, &[&ctx.platform_authority]).await
 }
 
 pub async fn withdraw_donations(
@@ -391,24 +478,12 @@ pub async fn withdraw_donations(
     ctx: &Ctx,
     campaign_id: u16,
 ) -> Result<(), BanksClientError> {
-    ptc.banks_client
-        .process_transaction(Transaction::new_signed_with_payer(
-            &[Instruction {
-                program_id: crowdfunding::ID,
-                data: crowdfunding::instruction::WithdrawDonations {}.data(),
-                accounts: crowdfunding::accounts::WithdrawDonations {
-                    platform: ctx.platform,
-                    sol_vault: ctx.sol_vault,
-                    campaign: find_campaign(campaign_id),
-                    campaign_authority: ctx.campaign_authority.pubkey(),
-                }
-                .to_account_metas(None),
-            }],
-            Some(&ctx.campaign_authority.pubkey()),
-            &[&ctx.campaign_authority],
-            ptc.last_blockhash,
-        ))
-        .await
+    let instruction = ix::withdraw_donations(
+        ctx.campaign_authority.pubkey(),
+        campaign_id,
+        ctx.campaign_beneficiary.pubkey(),
+    );
+    process(ptc, instruction, &[&ctx.campaign_authority]).await
 }
 
 pub async fn liquidate_campaign(
@@ -419,30 +494,12 @@ pub async fn liquidate_campaign(
     let clock: Clock = ptc.banks_client.get_sysvar().await.unwrap();
     ptc.warp_to_slot(clock.slot + 1).unwrap();
 
-    ptc.banks_client
-        .process_transaction(Transaction::new_signed_with_payer(
-            &[Instruction {
-                program_id: crowdfunding::ID,
-                data: crowdfunding::instruction::LiquidateCampaign {}.data(),
-                accounts: crowdfunding::accounts::LiquidateCampaign {
-                    platform: ctx.platform,
-                    fee_vault: ctx.fee_vault,
-                    sol_vault: ctx.sol_vault,
-                    chrt_mint: ctx.chrt_mint,
-                    campaign: find_campaign(campaign_id),
-                    campaign_authority: ctx.campaign_authority.pubkey(),
-                    fee_exemption_vault: find_fee_exemption_vault(campaign_id),
-                    liquidation_vault: find_liquidation_vault(campaign_id),
-                    token_program: anchor_spl::token::ID,
-                    system_program: system_program::ID,
-                }
-                .to_account_metas(None),
-            }],
-            Some(&ptc.payer.pubkey()),
-            &[&ptc.payer],
-            ptc.last_blockhash,
-        ))
-        .await
+    let instruction = ix::liquidate_campaign(
+        campaign_id,
+        ctx.campaign_beneficiary.pubkey(),
+        ctx.campaign_authority.pubkey(),
+    );
+    process_paid_by_payer(ptc, instruction, &[]).await
 }
 
 pub async fn stop_campaign(
@@ -450,50 +507,321 @@ pub async fn stop_campaign(
     ctx: &Ctx,
     campaign_id: u16,
 ) -> Result<(), BanksClientError> {
-    ptc.banks_client
-        .process_transaction(Transaction::new_signed_with_payer(
-            &[Instruction {
-                program_id: crowdfunding::ID,
-                data: crowdfunding::instruction::StopCampaign {}.data(),
-                accounts: crowdfunding::accounts::StopCampaign {
-                    platform: ctx.platform,
-                    sol_vault: ctx.sol_vault,
-                    chrt_mint: ctx.chrt_mint,
-                    campaign: find_campaign(campaign_id),
-                    campaign_authority: ctx.campaign_authority.pubkey(),
-                    fee_exemption_vault: find_fee_exemption_vault(campaign_id),
-                    liquidation_vault: find_liquidation_vault(campaign_id),
-                    token_program: anchor_spl::token::ID,
-                    system_program: system_program::ID,
-                }
-                .to_account_metas(None),
-            }],
-            Some(&ctx.campaign_authority.pubkey()),
-            &[&ctx.campaign_authority],
-            ptc.last_blockhash,
-        ))
-        .await
+    let instruction = ix::stop_campaign(
+        ctx.campaign_authority.pubkey(),
+        campaign_id,
+        ctx.campaign_beneficiary.pubkey(),
+        ctx.campaign_authority.pubkey(),
+    );
+    process(ptc, instruction, &[&ctx.campaign_authority]).await
 }
 
 pub async fn withdraw_fees(
     ptc: &mut ProgramTestContext,
     ctx: &Ctx,
 ) -> Result<(), BanksClientError> {
-    ptc.banks_client
-        .process_transaction(Transaction::new_signed_with_payer(
-            &[Instruction {
-                program_id: crowdfunding::ID,
-                data: crowdfunding::instruction::WithdrawFees {}.data(),
-                accounts: crowdfunding::accounts::WithdrawFees {
-                    platform: ctx.platform,
-                    platform_authority: ctx.platform_authority.pubkey(),
-                    fee_vault: ctx.fee_vault,
-                }
-                .to_account_metas(None),
-            }],
-            Some(&ctx.platform_authority.pubkey()),
-            &[&ctx.platform_authority],
-            ptc.last_blockhash,
-        ))
-        .await
+    let instruction = ix::withdraw_fees(ctx.platform_authority.pubkey());
+    process(ptc, instruction, &[&ctx.platform_authority]).await
+}
+
+#[allow(clippy::too_many_arguments)]
+pub async fn update_platform_config(
+    ptc: &mut ProgramTestContext,
+    ctx: &Ctx,
+    reward_cooldown: Option<u32>,
+    reward_procedure_timeout: Option<u32>,
+    reward_amount: Option<u64>,
+    reward_distribution: Option<RewardDistribution>,
+    reward_tiers: Option<Vec<RewardTier>>,
+    fee_basis_points: Option<u16>,
+    fee_exemption_limit: Option<u64>,
+    liquidation_limit: Option<u64>,
+    record_donor_bounty: Option<u64>,
+    drop_rewards_bounty: Option<u64>,
+    referral_rate: Option<u64>,
+    referral_donation_cap: Option<u64>,
+    referral_season_cap: Option<u64>,
+    unstake_cooldown: Option<u32>,
+) -> Result<(), BanksClientError> {
+    let instruction = ix::update_platform_config(
+        ctx.platform_authority.pubkey(),
+        reward_cooldown,
+        reward_procedure_timeout,
+        reward_amount,
+        reward_distribution,
+        reward_tiers,
+        fee_basis_points,
+        fee_exemption_limit,
+        liquidation_limit,
+        record_donor_bounty,
+        drop_rewards_bounty,
+        referral_rate,
+        referral_donation_cap,
+        referral_season_cap,
+        unstake_cooldown,
+    );
+    process(ptc, instruction, &[&ctx.platform_authority]).await
+}
+
+pub async fn stake(
+    ptc: &mut ProgramTestContext,
+    staker: &Keypair,
+    campaign_id: u16,
+    amount: u64,
+) -> Result<(), BanksClientError> {
+    let instruction = ix::stake(staker.pubkey(), campaign_id, amount);
+    process(ptc, instruction, &[staker]).await
+}
+
+pub async fn request_unstake(
+    ptc: &mut ProgramTestContext,
+    staker: &Keypair,
+    campaign_id: u16,
+    amount: u64,
+) -> Result<(), BanksClientError> {
+    let instruction = ix::request_unstake(staker.pubkey(), campaign_id, amount);
+    process(ptc, instruction, &[staker]).await
+}
+
+pub async fn unstake(
+    ptc: &mut ProgramTestContext,
+    staker: &Keypair,
+    campaign_id: u16,
+) -> Result<(), BanksClientError> {
+    let instruction = ix::unstake(staker.pubkey(), campaign_id);
+    process(ptc, instruction, &[staker]).await
+}
+
+pub async fn support_campaign(
+    ptc: &mut ProgramTestContext,
+    supporter: &Keypair,
+    campaign_id: u16,
+    amount: u64,
+) -> Result<(), BanksClientError> {
+    let instruction = ix::support_campaign(supporter.pubkey(), campaign_id, amount);
+    process(ptc, instruction, &[supporter]).await
+}
+
+pub async fn flag_campaign(
+    ptc: &mut ProgramTestContext,
+    flagger: &Keypair,
+    campaign_id: u16,
+    amount: u64,
+) -> Result<(), BanksClientError> {
+    let instruction = ix::flag_campaign(flagger.pubkey(), campaign_id, amount);
+    process(ptc, instruction, &[flagger]).await
+}
+
+pub async fn withdraw_chrt_deposit(
+    ptc: &mut ProgramTestContext,
+    ctx: &Ctx,
+    depositor: &Keypair,
+    campaign_id: u16,
+    vault: ChrtVault,
+) -> Result<(), BanksClientError> {
+    let instruction = ix::withdraw_chrt_deposit(
+        depositor.pubkey(),
+        campaign_id,
+        vault,
+        ctx.campaign_authority.pubkey(),
+    );
+    process(ptc, instruction, &[depositor]).await
+}
+
+pub async fn propose_platform_authority(
+    ptc: &mut ProgramTestContext,
+    ctx: &Ctx,
+    new_authority: Pubkey,
+) -> Result<(), BanksClientError> {
+    let instruction =
+        ix::propose_platform_authority(ctx.platform_authority.pubkey(), new_authority);
+    process(ptc, instruction, &[&ctx.platform_authority]).await
+}
+
+pub async fn accept_platform_authority(
+    ptc: &mut ProgramTestContext,
+    new_authority: &Keypair,
+) -> Result<(), BanksClientError> {
+    let instruction = ix::accept_platform_authority(new_authority.pubkey());
+    process_paid_by_payer(ptc, instruction, &[new_authority]).await
+}
+
+pub async fn cancel_platform_authority(
+    ptc: &mut ProgramTestContext,
+    ctx: &Ctx,
+) -> Result<(), BanksClientError> {
+    let instruction = ix::cancel_platform_authority(ctx.platform_authority.pubkey());
+    process(ptc, instruction, &[&ctx.platform_authority]).await
+}
+
+pub async fn propose_campaign_authority(
+    ptc: &mut ProgramTestContext,
+    campaign_authority: &Keypair,
+    campaign_id: u16,
+    new_authority: Pubkey,
+) -> Result<(), BanksClientError> {
+    let instruction =
+        ix::propose_campaign_authority(campaign_authority.pubkey(), campaign_id, new_authority);
+    process(ptc, instruction, &[campaign_authority]).await
+}
+
+pub async fn accept_campaign_authority(
+    ptc: &mut ProgramTestContext,
+    new_authority: &Keypair,
+    campaign_id: u16,
+) -> Result<(), BanksClientError> {
+    let instruction = ix::accept_campaign_authority(new_authority.pubkey(), campaign_id);
+    process_paid_by_payer(ptc, instruction, &[new_authority]).await
+}
+
+pub async fn cancel_campaign_authority(
+    ptc: &mut ProgramTestContext,
+    campaign_authority: &Keypair,
+    campaign_id: u16,
+) -> Result<(), BanksClientError> {
+    let instruction = ix::cancel_campaign_authority(campaign_authority.pubkey(), campaign_id);
+    process(ptc, instruction, &[campaign_authority]).await
+}
+
+pub async fn update_campaign_metadata(
+    ptc: &mut ProgramTestContext,
+    ctx: &Ctx,
+    campaign_id: u16,
+    title: Option<String>,
+    uri: Option<String>,
+    category: Option<CampaignCategory>,
+) -> Result<(), BanksClientError> {
+    let instruction = ix::update_campaign_metadata(
+        ctx.campaign_authority.pubkey(),
+        campaign_id,
+        title,
+        uri,
+        category,
+    );
+    process(ptc, instruction, &[&ctx.campaign_authority]).await
+}
+
+pub async fn refund_donations(
+    ptc: &mut ProgramTestContext,
+    donor_authority: &Keypair,
+    campaign_id: u16,
+) -> Result<(), BanksClientError> {
+    let instruction = ix::refund_donations(donor_authority.pubkey(), campaign_id);
+    process(ptc, instruction, &[donor_authority]).await
+}
+
+pub async fn expire_campaign(
+    ptc: &mut ProgramTestContext,
+    ctx: &Ctx,
+    campaign_id: u16,
+) -> Result<(), BanksClientError> {
+    let instruction = ix::expire_campaign(
+        campaign_id,
+        ctx.campaign_beneficiary.pubkey(),
+        ctx.campaign_authority.pubkey(),
+    );
+    process_paid_by_payer(ptc, instruction, &[]).await
+}
+
+pub async fn vote_milestone(
+    ptc: &mut ProgramTestContext,
+    donor_authority: &Keypair,
+    campaign_id: u16,
+) -> Result<(), BanksClientError> {
+    let Campaign {
+        milestones_unlocked,
+        ..
+    } = fetch(ptc, find_campaign(campaign_id)).await?;
+
+    let instruction =
+        ix::vote_milestone(donor_authority.pubkey(), campaign_id, milestones_unlocked);
+    process(ptc, instruction, &[donor_authority]).await
+}
+
+pub async fn open_token_vault(
+    ptc: &mut ProgramTestContext,
+    ctx: &Ctx,
+    campaign_id: u16,
+    mint: Pubkey,
+) -> Result<(), BanksClientError> {
+    let instruction = ix::open_token_vault(ctx.campaign_authority.pubkey(), campaign_id, mint);
+    process(ptc, instruction, &[&ctx.campaign_authority]).await
+}
+
+pub async fn donate_token(
+    ptc: &mut ProgramTestContext,
+    donor_authority: &Keypair,
+    campaign_id: u16,
+    mint: Pubkey,
+    amount: u64,
+) -> Result<(), BanksClientError> {
+    let instruction = ix::donate_token(donor_authority.pubkey(), campaign_id, mint, amount);
+    process(ptc, instruction, &[donor_authority]).await
+}
+
+pub async fn withdraw_token_donations(
+    ptc: &mut ProgramTestContext,
+    ctx: &Ctx,
+    campaign_id: u16,
+    mint: Pubkey,
+) -> Result<(), BanksClientError> {
+    let instruction = ix::withdraw_token_donations(
+        ctx.campaign_authority.pubkey(),
+        campaign_id,
+        mint,
+        ctx.campaign_beneficiary.pubkey(),
+    );
+    process(ptc, instruction, &[&ctx.campaign_authority]).await
+}
+
+pub async fn close_token_vault(
+    ptc: &mut ProgramTestContext,
+    ctx: &Ctx,
+    authority: &Keypair,
+    campaign_id: u16,
+    mint: Pubkey,
+) -> Result<(), BanksClientError> {
+    let instruction = ix::close_token_vault(
+        authority.pubkey(),
+        campaign_id,
+        mint,
+        ctx.campaign_beneficiary.pubkey(),
+    );
+    process(ptc, instruction, &[authority]).await
+}
+
+pub async fn refund_token_donations(
+    ptc: &mut ProgramTestContext,
+    ctx: &Ctx,
+    donor_authority: &Keypair,
+    campaign_id: u16,
+    mint: Pubkey,
+) -> Result<(), BanksClientError> {
+    let instruction = ix::refund_token_donations(
+        donor_authority.pubkey(),
+        campaign_id,
+        mint,
+        ctx.campaign_beneficiary.pubkey(),
+    );
+    process(ptc, instruction, &[donor_authority]).await
+}
+
+pub async fn liquidate_token_vault(
+    ptc: &mut ProgramTestContext,
+    ctx: &Ctx,
+    campaign_id: u16,
+    mint: Pubkey,
+) -> Result<(), BanksClientError> {
+    let instruction =
+        ix::liquidate_token_vault(campaign_id, mint, ctx.campaign_beneficiary.pubkey());
+    process_paid_by_payer(ptc, instruction, &[]).await
+}
+
+pub async fn withdraw_token_fees(
+    ptc: &mut ProgramTestContext,
+    ctx: &Ctx,
+    mint: Pubkey,
+) -> Result<(), BanksClientError> {
+    let instruction = ix::withdraw_token_fees(ctx.platform_authority.pubkey(), mint);
+    process(ptc, instruction, &[&ctx.platform_authority]).await
 }
@@ -1,4 +1,5 @@
 use anchor_lang::prelude::*;
+use crowdfunding_client::pda::*;
 use solana_program::system_instruction;
 use solana_program_test::*;
 use solana_sdk::{signature::Keypair, signer::Signer, transaction::Transaction};
@@ -9,8 +10,10 @@ pub const DONORS_LEN: usize = 15;
 pub struct Ctx {
     pub platform_authority: Keypair,
     pub campaign_authority: Keypair,
+    pub campaign_beneficiary: Keypair,
     pub donors: [Keypair; DONORS_LEN],
     pub platform: Pubkey,
+    pub seasonal_top: Pubkey,
     pub fee_vault: Pubkey,
     pub sol_vault: Pubkey,
     pub chrt_mint: Pubkey,
@@ -18,13 +21,10 @@ pub struct Ctx {
 
 impl Ctx {
     pub fn new() -> Self {
-        let platform = Pubkey::find_program_address(&[b"platform"], &crowdfunding::ID).0;
-        let fee_vault = Pubkey::find_program_address(&[b"fee_vault"], &crowdfunding::ID).0;
-        let sol_vault = Pubkey::find_program_address(&[b"sol_vault"], &crowdfunding::ID).0;
-        let chrt_mint = Pubkey::find_program_address(&[b"chrt_mint"], &crowdfunding::ID).0;
         Ctx {
             platform_authority: Keypair::new(),
             campaign_authority: Keypair::new(),
+            campaign_beneficiary: Keypair::new(),
             donors: [
                 Keypair::new(),
                 Keypair::new(),
@@ -42,18 +42,20 @@ impl Ctx {
                 Keypair::new(),
                 Keypair::new(),
             ],
-            platform,
-            fee_vault,
-            sol_vault,
-            chrt_mint,
+            platform: find_platform(),
+            seasonal_top: find_seasonal_top(),
+            fee_vault: find_fee_vault(),
+            sol_vault: find_sol_vault(),
+            chrt_mint: find_chrt_mint(),
         }
     }
 
     pub async fn airdrop(&self, ptc: &mut ProgramTestContext) {
-        let mut instructions = heapless::Vec::<_, { DONORS_LEN + 2 }>::new();
+        let mut instructions = heapless::Vec::<_, { DONORS_LEN + 3 }>::new();
         for to_pubkey in self.donors.iter().map(|d| d.pubkey()).chain([
             self.platform_authority.pubkey(),
             self.campaign_authority.pubkey(),
+            self.campaign_beneficiary.pubkey(),
         ]) {
             instructions
                 .push(system_instruction::transfer(
@@ -2,7 +2,8 @@ use crate::{api::*, ctx::*, utils::*};
 use anchor_lang::prelude::ErrorCode;
 use anchor_spl::token::TokenAccount;
 use core::assert_matches::assert_matches;
-use crowdfunding::{error::*, state::*};
+use crowdfunding::{error::*, events::*, state::*};
+use crowdfunding_client::instructions as ix;
 use solana_program::instruction::InstructionError;
 use solana_program_test::*;
 use solana_sdk::{signer::Signer, transaction::TransactionError};
@@ -33,7 +34,7 @@ async fn initializes(ptc: &mut ProgramTestContext, ctx: &Ctx) {
 
 async fn registers_donors(ptc: &mut ProgramTestContext, ctx: &Ctx) {
     for donor in &ctx.donors {
-        register_donor(ptc, ctx, donor).await.unwrap();
+        register_donor(ptc, donor).await.unwrap();
     }
 }
 
@@ -47,12 +48,13 @@ async fn starts_campaign(ptc: &mut ProgramTestContext, ctx: &Ctx) {
             id: 0,
             donations_sum: 0,
             withdrawn_sum: 0,
+            goal: 0,
         }]
     );
 }
 
 async fn donates(ptc: &mut ProgramTestContext, ctx: &Ctx) {
-    donate(ptc, ctx, &ctx.donors[0], 0, 100).await.unwrap();
+    donate(ptc, &ctx.donors[0], 0, 100).await.unwrap();
     let (platform_top, len) = fetch_platform_top(ptc, ctx).await.unwrap();
     assert_eq!(
         platform_top[..len],
@@ -62,7 +64,7 @@ async fn donates(ptc: &mut ProgramTestContext, ctx: &Ctx) {
         }]
     );
 
-    donate(ptc, ctx, &ctx.donors[0], 0, 1000).await.unwrap();
+    donate(ptc, &ctx.donors[0], 0, 1000).await.unwrap();
     let (platform_top, len) = fetch_platform_top(ptc, ctx).await.unwrap();
     assert_eq!(
         platform_top[..len],
@@ -72,7 +74,7 @@ async fn donates(ptc: &mut ProgramTestContext, ctx: &Ctx) {
         },]
     );
 
-    donate(ptc, ctx, &ctx.donors[2], 0, 10000).await.unwrap();
+    donate(ptc, &ctx.donors[2], 0, 10000).await.unwrap();
     let (platform_top, len) = fetch_platform_top(ptc, ctx).await.unwrap();
     assert_eq!(
         &platform_top[..len],
@@ -88,7 +90,7 @@ async fn donates(ptc: &mut ProgramTestContext, ctx: &Ctx) {
         ]
     );
 
-    donate(ptc, ctx, &ctx.donors[3], 0, 1).await.unwrap();
+    donate(ptc, &ctx.donors[3], 0, 1).await.unwrap();
     let (platform_top, len) = fetch_platform_top(ptc, ctx).await.unwrap();
     assert_eq!(
         &platform_top[..len],
@@ -117,6 +119,7 @@ async fn withdraws_donations(ptc: &mut ProgramTestContext, ctx: &Ctx) {
             id: 0,
             donations_sum: 97 + 970 + 9700 + 1,
             withdrawn_sum: 0,
+            goal: 0,
         }]
     );
 
@@ -130,6 +133,7 @@ async fn withdraws_donations(ptc: &mut ProgramTestContext, ctx: &Ctx) {
             id: 0,
             donations_sum: 97 + 970 + 9700 + 1,
             withdrawn_sum: 97 + 970 + 9700 + 1,
+            goal: 0,
         }]
     );
 }
@@ -142,15 +146,6 @@ async fn withdraws_fees(ptc: &mut ProgramTestContext, ctx: &Ctx) {
 
 async fn incentivizes(ptc: &mut ProgramTestContext, ctx: &Ctx) {
     record_donors(ptc, ctx).await.unwrap();
-
-    const CODE: u32 = 6000 + CrowdfundingError::RewardProcedureInProcess as u32;
-    assert_matches!(
-        donate(ptc, ctx, &ctx.donors[5], 0, 1).await,
-        Err(BanksClientError::TransactionError(
-            TransactionError::InstructionError(0, InstructionError::Custom(CODE))
-        ))
-    );
-
     drop_rewards(ptc, ctx).await.unwrap();
 
     let donor_chrt: TokenAccount = fetch(
@@ -195,17 +190,11 @@ async fn incentivizes(ptc: &mut ProgramTestContext, ctx: &Ctx) {
 }
 
 async fn exempts_from_fees(ptc: &mut ProgramTestContext, ctx: &Ctx) {
-    transfer_tokens(
-        ptc,
-        &get_associated_token_address(&ctx.donors[0].pubkey(), &ctx.chrt_mint),
-        &find_fee_exemption_vault(0),
-        1000,
-        &ctx.donors[0],
-    )
-    .await
-    .unwrap();
+    support_campaign(ptc, &ctx.donors[0], 0, 1000)
+        .await
+        .unwrap();
 
-    donate(ptc, ctx, &ctx.donors[3], 0, 100_000).await.unwrap();
+    donate(ptc, &ctx.donors[3], 0, 100_000).await.unwrap();
     let (active_campaigns, len) = fetch_active_campaigns(ptc, ctx).await.unwrap();
     assert_eq!(
         active_campaigns[..len],
@@ -213,6 +202,7 @@ async fn exempts_from_fees(ptc: &mut ProgramTestContext, ctx: &Ctx) {
             id: 0,
             donations_sum: 97 + 970 + 9700 + 1 + 100_000,
             withdrawn_sum: 97 + 970 + 9700 + 1,
+            goal: 0,
         }]
     );
 }
@@ -229,22 +219,25 @@ async fn starts_more_campaigns_and_donates(ptc: &mut ProgramTestContext, ctx: &C
                 id: 0,
                 donations_sum: 97 + 970 + 9700 + 1 + 100_000,
                 withdrawn_sum: 97 + 970 + 9700 + 1,
+                goal: 0,
             },
             CampaignRecord {
                 id: 1,
                 donations_sum: 0,
                 withdrawn_sum: 0,
+                goal: 0,
             },
             CampaignRecord {
                 id: 2,
                 donations_sum: 0,
                 withdrawn_sum: 0,
+                goal: 0,
             }
         ]
     );
 
-    donate(ptc, ctx, &ctx.donors[3], 1, 1).await.unwrap();
-    donate(ptc, ctx, &ctx.donors[3], 2, 9).await.unwrap();
+    donate(ptc, &ctx.donors[3], 1, 1).await.unwrap();
+    donate(ptc, &ctx.donors[3], 2, 9).await.unwrap();
 
     let (active_campaigns, len) = fetch_active_campaigns(ptc, ctx).await.unwrap();
     assert_eq!(
@@ -254,16 +247,19 @@ async fn starts_more_campaigns_and_donates(ptc: &mut ProgramTestContext, ctx: &C
                 id: 0,
                 donations_sum: 97 + 970 + 9700 + 1 + 100_000,
                 withdrawn_sum: 97 + 970 + 9700 + 1,
+                goal: 0,
             },
             CampaignRecord {
                 id: 1,
                 donations_sum: 1,
                 withdrawn_sum: 0,
+                goal: 0,
             },
             CampaignRecord {
                 id: 2,
                 donations_sum: 9,
                 withdrawn_sum: 0,
+                goal: 0,
             }
         ]
     );
@@ -278,21 +274,28 @@ async fn liquidates_campaign(ptc: &mut ProgramTestContext, ctx: &Ctx) {
         ))
     );
 
-    transfer_tokens(
+    flag_campaign(ptc, &ctx.donors[0], 0, 2000).await.unwrap();
+
+    let logs = process_with_logs(
         ptc,
-        &get_associated_token_address(&ctx.donors[0].pubkey(), &ctx.chrt_mint),
-        &find_liquidation_vault(0),
-        2000,
-        &ctx.donors[0],
+        ix::liquidate_campaign(
+            0,
+            ctx.campaign_beneficiary.pubkey(),
+            ctx.campaign_authority.pubkey(),
+        ),
+        &[&ctx.donors[0]],
     )
     .await
     .unwrap();
-
-    liquidate_campaign(ptc, ctx, 0).await.unwrap();
+    let liquidations: Vec<CampaignLiquidated> = events(&logs);
+    assert_eq!(liquidations.len(), 1);
+    assert_eq!(liquidations[0].campaign_id, 0);
+    assert_eq!(liquidations[0].amount, 100_000);
+    assert_eq!(liquidations[0].distributed_amount, 100_000);
 
     const CODE2: u32 = ErrorCode::AccountOwnedByWrongProgram as u32;
     assert_matches!(
-        donate(ptc, ctx, &ctx.donors[5], 0, 2).await,
+        donate(ptc, &ctx.donors[5], 0, 2).await,
         Err(BanksClientError::TransactionError(
             TransactionError::InstructionError(0, InstructionError::Custom(CODE2))
         ))
@@ -306,11 +309,13 @@ async fn liquidates_campaign(ptc: &mut ProgramTestContext, ctx: &Ctx) {
                 id: 1,
                 donations_sum: 1 + 10000,
                 withdrawn_sum: 0,
+                goal: 0,
             },
             CampaignRecord {
                 id: 2,
                 donations_sum: 9 + 90000,
                 withdrawn_sum: 0,
+                goal: 0,
             }
         ]
     );
@@ -333,11 +338,13 @@ async fn withdraws_donations_that_came_from_liquidation(ptc: &mut ProgramTestCon
                 id: 1,
                 donations_sum: 1 + 10000,
                 withdrawn_sum: 1 + 10000,
+                goal: 0,
             },
             CampaignRecord {
                 id: 2,
                 donations_sum: 9 + 90000,
                 withdrawn_sum: 0,
+                goal: 0,
             }
         ]
     );
@@ -351,6 +358,7 @@ async fn withdraws_donations_that_came_from_liquidation(ptc: &mut ProgramTestCon
             id: 1,
             donations_sum: 1 + 10000,
             withdrawn_sum: 1 + 10000,
+            goal: 0,
         }]
     );
 
@@ -365,19 +373,19 @@ async fn withdraws_donations_that_came_from_liquidation(ptc: &mut ProgramTestCon
 async fn sorts_top_with_more_than_10_donors(ptc: &mut ProgramTestContext, ctx: &Ctx) {
     start_campaign(ptc, ctx).await.unwrap();
 
-    donate(ptc, ctx, &ctx.donors[14], 3, 14).await.unwrap();
-    donate(ptc, ctx, &ctx.donors[2], 3, 2).await.unwrap();
-    donate(ptc, ctx, &ctx.donors[5], 3, 5).await.unwrap();
-    donate(ptc, ctx, &ctx.donors[1], 3, 1).await.unwrap();
-    donate(ptc, ctx, &ctx.donors[11], 3, 11).await.unwrap();
-    donate(ptc, ctx, &ctx.donors[12], 3, 12).await.unwrap();
-    donate(ptc, ctx, &ctx.donors[10], 3, 10).await.unwrap();
-    donate(ptc, ctx, &ctx.donors[9], 3, 9).await.unwrap();
-    donate(ptc, ctx, &ctx.donors[13], 3, 13).await.unwrap();
-    donate(ptc, ctx, &ctx.donors[7], 3, 7).await.unwrap();
-    donate(ptc, ctx, &ctx.donors[8], 3, 8).await.unwrap();
-    donate(ptc, ctx, &ctx.donors[4], 3, 4).await.unwrap();
-    donate(ptc, ctx, &ctx.donors[3], 3, 3).await.unwrap();
+    donate(ptc, &ctx.donors[14], 3, 14).await.unwrap();
+    donate(ptc, &ctx.donors[2], 3, 2).await.unwrap();
+    donate(ptc, &ctx.donors[5], 3, 5).await.unwrap();
+    donate(ptc, &ctx.donors[1], 3, 1).await.unwrap();
+    donate(ptc, &ctx.donors[11], 3, 11).await.unwrap();
+    donate(ptc, &ctx.donors[12], 3, 12).await.unwrap();
+    donate(ptc, &ctx.donors[10], 3, 10).await.unwrap();
+    donate(ptc, &ctx.donors[9], 3, 9).await.unwrap();
+    donate(ptc, &ctx.donors[13], 3, 13).await.unwrap();
+    donate(ptc, &ctx.donors[7], 3, 7).await.unwrap();
+    donate(ptc, &ctx.donors[8], 3, 8).await.unwrap();
+    donate(ptc, &ctx.donors[4], 3, 4).await.unwrap();
+    donate(ptc, &ctx.donors[3], 3, 3).await.unwrap();
 
     let (campaign_top, len) = fetch_campaign_top(ptc, 3).await.unwrap();
     assert_eq!(
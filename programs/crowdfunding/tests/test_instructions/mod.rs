@@ -1,11 +1,12 @@
 use crate::{api::*, ctx::*, utils::*};
-use anchor_lang::prelude::{Clock, ErrorCode};
+use anchor_lang::prelude::{Clock, ErrorCode, Pubkey};
 use anchor_spl::token::TokenAccount;
 use core::assert_matches::assert_matches;
-use crowdfunding::{error::*, state::*};
+use crowdfunding::{config::*, error::*, events::*, merkle::*, state::*};
+use crowdfunding_client::instructions as ix;
 use solana_program::instruction::InstructionError;
 use solana_program_test::*;
-use solana_sdk::{signer::Signer, transaction::TransactionError};
+use solana_sdk::{signature::Keypair, signer::Signer, transaction::TransactionError};
 use spl_associated_token_account::get_associated_token_address;
 
 pub async fn test_instructions() {
@@ -14,14 +15,40 @@ pub async fn test_instructions() {
     ctx.create_atas(&mut ptc).await;
     test_register_donor(&mut ptc, &ctx).await;
     test_start_campaign(&mut ptc, &ctx).await;
+    test_update_campaign_metadata(&mut ptc, &ctx).await;
     test_donate(&mut ptc, &ctx).await;
     test_donate_with_referer(&mut ptc, &ctx).await;
     test_record_donors(&mut ptc, &ctx).await;
     test_drop_rewards(&mut ptc, &ctx).await;
     test_withdraw_donations(&mut ptc, &ctx).await;
     test_liquidate_campaign(&mut ptc, &ctx).await;
+    test_transfer_campaign_authority(&mut ptc, &ctx).await;
+    test_refund_donations(&mut ptc, &ctx).await;
+    test_expire_campaign(&mut ptc, &ctx).await;
+    test_vote_milestone(&mut ptc, &ctx).await;
+    test_token_donations(&mut ptc, &ctx).await;
+    test_donate_during_reward_procedure(&mut ptc, &ctx).await;
+    test_reward_tiers(&mut ptc, &ctx).await;
+    test_claim_reward(&mut ptc, &ctx).await;
+    test_resize_seasonal_top(&mut ptc, &ctx).await;
+    test_referral_caps(&mut ptc, &ctx).await;
+    test_referral_code(&mut ptc, &ctx).await;
+    test_create_referer_chrt(&mut ptc, &ctx).await;
+    test_credit_donations(&mut ptc, &ctx).await;
+    test_stop_with_locked_milestones(&mut ptc, &ctx).await;
+    test_token_vault_with_locked_milestones(&mut ptc, &ctx).await;
+    test_token_vaults_limit(&mut ptc, &ctx).await;
+    test_stake(&mut ptc, &ctx).await;
+    test_withdraw_chrt_deposit(&mut ptc, &ctx).await;
+    test_zero_chrt_deposit(&mut ptc, &ctx).await;
+    test_withdraw_chrt_deposit_after_expiry(&mut ptc, &ctx).await;
+    test_chrt_sent_to_vaults_directly(&mut ptc, &ctx).await;
+    test_liquidation_shares(&mut ptc, &ctx).await;
+    test_abort_reward_procedure(&mut ptc, &ctx).await;
     test_stop_campaign(&mut ptc, &ctx).await;
     test_withdraw_fees(&mut ptc, &ctx).await;
+    test_update_platform_config(&mut ptc, &ctx).await;
+    test_transfer_platform_authority(&mut ptc, &ctx).await;
 }
 
 async fn test_initialize(ptc: &mut ProgramTestContext, ctx: &Ctx) {
@@ -46,6 +73,9 @@ async fn test_initialize(ptc: &mut ProgramTestContext, ctx: &Ctx) {
     let Platform {
         authority,
         reward_amount,
+        reward_distribution,
+        reward_tiers_count,
+        reward_tiers,
         reward_cooldown,
         fee_basis_points,
         fee_exemption_limit,
@@ -55,6 +85,14 @@ async fn test_initialize(ptc: &mut ProgramTestContext, ctx: &Ctx) {
     assert_eq!(authority, ctx.platform_authority.pubkey());
     assert_eq!(reward_cooldown, reward_cooldown_);
     assert_eq!(reward_amount, reward_amount_);
+    assert_eq!(reward_distribution, RewardDistribution::Tiers as u8);
+    assert_eq!(
+        reward_tiers[..reward_tiers_count as usize],
+        [RewardTier {
+            last_rank: SEASONAL_TOP_CAPACITY,
+            amount: reward_amount_,
+        }]
+    );
     assert_eq!(fee_basis_points, fee_basis_points_);
     assert_eq!(fee_exemption_limit, fee_exemption_limit_);
     assert_eq!(liquidation_limit, liquidation_limit_);
@@ -62,7 +100,7 @@ async fn test_initialize(ptc: &mut ProgramTestContext, ctx: &Ctx) {
 
 async fn test_register_donor(ptc: &mut ProgramTestContext, ctx: &Ctx) {
     for donor in &ctx.donors {
-        register_donor(ptc, ctx, donor).await.unwrap();
+        register_donor(ptc, donor).await.unwrap();
     }
 
     let Donor { authority, .. } = fetch(ptc, find_donor(ctx.donors[0].pubkey()))
@@ -74,9 +112,22 @@ async fn test_register_donor(ptc: &mut ProgramTestContext, ctx: &Ctx) {
 async fn test_start_campaign(ptc: &mut ProgramTestContext, ctx: &Ctx) {
     start_campaign(ptc, ctx).await.unwrap();
 
-    let Campaign { authority, id, .. } = fetch(ptc, find_campaign(0)).await.unwrap();
+    let Campaign {
+        authority,
+        beneficiary,
+        id,
+        title,
+        uri,
+        category,
+        ..
+    } = fetch(ptc, find_campaign(0)).await.unwrap();
     assert_eq!(authority, ctx.campaign_authority.pubkey());
+    assert_eq!(beneficiary, ctx.campaign_beneficiary.pubkey());
     assert_eq!(id, 0);
+    assert_eq!(&title[..CAMPAIGN_TITLE.len()], CAMPAIGN_TITLE.as_bytes());
+    assert_eq!(title[CAMPAIGN_TITLE.len()], 0);
+    assert_eq!(&uri[..CAMPAIGN_URI.len()], CAMPAIGN_URI.as_bytes());
+    assert_eq!(category, CampaignCategory::Charity as u8);
 
     let (active_campaigns, len) = fetch_active_campaigns(ptc, ctx).await.unwrap();
     assert_eq!(
@@ -85,6 +136,7 @@ async fn test_start_campaign(ptc: &mut ProgramTestContext, ctx: &Ctx) {
             id: 0,
             donations_sum: 0,
             withdrawn_sum: 0,
+            goal: 0,
         }]
     );
 
@@ -98,18 +150,64 @@ async fn test_start_campaign(ptc: &mut ProgramTestContext, ctx: &Ctx) {
                 id: 0,
                 donations_sum: 0,
                 withdrawn_sum: 0,
+                goal: 0,
             },
             CampaignRecord {
                 id: 1,
                 donations_sum: 0,
                 withdrawn_sum: 0,
+                goal: 0,
             }
         ]
     );
 }
 
+async fn test_update_campaign_metadata(ptc: &mut ProgramTestContext, ctx: &Ctx) {
+    update_campaign_metadata(
+        ptc,
+        ctx,
+        1,
+        Some("Renamed".to_string()),
+        None,
+        Some(CampaignCategory::Education),
+    )
+    .await
+    .unwrap();
+
+    let Campaign {
+        title,
+        uri,
+        category,
+        ..
+    } = fetch(ptc, find_campaign(1)).await.unwrap();
+    assert_eq!(&title[..8], b"Renamed\0");
+    assert_eq!(&uri[..CAMPAIGN_URI.len()], CAMPAIGN_URI.as_bytes());
+    assert_eq!(category, CampaignCategory::Education as u8);
+
+    const CODE: u32 = 6000 + CrowdfundingError::TitleTooLong as u32;
+    assert_matches!(
+        update_campaign_metadata(ptc, ctx, 1, Some("a".repeat(65)), None, None).await,
+        Err(BanksClientError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::Custom(CODE))
+        ))
+    );
+}
+
 async fn test_donate(ptc: &mut ProgramTestContext, ctx: &Ctx) {
-    donate(ptc, ctx, &ctx.donors[0], 0, 100).await.unwrap();
+    let logs = process_with_logs(
+        ptc,
+        ix::donate(ctx.donors[0].pubkey(), 0, 100),
+        &[&ctx.donors[0]],
+    )
+    .await
+    .unwrap();
+    let donations: Vec<DonationMade> = events(&logs);
+    assert_eq!(donations.len(), 1);
+    assert_eq!(donations[0].donor, ctx.donors[0].pubkey());
+    assert_eq!(donations[0].campaign_id, 0);
+    assert_eq!(donations[0].amount, 100);
+    assert_eq!(donations[0].fee, 3);
+    assert!(!donations[0].fee_exempted);
 
     let Platform {
         sum_of_all_donations,
@@ -148,11 +246,13 @@ async fn test_donate(ptc: &mut ProgramTestContext, ctx: &Ctx) {
                 id: 0,
                 donations_sum: 97,
                 withdrawn_sum: 0,
+                goal: 0,
             },
             CampaignRecord {
                 id: 1,
                 donations_sum: 0,
                 withdrawn_sum: 0,
+                goal: 0,
             }
         ]
     );
@@ -177,9 +277,18 @@ async fn test_donate(ptc: &mut ProgramTestContext, ctx: &Ctx) {
 }
 
 async fn test_donate_with_referer(ptc: &mut ProgramTestContext, ctx: &Ctx) {
-    donate_with_referer(ptc, ctx, &ctx.donors[1], 0, 10000, ctx.donors[0].pubkey())
-        .await
-        .unwrap();
+    let logs = process_with_logs(
+        ptc,
+        ix::donate_with_referer(ctx.donors[1].pubkey(), 0, 10000, ctx.donors[0].pubkey()),
+        &[&ctx.donors[1]],
+    )
+    .await
+    .unwrap();
+    let rewards: Vec<ReferralRewardMinted> = events(&logs);
+    assert_eq!(rewards.len(), 1);
+    assert_eq!(rewards[0].referer, ctx.donors[0].pubkey());
+    assert_eq!(rewards[0].donor, ctx.donors[1].pubkey());
+    assert_eq!(rewards[0].amount, 1);
 
     let donor_chrt: TokenAccount = fetch(
         ptc,
@@ -197,10 +306,27 @@ async fn test_donate_with_referer(ptc: &mut ProgramTestContext, ctx: &Ctx) {
     assert_eq!(sum_of_all_donations, 97 + 9700);
     assert_eq!(sum_of_active_campaign_donations, 97 + 9700);
 
-    let Donor { donations_sum, .. } = fetch(ptc, find_donor(ctx.donors[1].pubkey()))
+    let Donor {
+        donations_sum,
+        referer,
+        ..
+    } = fetch(ptc, find_donor(ctx.donors[1].pubkey()))
         .await
         .unwrap();
     assert_eq!(donations_sum, 9700);
+    assert_eq!(referer, ctx.donors[0].pubkey());
+
+    let Donor {
+        referred_donors,
+        referred_volume,
+        referral_rewards_sum,
+        ..
+    } = fetch(ptc, find_donor(ctx.donors[0].pubkey()))
+        .await
+        .unwrap();
+    assert_eq!(referred_donors, 1);
+    assert_eq!(referred_volume, 10000);
+    assert_eq!(referral_rewards_sum, 1);
 
     let Donations { donations_sum, .. } = fetch(
         ptc,
@@ -226,11 +352,13 @@ async fn test_donate_with_referer(ptc: &mut ProgramTestContext, ctx: &Ctx) {
                 id: 0,
                 donations_sum: 97 + 9700,
                 withdrawn_sum: 0,
+                goal: 0,
             },
             CampaignRecord {
                 id: 1,
                 donations_sum: 0,
                 withdrawn_sum: 0,
+                goal: 0,
             }
         ]
     );
@@ -267,7 +395,29 @@ async fn test_donate_with_referer(ptc: &mut ProgramTestContext, ctx: &Ctx) {
 }
 
 async fn test_record_donors(ptc: &mut ProgramTestContext, ctx: &Ctx) {
+    update_platform_config(
+        ptc,
+        ctx,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        Some(10),
+        Some(100),
+        None,
+        None,
+        None,
+        None,
+    )
+    .await
+    .unwrap();
     record_donors(ptc, ctx).await.unwrap();
+    // only the donor whose fees cover the bounty earns it
+    assert_eq!(get_fee_vault_balance(ptc, ctx).await.unwrap(), 3 + 300 - 10);
 
     let Platform {
         reward_procedure_is_in_process,
@@ -285,20 +435,49 @@ async fn test_record_donors(ptc: &mut ProgramTestContext, ctx: &Ctx) {
     assert_eq!(
         seasonal_top[..len],
         [
-            DonorRecord {
+            SeasonalTopRecord {
                 donor: ctx.donors[1].pubkey(),
                 donations_sum: 9700,
+                unrewarded_sum: 9700,
             },
-            DonorRecord {
+            SeasonalTopRecord {
                 donor: ctx.donors[0].pubkey(),
                 donations_sum: 97,
+                unrewarded_sum: 97,
             }
         ]
     );
 }
 
 async fn test_drop_rewards(ptc: &mut ProgramTestContext, ctx: &Ctx) {
-    drop_rewards(ptc, ctx).await.unwrap();
+    let page = fetch_reward_page(ptc, ctx, usize::MAX).await.unwrap();
+    let logs = process_with_logs(
+        ptc,
+        ix::drop_rewards(ctx.platform_authority.pubkey(), 1, &page),
+        &[&ctx.platform_authority],
+    )
+    .await
+    .unwrap();
+    let rewards: Vec<RewardDropped> = events(&logs);
+    assert_eq!(
+        rewards
+            .iter()
+            .map(|r| (r.donor, r.amount))
+            .collect::<Vec<_>>(),
+        [
+            (ctx.donors[1].pubkey(), 1000),
+            (ctx.donors[0].pubkey(), 1000)
+        ]
+    );
+    let drops: Vec<RewardsDropped> = events(&logs);
+    assert_eq!(drops.len(), 1);
+    assert_eq!(drops[0].season, 1);
+    assert_eq!(drops[0].donors_rewarded, 2);
+    assert_eq!(drops[0].total_amount, 2000);
+    assert_eq!(
+        get_fee_vault_balance(ptc, ctx).await.unwrap(),
+        3 + 300 - 10 * 2 - 100
+    );
 
     let Platform {
         reward_procedure_is_in_process,
@@ -311,6 +490,35 @@ async fn test_drop_rewards(ptc: &mut ProgramTestContext, ctx: &Ctx) {
     let (seasonal_top, len) = fetch_seasonal_top(ptc, ctx).await.unwrap();
     assert_eq!(seasonal_top[..len], []);
 
+    let (season, season_top) = fetch_with_records::<Season, SeasonRecord>(ptc, find_season(1))
+        .await
+        .unwrap();
+    assert_eq!(season_top.len(), SEASONAL_TOP_CAPACITY as usize);
+    assert_eq!({ season.id }, 1);
+    assert!(season.end_ts >= season.start_ts);
+    assert_eq!({ season.donations_sum }, 9797);
+    assert_eq!({ season.donors_recorded }, ctx.donors.len() as u32);
+    assert_eq!({ season.rewards_sum }, 2000);
+    assert_eq!({ season.donors_rewarded }, 2);
+    assert!(!season.rewards_committed);
+    assert_eq!({ season.merkle_root }, [0; 32]);
+    assert_eq!(
+        season_top[..3],
+        [
+            SeasonRecord {
+                donor: ctx.donors[1].pubkey(),
+                donations_sum: 9700,
+                reward: 1000,
+            },
+            SeasonRecord {
+                donor: ctx.donors[0].pubkey(),
+                donations_sum: 97,
+                reward: 1000,
+            },
+            SeasonRecord::default(),
+        ]
+    );
+
     let donor_chrt: TokenAccount = fetch(
         ptc,
         get_associated_token_address(&ctx.donors[0].pubkey(), &ctx.chrt_mint),
@@ -348,9 +556,22 @@ async fn test_drop_rewards(ptc: &mut ProgramTestContext, ctx: &Ctx) {
 }
 
 async fn test_withdraw_donations(ptc: &mut ProgramTestContext, ctx: &Ctx) {
+    let beneficiary_balance = ptc
+        .banks_client
+        .get_balance(ctx.campaign_beneficiary.pubkey())
+        .await
+        .unwrap();
+
     withdraw_donations(ptc, ctx, 0).await.unwrap();
 
     assert_eq!(get_sol_vault_balance(ptc, ctx).await.unwrap(), 0);
+    assert_eq!(
+        ptc.banks_client
+            .get_balance(ctx.campaign_beneficiary.pubkey())
+            .await
+            .unwrap(),
+        beneficiary_balance + 97 + 9700
+    );
 
     let (active_campaigns, len) = fetch_active_campaigns(ptc, ctx).await.unwrap();
     assert_eq!(
@@ -360,11 +581,13 @@ async fn test_withdraw_donations(ptc: &mut ProgramTestContext, ctx: &Ctx) {
                 id: 0,
                 donations_sum: 97 + 9700,
                 withdrawn_sum: 97 + 9700,
+                goal: 0,
             },
             CampaignRecord {
                 id: 1,
                 donations_sum: 0,
                 withdrawn_sum: 0,
+                goal: 0,
             }
         ]
     );
@@ -380,41 +603,2056 @@ async fn test_liquidate_campaign(ptc: &mut ProgramTestContext, ctx: &Ctx) {
     );
 }
 
-async fn test_stop_campaign(ptc: &mut ProgramTestContext, ctx: &Ctx) {
-    stop_campaign(ptc, ctx, 0).await.unwrap();
+async fn test_transfer_campaign_authority(ptc: &mut ProgramTestContext, ctx: &Ctx) {
+    let new_authority = Keypair::new();
 
-    const CODE: u32 = ErrorCode::AccountOwnedByWrongProgram as u32;
+    const CODE: u32 = 6000 + CrowdfundingError::NoPendingAuthority as u32;
+    assert_matches!(
+        cancel_campaign_authority(ptc, &ctx.campaign_authority, 1).await,
+        Err(BanksClientError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::Custom(CODE))
+        ))
+    );
+
+    propose_campaign_authority(ptc, &ctx.campaign_authority, 1, new_authority.pubkey())
+        .await
+        .unwrap();
+    accept_campaign_authority(ptc, &new_authority, 1)
+        .await
+        .unwrap();
+
+    let Campaign {
+        authority,
+        pending_authority,
+        ..
+    } = fetch(ptc, find_campaign(1)).await.unwrap();
+    assert_eq!(authority, new_authority.pubkey());
+    assert_eq!(pending_authority, Pubkey::default());
+
+    refresh_blockhash(ptc).await;
+    const CODE2: u32 = ErrorCode::ConstraintAddress as u32;
+    assert_matches!(
+        cancel_campaign_authority(ptc, &ctx.campaign_authority, 1).await,
+        Err(BanksClientError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::Custom(CODE2))
+        ))
+    );
+
+    propose_campaign_authority(ptc, &new_authority, 1, ctx.campaign_authority.pubkey())
+        .await
+        .unwrap();
+    accept_campaign_authority(ptc, &ctx.campaign_authority, 1)
+        .await
+        .unwrap();
+
+    let Campaign { authority, .. } = fetch(ptc, find_campaign(1)).await.unwrap();
+    assert_eq!(authority, ctx.campaign_authority.pubkey());
+}
+
+async fn test_refund_donations(ptc: &mut ProgramTestContext, ctx: &Ctx) {
+    let clock: Clock = ptc.banks_client.get_sysvar().await.unwrap();
+    start_campaign_with_options(
+        ptc,
+        ctx,
+        Some(1_000_000),
+        Some(clock.unix_timestamp as u32 + 100),
+        vec![],
+    )
+    .await
+    .unwrap();
+    donate(ptc, &ctx.donors[2], 2, 1000).await.unwrap();
+
+    let Donations { uncredited_sum, .. } = fetch(
+        ptc,
+        find_donor_donations_to_campaign(ctx.donors[2].pubkey(), 2),
+    )
+    .await
+    .unwrap();
+    assert_eq!(uncredited_sum, 970);
+    let Donor { donations_sum, .. } = fetch(ptc, find_donor(ctx.donors[2].pubkey()))
+        .await
+        .unwrap();
+    assert_eq!(donations_sum, 0);
+
+    const CODE: u32 = 6000 + CrowdfundingError::FundingGoalNotReached as u32;
+    assert_matches!(
+        credit_donations(ptc, &ctx.donors[2], 2).await,
+        Err(BanksClientError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::Custom(CODE))
+        ))
+    );
+    assert_matches!(
+        withdraw_donations(ptc, ctx, 2).await,
+        Err(BanksClientError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::Custom(CODE))
+        ))
+    );
+    assert_matches!(
+        stop_campaign(ptc, ctx, 2).await,
+        Err(BanksClientError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::Custom(CODE))
+        ))
+    );
+
+    const CODE2: u32 = 6000 + CrowdfundingError::RefundUnavailable as u32;
+    assert_matches!(
+        refund_donations(ptc, &ctx.donors[2], 2).await,
+        Err(BanksClientError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::Custom(CODE2))
+        ))
+    );
+
+    advance_clock(ptc, 101).await;
+    refresh_blockhash(ptc).await;
+
+    const CODE3: u32 = 6000 + CrowdfundingError::CampaignMissedGoal as u32;
+    assert_matches!(
+        liquidate_campaign(ptc, ctx, 2).await,
+        Err(BanksClientError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::Custom(CODE3))
+        ))
+    );
+
+    assert_matches!(
+        refund_donations(ptc, &ctx.donors[2], 2).await,
+        Err(BanksClientError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::Custom(CODE2))
+        ))
+    );
+
+    // the missed goal leaves the donations in the vault for refunds
+    let vault_balance = ptc.banks_client.get_balance(ctx.sol_vault).await.unwrap();
+    expire_campaign(ptc, ctx, 2).await.unwrap();
+    assert_eq!(
+        ptc.banks_client.get_balance(ctx.sol_vault).await.unwrap(),
+        vault_balance
+    );
+    let Donations { refundable_sum, .. } = fetch(ptc, find_total_donations_to_campaign(2))
+        .await
+        .unwrap();
+    assert_eq!(refundable_sum, 970);
+
+    refresh_blockhash(ptc).await;
+    let platform_before: Platform = fetch(ptc, ctx.platform).await.unwrap();
+    refund_donations(ptc, &ctx.donors[2], 2).await.unwrap();
+    assert_eq!(
+        ptc.banks_client.get_balance(ctx.sol_vault).await.unwrap(),
+        vault_balance - 970
+    );
+    // refunded donations were never recorded, so the season sums stay exact
+    let platform: Platform = fetch(ptc, ctx.platform).await.unwrap();
+    assert_eq!({ platform.recorded_donations_sum }, {
+        platform_before.recorded_donations_sum
+    });
+    assert_eq!({ platform.ended_seasons_donations_sum }, {
+        platform_before.ended_seasons_donations_sum
+    });
+
+    assert_matches!(
+        ptc.banks_client
+            .get_account(find_donor_donations_to_campaign(ctx.donors[2].pubkey(), 2))
+            .await,
+        Ok(None)
+    );
+
+    let Donations { donations_sum, .. } = fetch(ptc, find_total_donations_to_campaign(2))
+        .await
+        .unwrap();
+    assert_eq!(donations_sum, 0);
+
+    let Donor { donations_sum, .. } = fetch(ptc, find_donor(ctx.donors[2].pubkey()))
+        .await
+        .unwrap();
+    assert_eq!(donations_sum, 0);
+}
+
+async fn test_expire_campaign(ptc: &mut ProgramTestContext, ctx: &Ctx) {
+    let clock: Clock = ptc.banks_client.get_sysvar().await.unwrap();
+    start_campaign_with_options(
+        ptc,
+        ctx,
+        None,
+        Some(clock.unix_timestamp as u32 + 100),
+        vec![],
+    )
+    .await
+    .unwrap();
+    donate(ptc, &ctx.donors[3], 3, 1000).await.unwrap();
+
+    const CODE: u32 = 6000 + CrowdfundingError::CampaignNotExpired as u32;
+    assert_matches!(
+        expire_campaign(ptc, ctx, 3).await,
+        Err(BanksClientError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::Custom(CODE))
+        ))
+    );
+
+    advance_clock(ptc, 101).await;
+    refresh_blockhash(ptc).await;
+
+    const CODE2: u32 = 6000 + CrowdfundingError::CampaignEnded as u32;
+    assert_matches!(
+        donate(ptc, &ctx.donors[3], 3, 1000).await,
+        Err(BanksClientError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::Custom(CODE2))
+        ))
+    );
+
+    let beneficiary_balance = ptc
+        .banks_client
+        .get_balance(ctx.campaign_beneficiary.pubkey())
+        .await
+        .unwrap();
+    expire_campaign(ptc, ctx, 3).await.unwrap();
+    assert!(
+        ptc.banks_client
+            .get_balance(ctx.campaign_beneficiary.pubkey())
+            .await
+            .unwrap()
+            >= beneficiary_balance + 970
+    );
+
+    let (active_campaigns, len) = fetch_active_campaigns(ptc, ctx).await.unwrap();
+    assert!(active_campaigns[..len].iter().all(|c| c.id != 3));
+}
+
+async fn test_vote_milestone(ptc: &mut ProgramTestContext, ctx: &Ctx) {
+    const CODE: u32 = 6000 + CrowdfundingError::InvalidMilestones as u32;
     assert_matches!(
-        donate(ptc, ctx, &ctx.donors[5], 0, 1).await,
+        start_campaign_with_options(ptc, ctx, None, None, vec![5000, 4000]).await,
         Err(BanksClientError::TransactionError(
             TransactionError::InstructionError(0, InstructionError::Custom(CODE))
         ))
     );
 
+    start_campaign_with_options(ptc, ctx, None, None, vec![5000, 5000])
+        .await
+        .unwrap();
+    donate(ptc, &ctx.donors[4], 4, 1000).await.unwrap();
+    donate(ptc, &ctx.donors[5], 4, 100).await.unwrap();
+
+    withdraw_donations(ptc, ctx, 4).await.unwrap();
     let (active_campaigns, len) = fetch_active_campaigns(ptc, ctx).await.unwrap();
     assert_eq!(
-        active_campaigns[..len],
-        [CampaignRecord {
-            id: 1,
-            donations_sum: 0,
+        active_campaigns[..len].iter().find(|c| c.id == 4),
+        Some(&CampaignRecord {
+            id: 4,
+            donations_sum: 970 + 97,
             withdrawn_sum: 0,
-        }]
+            goal: 0,
+        })
     );
 
-    let Platform {
-        sum_of_active_campaign_donations,
+    vote_milestone(ptc, &ctx.donors[5], 4).await.unwrap();
+    let Campaign {
+        milestones_unlocked,
         ..
-    } = fetch(ptc, ctx.platform).await.unwrap();
-    assert_eq!(sum_of_active_campaign_donations, 0);
+    } = fetch(ptc, find_campaign(4)).await.unwrap();
+    assert_eq!(milestones_unlocked, 0);
 
-    stop_campaign(ptc, ctx, 1).await.unwrap();
+    vote_milestone(ptc, &ctx.donors[4], 4).await.unwrap();
+    let Campaign {
+        milestones_unlocked,
+        ..
+    } = fetch(ptc, find_campaign(4)).await.unwrap();
+    assert_eq!(milestones_unlocked, 1);
 
+    refresh_blockhash(ptc).await;
+    withdraw_donations(ptc, ctx, 4).await.unwrap();
     let (active_campaigns, len) = fetch_active_campaigns(ptc, ctx).await.unwrap();
-    assert_eq!(active_campaigns[..len], []);
+    assert_eq!(
+        active_campaigns[..len].iter().find(|c| c.id == 4),
+        Some(&CampaignRecord {
+            id: 4,
+            donations_sum: 970 + 97,
+            withdrawn_sum: (970 + 97) / 2,
+            goal: 0,
+        })
+    );
+
+    vote_milestone(ptc, &ctx.donors[4], 4).await.unwrap();
+
+    const CODE3: u32 = 6000 + CrowdfundingError::AllMilestonesUnlocked as u32;
+    assert_matches!(
+        vote_milestone(ptc, &ctx.donors[5], 4).await,
+        Err(BanksClientError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::Custom(CODE3))
+        ))
+    );
+
+    refresh_blockhash(ptc).await;
+    stop_campaign(ptc, ctx, 4).await.unwrap();
 }
 
-async fn test_withdraw_fees(ptc: &mut ProgramTestContext, ctx: &Ctx) {
-    withdraw_fees(ptc, ctx).await.unwrap();
+async fn test_token_donations(ptc: &mut ProgramTestContext, ctx: &Ctx) {
+    let mint = Keypair::new();
+    create_mint(ptc, &mint, &ctx.platform_authority.pubkey())
+        .await
+        .unwrap();
+    let mint = mint.pubkey();
+    let donor_token_account = create_ata(ptc, &ctx.donors[6].pubkey(), &mint)
+        .await
+        .unwrap();
+    let beneficiary_token_account = create_ata(ptc, &ctx.campaign_beneficiary.pubkey(), &mint)
+        .await
+        .unwrap();
+    let platform_authority_token_account = create_ata(ptc, &ctx.platform_authority.pubkey(), &mint)
+        .await
+        .unwrap();
+    mint_tokens(
+        ptc,
+        &mint,
+        &donor_token_account,
+        10000,
+        &ctx.platform_authority,
+    )
+    .await
+    .unwrap();
 
-    assert_eq!(get_fee_vault_balance(ptc, ctx).await.unwrap(), 0);
+    start_campaign(ptc, ctx).await.unwrap();
+    open_token_vault(ptc, ctx, 5, mint).await.unwrap();
+    donate_token(ptc, &ctx.donors[6], 5, mint, 10000)
+        .await
+        .unwrap();
+
+    let token_vault: TokenAccount = fetch(ptc, find_token_vault(5, mint)).await.unwrap();
+    assert_eq!(token_vault.amount, 9700);
+    let token_fee_vault: TokenAccount = fetch(ptc, find_token_fee_vault(mint)).await.unwrap();
+    assert_eq!(token_fee_vault.amount, 300);
+
+    const CODE: u32 = 6000 + CrowdfundingError::TokenVaultsOpen as u32;
+    assert_matches!(
+        stop_campaign(ptc, ctx, 5).await,
+        Err(BanksClientError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::Custom(CODE))
+        ))
+    );
+
+    const CODE2: u32 = 6000 + CrowdfundingError::CampaignNotExpired as u32;
+    assert_matches!(
+        close_token_vault(ptc, ctx, &ctx.donors[6], 5, mint).await,
+        Err(BanksClientError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::Custom(CODE2))
+        ))
+    );
+
+    withdraw_token_donations(ptc, ctx, 5, mint).await.unwrap();
+    let beneficiary_token: TokenAccount = fetch(ptc, beneficiary_token_account).await.unwrap();
+    assert_eq!(beneficiary_token.amount, 9700);
+
+    close_token_vault(ptc, ctx, &ctx.campaign_authority, 5, mint)
+        .await
+        .unwrap();
+    assert!(ptc
+        .banks_client
+        .get_account(find_token_vault(5, mint))
+        .await
+        .unwrap()
+        .is_none());
+
+    refresh_blockhash(ptc).await;
+    stop_campaign(ptc, ctx, 5).await.unwrap();
+
+    withdraw_token_fees(ptc, ctx, mint).await.unwrap();
+    let platform_authority_token: TokenAccount =
+        fetch(ptc, platform_authority_token_account).await.unwrap();
+    assert_eq!(platform_authority_token.amount, 300);
+}
+
+async fn test_donate_during_reward_procedure(ptc: &mut ProgramTestContext, ctx: &Ctx) {
+    start_campaign(ptc, ctx).await.unwrap();
+    update_platform_config(
+        ptc,
+        ctx,
+        Some(0),
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+    )
+    .await
+    .unwrap();
+
+    let donor_0: Donor = fetch(ptc, find_donor(ctx.donors[0].pubkey()))
+        .await
+        .unwrap();
+    let donor_5: Donor = fetch(ptc, find_donor(ctx.donors[5].pubkey()))
+        .await
+        .unwrap();
+
+    record_some_donors(ptc, ctx, &ctx.donors[..1])
+        .await
+        .unwrap();
+    donate(ptc, &ctx.donors[0], 6, 100).await.unwrap();
+    donate(ptc, &ctx.donors[5], 6, 100).await.unwrap();
+    record_some_donors(ptc, ctx, &ctx.donors[1..])
+        .await
+        .unwrap();
+
+    let Platform { season, .. } = fetch(ptc, ctx.platform).await.unwrap();
+    let Donor {
+        donations_sum,
+        recorded_donations_sum,
+        season: donor_season,
+        ..
+    } = fetch(ptc, find_donor(ctx.donors[0].pubkey()))
+        .await
+        .unwrap();
+    assert_eq!(donations_sum, { donor_0.donations_sum } + 97);
+    assert_eq!(recorded_donations_sum, { donor_0.donations_sum });
+    assert_eq!(donor_season, season);
+
+    let Donor {
+        donations_sum,
+        recorded_donations_sum,
+        ..
+    } = fetch(ptc, find_donor(ctx.donors[5].pubkey()))
+        .await
+        .unwrap();
+    assert_eq!(donations_sum, { donor_5.donations_sum } + 97);
+    assert_eq!(recorded_donations_sum, { donor_5.donations_sum });
+
+    drop_rewards(ptc, ctx).await.unwrap();
+
+    let Donor {
+        rewarded_donations_sum,
+        ..
+    } = fetch(ptc, find_donor(ctx.donors[0].pubkey()))
+        .await
+        .unwrap();
+    assert_eq!(rewarded_donations_sum, { donor_0.donations_sum });
+}
+
+async fn test_reward_tiers(ptc: &mut ProgramTestContext, ctx: &Ctx) {
+    const CODE: u32 = 6000 + CrowdfundingError::InvalidRewardTiers as u32;
+    assert_matches!(
+        update_platform_config(
+            ptc,
+            ctx,
+            None,
+            None,
+            None,
+            None,
+            Some(vec![
+                RewardTier {
+                    last_rank: 3,
+                    amount: 300,
+                },
+                RewardTier {
+                    last_rank: 3,
+                    amount: 100,
+                },
+            ]),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+        .await,
+        Err(BanksClientError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::Custom(CODE))
+        ))
+    );
+
+    update_platform_config(
+        ptc,
+        ctx,
+        None,
+        None,
+        None,
+        None,
+        Some(vec![RewardTier {
+            last_rank: 1,
+            amount: 300,
+        }]),
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+    )
+    .await
+    .unwrap();
+
+    advance_clock(ptc, 1).await;
+    record_donors(ptc, ctx).await.unwrap();
+
+    // donors 0 and 5 donated during the previous procedure
+    let Platform {
+        recorded_donations_sum,
+        ended_seasons_donations_sum,
+        ..
+    } = fetch(ptc, ctx.platform).await.unwrap();
+    assert_eq!(recorded_donations_sum - ended_seasons_donations_sum, 2 * 97);
+    let (seasonal_top, len) = fetch_seasonal_top(ptc, ctx).await.unwrap();
+    assert_eq!(len, 2);
+    assert!(seasonal_top[..len].iter().all(|d| d.unrewarded_sum == 97));
+    let first_chrt = get_associated_token_address(&seasonal_top[0].donor, &ctx.chrt_mint);
+    let second_chrt = get_associated_token_address(&seasonal_top[1].donor, &ctx.chrt_mint);
+    let first_balance = fetch::<TokenAccount>(ptc, first_chrt).await.unwrap().amount;
+    let second_balance = fetch::<TokenAccount>(ptc, second_chrt)
+        .await
+        .unwrap()
+        .amount;
+
+    drop_rewards(ptc, ctx).await.unwrap();
+
+    let first: TokenAccount = fetch(ptc, first_chrt).await.unwrap();
+    assert_eq!(first.amount, first_balance + 300);
+    let second: TokenAccount = fetch(ptc, second_chrt).await.unwrap();
+    assert_eq!(second.amount, second_balance);
+
+    update_platform_config(
+        ptc,
+        ctx,
+        None,
+        None,
+        Some(1000),
+        Some(RewardDistribution::Proportional),
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+    )
+    .await
+    .unwrap();
+
+    // the unrewarded donor stays in the next seasonal top alone
+    advance_clock(ptc, 1).await;
+    record_donors(ptc, ctx).await.unwrap();
+    drop_rewards(ptc, ctx).await.unwrap();
+
+    let second: TokenAccount = fetch(ptc, second_chrt).await.unwrap();
+    assert_eq!(second.amount, second_balance + 1000);
+}
+
+async fn test_claim_reward(ptc: &mut ProgramTestContext, ctx: &Ctx) {
+    update_platform_config(
+        ptc,
+        ctx,
+        None,
+        None,
+        None,
+        Some(RewardDistribution::Tiers),
+        Some(vec![RewardTier {
+            last_rank: SEASONAL_TOP_CAPACITY,
+            amount: 500,
+        }]),
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+    )
+    .await
+    .unwrap();
+
+    donate(ptc, &ctx.donors[1], 6, 100).await.unwrap();
+    donate(ptc, &ctx.donors[2], 6, 100).await.unwrap();
+    advance_clock(ptc, 1).await;
+    record_donors(ptc, ctx).await.unwrap();
+
+    let (seasonal_top, len) = fetch_seasonal_top(ptc, ctx).await.unwrap();
+    assert_eq!(len, 2);
+    let leaves: Vec<_> = seasonal_top[..len]
+        .iter()
+        .map(|d| reward_leaf(&d.donor, 500))
+        .collect();
+    let Platform { season, .. } = fetch(ptc, ctx.platform).await.unwrap();
+
+    commit_rewards(ptc, ctx).await.unwrap();
+
+    let season_account: Season = fetch(ptc, find_season(season)).await.unwrap();
+    assert_eq!({ season_account.merkle_root }, merkle_root(&leaves));
+    assert_eq!({ season_account.rewards_sum }, 1000);
+    let (seasonal_top_after, len_after) = fetch_seasonal_top(ptc, ctx).await.unwrap();
+    assert_eq!(seasonal_top_after[..len_after], []);
+
+    let donor = ctx
+        .donors
+        .iter()
+        .find(|d| d.pubkey() == seasonal_top[0].donor)
+        .unwrap();
+    let proof = merkle_proof(&leaves, 0);
+
+    const CODE: u32 = 6000 + CrowdfundingError::InvalidMerkleProof as u32;
+    assert_matches!(
+        claim_reward(ptc, donor, season, 501, proof.clone()).await,
+        Err(BanksClientError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::Custom(CODE))
+        ))
+    );
+
+    let donor_chrt = get_associated_token_address(&donor.pubkey(), &ctx.chrt_mint);
+    let balance = fetch::<TokenAccount>(ptc, donor_chrt).await.unwrap().amount;
+    claim_reward(ptc, donor, season, 500, proof.clone())
+        .await
+        .unwrap();
+    let chrt: TokenAccount = fetch(ptc, donor_chrt).await.unwrap();
+    assert_eq!(chrt.amount, balance + 500);
+
+    refresh_blockhash(ptc).await;
+    assert!(claim_reward(ptc, donor, season, 500, proof).await.is_err());
+
+    let Season {
+        claimed_rewards_sum,
+        ..
+    } = fetch(ptc, find_season(season)).await.unwrap();
+    assert_eq!(claimed_rewards_sum, 500);
+}
+
+async fn test_resize_seasonal_top(ptc: &mut ProgramTestContext, ctx: &Ctx) {
+    const CODE: u32 = 6000 + CrowdfundingError::InvalidSeasonalTopCapacity as u32;
+    assert_matches!(
+        resize_seasonal_top(ptc, ctx, MAX_SEASONAL_TOP_CAPACITY + 1).await,
+        Err(BanksClientError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::Custom(CODE))
+        ))
+    );
+
+    resize_seasonal_top(ptc, ctx, 3).await.unwrap();
+    let (seasonal_top, len) = fetch_seasonal_top(ptc, ctx).await.unwrap();
+    assert_eq!((seasonal_top.len(), len), (3, 0));
+
+    for donor in &ctx.donors[3..7] {
+        donate(ptc, donor, 6, 100).await.unwrap();
+    }
+    advance_clock(ptc, 1).await;
+    record_donors(ptc, ctx).await.unwrap();
+
+    let (_, len) = fetch_seasonal_top(ptc, ctx).await.unwrap();
+    assert_eq!(len, 3);
+
+    const CODE2: u32 = 6000 + CrowdfundingError::RewardProcedureInProcess as u32;
+    assert_matches!(
+        resize_seasonal_top(ptc, ctx, SEASONAL_TOP_CAPACITY).await,
+        Err(BanksClientError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::Custom(CODE2))
+        ))
+    );
+    assert_matches!(
+        update_platform_config(
+            ptc,
+            ctx,
+            None,
+            None,
+            Some(1000),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+        .await,
+        Err(BanksClientError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::Custom(CODE2))
+        ))
+    );
+
+    // the first page starts the distribution that has to be finished before anything else
+    let page = fetch_reward_page(ptc, ctx, 2).await.unwrap();
+    drop_rewards_page(ptc, ctx, &page).await.unwrap();
+    let SeasonalTop { cursor, .. } = fetch(ptc, ctx.seasonal_top).await.unwrap();
+    assert_eq!(cursor, 2);
+
+    const CODE3: u32 = 6000 + CrowdfundingError::RewardDistributionInProgress as u32;
+    refresh_blockhash(ptc).await;
+    assert_matches!(
+        record_donors(ptc, ctx).await,
+        Err(BanksClientError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::Custom(CODE3))
+        ))
+    );
+    assert_matches!(
+        abort_reward_procedure(ptc, ctx).await,
+        Err(BanksClientError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::Custom(CODE3))
+        ))
+    );
+
+    const CODE4: u32 = 6000 + CrowdfundingError::RewardModeMismatch as u32;
+    assert_matches!(
+        commit_rewards(ptc, ctx).await,
+        Err(BanksClientError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::Custom(CODE4))
+        ))
+    );
+
+    let Platform { season, .. } = fetch(ptc, ctx.platform).await.unwrap();
+    drop_rewards(ptc, ctx).await.unwrap();
+
+    let Platform {
+        reward_procedure_is_in_process,
+        ..
+    } = fetch(ptc, ctx.platform).await.unwrap();
+    assert!(!reward_procedure_is_in_process);
+    let (season, season_top) = fetch_with_records::<Season, SeasonRecord>(ptc, find_season(season))
+        .await
+        .unwrap();
+    assert_eq!(season_top.len(), 3);
+    assert_eq!({ season.donors_rewarded }, 3);
+    assert_eq!({ season.rewards_sum }, 1500);
+
+    resize_seasonal_top(ptc, ctx, SEASONAL_TOP_CAPACITY)
+        .await
+        .unwrap();
+}
+
+async fn test_referral_caps(ptc: &mut ProgramTestContext, ctx: &Ctx) {
+    // 1 CHRT base unit per 1000 lamports, at most 50 per donation and 80 per season
+    update_platform_config(
+        ptc,
+        ctx,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        Some(1_000_000),
+        Some(50),
+        Some(80),
+        None,
+    )
+    .await
+    .unwrap();
+
+    let referer = &ctx.donors[8];
+    let referer_chrt = get_associated_token_address(&referer.pubkey(), &ctx.chrt_mint);
+    let balance = fetch::<TokenAccount>(ptc, referer_chrt)
+        .await
+        .unwrap()
+        .amount;
+
+    for expected in [50, 80, 80] {
+        donate_with_referer(ptc, &ctx.donors[7], 6, 100_000, referer.pubkey())
+            .await
+            .unwrap();
+        let chrt: TokenAccount = fetch(ptc, referer_chrt).await.unwrap();
+        assert_eq!(chrt.amount, balance + expected);
+        refresh_blockhash(ptc).await;
+    }
+
+    let Platform { season, .. } = fetch(ptc, ctx.platform).await.unwrap();
+    let Donor {
+        referral_season,
+        season_referral_rewards,
+        ..
+    } = fetch(ptc, find_donor(referer.pubkey())).await.unwrap();
+    assert_eq!(referral_season, season);
+    assert_eq!(season_referral_rewards, 80);
+
+    update_platform_config(
+        ptc,
+        ctx,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        Some(DEFAULT_REFERRAL_RATE),
+        Some(0),
+        Some(0),
+        None,
+    )
+    .await
+    .unwrap();
+}
+
+async fn test_referral_code(ptc: &mut ProgramTestContext, ctx: &Ctx) {
+    let referer = &ctx.donors[9];
+
+    const CODE: u32 = 6000 + CrowdfundingError::InvalidReferralCode as u32;
+    assert_matches!(
+        register_referral_code(ptc, referer, "not-a-code!").await,
+        Err(BanksClientError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::Custom(CODE))
+        ))
+    );
+    // rejected before the over-long code would fail deriving the PDA
+    assert_matches!(
+        register_raw_referral_code(ptc, referer, &"a".repeat(40)).await,
+        Err(BanksClientError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::Custom(CODE))
+        ))
+    );
+    assert_matches!(
+        register_raw_referral_code(ptc, referer, "Donor-9").await,
+        Err(BanksClientError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::Custom(CODE))
+        ))
+    );
+
+    register_referral_code(ptc, referer, "donor-9")
+        .await
+        .unwrap();
+    let ReferralCode { donor } = fetch(ptc, find_referral_code("donor-9")).await.unwrap();
+    assert_eq!(donor, referer.pubkey());
+    assert!(register_referral_code(ptc, &ctx.donors[10], "donor-9")
+        .await
+        .is_err());
+    // the client lowercases codes, which keep their case-insensitive owner
+    assert!(register_referral_code(ptc, &ctx.donors[10], "Donor-9")
+        .await
+        .is_err());
+    let Donor { referral_code, .. } = fetch(ptc, find_donor(referer.pubkey())).await.unwrap();
+    assert_eq!(&referral_code[..7], b"donor-9");
+
+    const CODE3: u32 = 6000 + CrowdfundingError::ReferralCodeAlreadyRegistered as u32;
+    assert_matches!(
+        register_referral_code(ptc, referer, "donor-9-b").await,
+        Err(BanksClientError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::Custom(CODE3))
+        ))
+    );
+
+    const CODE2: u32 = 6000 + CrowdfundingError::ReferralCodeMismatch as u32;
+    assert_matches!(
+        donate_with_referral_code(
+            ptc,
+            &ctx.donors[11],
+            6,
+            100_000,
+            "donor-9",
+            ctx.donors[10].pubkey()
+        )
+        .await,
+        Err(BanksClientError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::Custom(CODE2))
+        ))
+    );
+
+    let referer_chrt = get_associated_token_address(&referer.pubkey(), &ctx.chrt_mint);
+    let balance = fetch::<TokenAccount>(ptc, referer_chrt)
+        .await
+        .unwrap()
+        .amount;
+    for _ in 0..2 {
+        donate_with_referral_code(
+            ptc,
+            &ctx.donors[11],
+            6,
+            100_000,
+            "donor-9",
+            referer.pubkey(),
+        )
+        .await
+        .unwrap();
+        refresh_blockhash(ptc).await;
+    }
+
+    // 101 CHRT per SOL
+    let chrt: TokenAccount = fetch(ptc, referer_chrt).await.unwrap();
+    assert_eq!(chrt.amount, balance + 2 * 10);
+
+    let Donor {
+        referred_donors,
+        referred_volume,
+        referral_rewards_sum,
+        ..
+    } = fetch(ptc, find_donor(referer.pubkey())).await.unwrap();
+    assert_eq!(referred_donors, 1);
+    assert_eq!(referred_volume, 2 * 100_000);
+    assert_eq!(referral_rewards_sum, 2 * 10);
+}
+
+async fn test_create_referer_chrt(ptc: &mut ProgramTestContext, ctx: &Ctx) {
+    let referer = Keypair::new();
+    airdrop(ptc, &referer.pubkey(), 100_000_000).await;
+    register_donor(ptc, &referer).await.unwrap();
+    let referer_chrt = get_associated_token_address(&referer.pubkey(), &ctx.chrt_mint);
+    assert!(ptc
+        .banks_client
+        .get_account(referer_chrt)
+        .await
+        .unwrap()
+        .is_none());
+
+    donate_with_referer(ptc, &ctx.donors[12], 6, 100_000, referer.pubkey())
+        .await
+        .unwrap();
+
+    let chrt: TokenAccount = fetch(ptc, referer_chrt).await.unwrap();
+    assert_eq!(chrt.owner, referer.pubkey());
+    assert_eq!(chrt.amount, 10);
+
+    // a CHRT account the referer already has is used as is
+    let referer = Keypair::new();
+    airdrop(ptc, &referer.pubkey(), 100_000_000).await;
+    register_donor(ptc, &referer).await.unwrap();
+    let referer_chrt = Keypair::new();
+    create_token_account(ptc, &referer_chrt, &ctx.chrt_mint, &referer.pubkey())
+        .await
+        .unwrap();
+
+    const CODE: u32 = ErrorCode::ConstraintTokenOwner as u32;
+    assert_matches!(
+        donate_with_referer_chrt(
+            ptc,
+            &ctx.donors[12],
+            6,
+            100_000,
+            referer.pubkey(),
+            get_associated_token_address(&ctx.donors[10].pubkey(), &ctx.chrt_mint),
+        )
+        .await,
+        Err(BanksClientError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::Custom(CODE))
+        ))
+    );
+
+    donate_with_referer_chrt(
+        ptc,
+        &ctx.donors[12],
+        6,
+        100_000,
+        referer.pubkey(),
+        referer_chrt.pubkey(),
+    )
+    .await
+    .unwrap();
+    let chrt: TokenAccount = fetch(ptc, referer_chrt.pubkey()).await.unwrap();
+    assert_eq!(chrt.amount, 10);
+    assert!(ptc
+        .banks_client
+        .get_account(get_associated_token_address(
+            &referer.pubkey(),
+            &ctx.chrt_mint
+        ))
+        .await
+        .unwrap()
+        .is_none());
+}
+
+async fn test_credit_donations(ptc: &mut ProgramTestContext, ctx: &Ctx) {
+    let Platform {
+        campaigns_count: id,
+        ..
+    } = fetch(ptc, ctx.platform).await.unwrap();
+    start_campaign_with_options(ptc, ctx, Some(150_000), Some(u32::MAX), vec![])
+        .await
+        .unwrap();
+
+    let referer = &ctx.donors[10];
+    let referer_chrt = get_associated_token_address(&referer.pubkey(), &ctx.chrt_mint);
+    let balance = fetch::<TokenAccount>(ptc, referer_chrt)
+        .await
+        .unwrap()
+        .amount;
+    let Donor {
+        donations_sum: donor13_sum,
+        ..
+    } = fetch(ptc, find_donor(ctx.donors[13].pubkey()))
+        .await
+        .unwrap();
+    let Donor {
+        donations_sum: donor14_sum,
+        ..
+    } = fetch(ptc, find_donor(ctx.donors[14].pubkey()))
+        .await
+        .unwrap();
+
+    // the referral is rewarded once the goal is reached
+    donate_with_referer(ptc, &ctx.donors[13], id, 100_000, referer.pubkey())
+        .await
+        .unwrap();
+    let chrt: TokenAccount = fetch(ptc, referer_chrt).await.unwrap();
+    assert_eq!(chrt.amount, balance);
+    let Donor { donations_sum, .. } = fetch(ptc, find_donor(ctx.donors[13].pubkey()))
+        .await
+        .unwrap();
+    assert_eq!(donations_sum, donor13_sum);
+
+    // the donation reaching the goal is credited right away
+    donate(ptc, &ctx.donors[14], id, 100_000).await.unwrap();
+    let Donations {
+        donations_sum: donor14_donations,
+        uncredited_sum,
+        ..
+    } = fetch(
+        ptc,
+        find_donor_donations_to_campaign(ctx.donors[14].pubkey(), id),
+    )
+    .await
+    .unwrap();
+    assert_eq!(uncredited_sum, 0);
+    let Donor { donations_sum, .. } = fetch(ptc, find_donor(ctx.donors[14].pubkey()))
+        .await
+        .unwrap();
+    assert_eq!(donations_sum, donor14_sum + donor14_donations);
+
+    let Donations {
+        donations_sum: donor13_donations,
+        ..
+    } = fetch(
+        ptc,
+        find_donor_donations_to_campaign(ctx.donors[13].pubkey(), id),
+    )
+    .await
+    .unwrap();
+
+    // the referral can be neither dropped nor redirected
+    const CODE: u32 = 6000 + CrowdfundingError::RefererMismatch as u32;
+    assert_matches!(
+        credit_donations(ptc, &ctx.donors[13], id).await,
+        Err(BanksClientError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::Custom(CODE))
+        ))
+    );
+    assert_matches!(
+        credit_donations_with_referer(ptc, &ctx.donors[13], id, ctx.donors[11].pubkey()).await,
+        Err(BanksClientError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::Custom(CODE))
+        ))
+    );
+    assert_matches!(
+        donate_with_referer(ptc, &ctx.donors[13], id, 1000, ctx.donors[11].pubkey()).await,
+        Err(BanksClientError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::Custom(CODE))
+        ))
+    );
+
+    let logs = process_with_logs(
+        ptc,
+        ix::credit_donations_with_referer(ctx.donors[13].pubkey(), id, referer.pubkey()),
+        &[&ctx.donors[13]],
+    )
+    .await
+    .unwrap();
+    let credits: Vec<DonationsCredited> = events(&logs);
+    assert_eq!(credits.len(), 1);
+    assert_eq!(credits[0].donor, ctx.donors[13].pubkey());
+    assert_eq!(credits[0].campaign_id, id);
+    assert_eq!(credits[0].amount, donor13_donations);
+    let rewards: Vec<ReferralRewardMinted> = events(&logs);
+    assert_eq!(rewards.len(), 1);
+    assert_eq!(rewards[0].amount, 10);
+
+    let chrt: TokenAccount = fetch(ptc, referer_chrt).await.unwrap();
+    assert_eq!(chrt.amount, balance + 10);
+    let Donor { donations_sum, .. } = fetch(ptc, find_donor(ctx.donors[13].pubkey()))
+        .await
+        .unwrap();
+    assert_eq!(donations_sum, donor13_sum + donor13_donations);
+
+    // nothing is left to credit
+    refresh_blockhash(ptc).await;
+    credit_donations_with_referer(ptc, &ctx.donors[13], id, referer.pubkey())
+        .await
+        .unwrap();
+    let chrt: TokenAccount = fetch(ptc, referer_chrt).await.unwrap();
+    assert_eq!(chrt.amount, balance + 10);
+    let Donor { donations_sum, .. } = fetch(ptc, find_donor(ctx.donors[13].pubkey()))
+        .await
+        .unwrap();
+    assert_eq!(donations_sum, donor13_sum + donor13_donations);
+}
+
+async fn test_stop_with_locked_milestones(ptc: &mut ProgramTestContext, ctx: &Ctx) {
+    let Platform {
+        campaigns_count: id,
+        ..
+    } = fetch(ptc, ctx.platform).await.unwrap();
+    start_campaign_with_options(ptc, ctx, None, None, vec![5000, 5000])
+        .await
+        .unwrap();
+    donate(ptc, &ctx.donors[4], id, 1000).await.unwrap();
+    donate(ptc, &ctx.donors[5], id, 3000).await.unwrap();
+
+    // the campaign's own wallets are left out of the vote
+    register_donor(ptc, &ctx.campaign_authority).await.unwrap();
+    donate(ptc, &ctx.campaign_authority, id, 1000)
+        .await
+        .unwrap();
+    const CODE: u32 = 6000 + CrowdfundingError::MilestoneSelfVote as u32;
+    assert_matches!(
+        vote_milestone(ptc, &ctx.campaign_authority, id).await,
+        Err(BanksClientError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::Custom(CODE))
+        ))
+    );
+    vote_milestone(ptc, &ctx.donors[5], id).await.unwrap();
+
+    // the locked second milestone stays in the vault for donors to refund
+    let fee_vault_balance = ptc.banks_client.get_balance(ctx.fee_vault).await.unwrap();
+    let vault_balance = ptc.banks_client.get_balance(ctx.sol_vault).await.unwrap();
+    let logs = process_with_logs(
+        ptc,
+        ix::stop_campaign(
+            ctx.campaign_authority.pubkey(),
+            id,
+            ctx.campaign_beneficiary.pubkey(),
+            ctx.campaign_authority.pubkey(),
+        ),
+        &[&ctx.campaign_authority],
+    )
+    .await
+    .unwrap();
+    let stops: Vec<CampaignStopped> = events(&logs);
+    assert_eq!(stops.len(), 1);
+    assert_eq!(stops[0].amount, 2425);
+    assert_eq!(stops[0].refundable_amount, 2425);
+    assert_eq!(
+        ptc.banks_client.get_balance(ctx.fee_vault).await.unwrap(),
+        fee_vault_balance
+    );
+    assert_eq!(
+        ptc.banks_client.get_balance(ctx.sol_vault).await.unwrap(),
+        vault_balance - 2425
+    );
+
+    // donors refund it pro rata to their donations
+    refund_donations(ptc, &ctx.donors[4], id).await.unwrap();
+    assert_eq!(
+        ptc.banks_client.get_balance(ctx.sol_vault).await.unwrap(),
+        vault_balance - 2425 - 485
+    );
+    refund_donations(ptc, &ctx.donors[5], id).await.unwrap();
+    assert_eq!(
+        ptc.banks_client.get_balance(ctx.sol_vault).await.unwrap(),
+        vault_balance - 2425 - 485 - 1455
+    );
+    let Donations { refundable_sum, .. } = fetch(ptc, find_total_donations_to_campaign(id))
+        .await
+        .unwrap();
+    assert_eq!(refundable_sum, 485);
+}
+
+async fn test_token_vault_with_locked_milestones(ptc: &mut ProgramTestContext, ctx: &Ctx) {
+    let mint = Keypair::new();
+    create_mint(ptc, &mint, &ctx.platform_authority.pubkey())
+        .await
+        .unwrap();
+    let mint = mint.pubkey();
+    let donor_token_account = create_ata(ptc, &ctx.donors[4].pubkey(), &mint)
+        .await
+        .unwrap();
+    let beneficiary_token_account = create_ata(ptc, &ctx.campaign_beneficiary.pubkey(), &mint)
+        .await
+        .unwrap();
+    mint_tokens(
+        ptc,
+        &mint,
+        &donor_token_account,
+        10000,
+        &ctx.platform_authority,
+    )
+    .await
+    .unwrap();
+
+    let Platform {
+        campaigns_count: id,
+        ..
+    } = fetch(ptc, ctx.platform).await.unwrap();
+    start_campaign_with_options(ptc, ctx, None, None, vec![5000, 5000])
+        .await
+        .unwrap();
+    open_token_vault(ptc, ctx, id, mint).await.unwrap();
+    donate_token(ptc, &ctx.donors[4], id, mint, 10000)
+        .await
+        .unwrap();
+
+    // the locked share keeps the vault open for the donor to refund
+    let logs = process_with_logs(
+        ptc,
+        ix::close_token_vault(
+            ctx.campaign_authority.pubkey(),
+            id,
+            mint,
+            ctx.campaign_beneficiary.pubkey(),
+        ),
+        &[&ctx.campaign_authority],
+    )
+    .await
+    .unwrap();
+    let closes: Vec<TokenVaultClosed> = events(&logs);
+    assert_eq!(closes.len(), 1);
+    assert_eq!(closes[0].amount, 0);
+    assert_eq!(closes[0].refundable_amount, 9700);
+    let token_vault: TokenAccount = fetch(ptc, find_token_vault(id, mint)).await.unwrap();
+    assert_eq!(token_vault.amount, 9700);
+    let beneficiary_token: TokenAccount = fetch(ptc, beneficiary_token_account).await.unwrap();
+    assert_eq!(beneficiary_token.amount, 0);
+
+    refresh_blockhash(ptc).await;
+    stop_campaign(ptc, ctx, id).await.unwrap();
+
+    refund_token_donations(ptc, ctx, &ctx.donors[4], id, mint)
+        .await
+        .unwrap();
+    let donor_token: TokenAccount = fetch(ptc, donor_token_account).await.unwrap();
+    assert_eq!(donor_token.amount, 9700);
+    assert!(ptc
+        .banks_client
+        .get_account(find_token_vault(id, mint))
+        .await
+        .unwrap()
+        .is_none());
+}
+
+async fn test_token_vaults_limit(ptc: &mut ProgramTestContext, ctx: &Ctx) {
+    let Platform {
+        campaigns_count: id,
+        ..
+    } = fetch(ptc, ctx.platform).await.unwrap();
+    start_campaign(ptc, ctx).await.unwrap();
+
+    let mut mints = vec![];
+    for _ in 0..=TOKEN_VAULTS_CAPACITY {
+        let mint = Keypair::new();
+        create_mint(ptc, &mint, &ctx.platform_authority.pubkey())
+            .await
+            .unwrap();
+        mints.push(mint.pubkey());
+    }
+    for &mint in &mints[..TOKEN_VAULTS_CAPACITY as usize] {
+        open_token_vault(ptc, ctx, id, mint).await.unwrap();
+    }
+
+    const CODE: u32 = 6000 + CrowdfundingError::TokenVaultsLimit as u32;
+    assert_matches!(
+        open_token_vault(ptc, ctx, id, mints[TOKEN_VAULTS_CAPACITY as usize]).await,
+        Err(BanksClientError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::Custom(CODE))
+        ))
+    );
+}
+
+async fn test_stake(ptc: &mut ProgramTestContext, ctx: &Ctx) {
+    update_platform_config(
+        ptc,
+        ctx,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        Some(20),
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        Some(100),
+    )
+    .await
+    .unwrap();
+
+    let staker = &ctx.donors[9];
+    let staker_chrt = get_associated_token_address(&staker.pubkey(), &ctx.chrt_mint);
+    let balance = fetch::<TokenAccount>(ptc, staker_chrt)
+        .await
+        .unwrap()
+        .amount;
+
+    let fees = get_fee_vault_balance(ptc, ctx).await.unwrap();
+    donate(ptc, &ctx.donors[12], 6, 1000).await.unwrap();
+    assert_eq!(get_fee_vault_balance(ptc, ctx).await.unwrap(), fees + 30);
+
+    stake(ptc, staker, 6, 20).await.unwrap();
+    let StakePool { staked_sum } = fetch(ptc, find_stake_pool(6)).await.unwrap();
+    assert_eq!(staked_sum, 20);
+    let chrt: TokenAccount = fetch(ptc, staker_chrt).await.unwrap();
+    assert_eq!(chrt.amount, balance - 20);
+
+    let fees = get_fee_vault_balance(ptc, ctx).await.unwrap();
+    refresh_blockhash(ptc).await;
+    let logs = process_with_logs(
+        ptc,
+        ix::donate(ctx.donors[12].pubkey(), 6, 1000),
+        &[&ctx.donors[12]],
+    )
+    .await
+    .unwrap();
+    assert_eq!(get_fee_vault_balance(ptc, ctx).await.unwrap(), fees);
+    let donations: Vec<DonationMade> = events(&logs);
+    assert_eq!(donations[0].fee, 30);
+    assert!(donations[0].fee_exempted);
+
+    const CODE: u32 = 6000 + CrowdfundingError::InsufficientStake as u32;
+    assert_matches!(
+        request_unstake(ptc, staker, 6, 21).await,
+        Err(BanksClientError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::Custom(CODE))
+        ))
+    );
+
+    request_unstake(ptc, staker, 6, 10).await.unwrap();
+    let StakePosition {
+        amount,
+        unstaking_amount,
+        ..
+    } = fetch(ptc, find_stake_position(6, staker.pubkey()))
+        .await
+        .unwrap();
+    assert_eq!(amount, 10);
+    assert_eq!(unstaking_amount, 10);
+    let StakePool { staked_sum } = fetch(ptc, find_stake_pool(6)).await.unwrap();
+    assert_eq!(staked_sum, 10);
+
+    refresh_blockhash(ptc).await;
+    donate(ptc, &ctx.donors[12], 6, 1000).await.unwrap();
+    assert_eq!(get_fee_vault_balance(ptc, ctx).await.unwrap(), fees + 30);
+
+    const CODE2: u32 = 6000 + CrowdfundingError::UnstakeCooldown as u32;
+    assert_matches!(
+        unstake(ptc, staker, 6).await,
+        Err(BanksClientError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::Custom(CODE2))
+        ))
+    );
+
+    advance_clock(ptc, 100).await;
+    unstake(ptc, staker, 6).await.unwrap();
+    let chrt: TokenAccount = fetch(ptc, staker_chrt).await.unwrap();
+    assert_eq!(chrt.amount, balance - 10);
+
+    const CODE3: u32 = 6000 + CrowdfundingError::NothingToUnstake as u32;
+    refresh_blockhash(ptc).await;
+    assert_matches!(
+        unstake(ptc, staker, 6).await,
+        Err(BanksClientError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::Custom(CODE3))
+        ))
+    );
+
+    update_platform_config(
+        ptc,
+        ctx,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        Some(1000),
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        Some(DEFAULT_UNSTAKE_COOLDOWN),
+    )
+    .await
+    .unwrap();
+}
+
+async fn test_zero_chrt_deposit(ptc: &mut ProgramTestContext, ctx: &Ctx) {
+    let id = 0;
+    const CODE: u32 = 6000 + CrowdfundingError::ZeroDeposit as u32;
+    assert_matches!(
+        support_campaign(ptc, &ctx.donors[9], id, 0).await,
+        Err(BanksClientError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::Custom(CODE))
+        ))
+    );
+    assert_matches!(
+        flag_campaign(ptc, &ctx.donors[9], id, 0).await,
+        Err(BanksClientError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::Custom(CODE))
+        ))
+    );
+    assert!(ptc
+        .banks_client
+        .get_account(find_chrt_deposit(
+            id,
+            ChrtVault::FeeExemption,
+            ctx.donors[9].pubkey()
+        ))
+        .await
+        .unwrap()
+        .is_none());
+}
+
+async fn test_withdraw_chrt_deposit(ptc: &mut ProgramTestContext, ctx: &Ctx) {
+    let Platform {
+        campaigns_count: id,
+        ..
+    } = fetch(ptc, ctx.platform).await.unwrap();
+    start_campaign(ptc, ctx).await.unwrap();
+
+    let supporter = &ctx.donors[9];
+    let flagger = &ctx.donors[8];
+    let supporter_chrt = get_associated_token_address(&supporter.pubkey(), &ctx.chrt_mint);
+    let flagger_chrt = get_associated_token_address(&flagger.pubkey(), &ctx.chrt_mint);
+    let supporter_balance = fetch::<TokenAccount>(ptc, supporter_chrt)
+        .await
+        .unwrap()
+        .amount;
+    let flagger_balance = fetch::<TokenAccount>(ptc, flagger_chrt)
+        .await
+        .unwrap()
+        .amount;
+
+    support_campaign(ptc, supporter, id, 5).await.unwrap();
+    flag_campaign(ptc, supporter, id, 3).await.unwrap();
+    flag_campaign(ptc, flagger, id, 2).await.unwrap();
+    let ChrtDeposit {
+        depositor, amount, ..
+    } = fetch(
+        ptc,
+        find_chrt_deposit(id, ChrtVault::Liquidation, supporter.pubkey()),
+    )
+    .await
+    .unwrap();
+    assert_eq!(depositor, supporter.pubkey());
+    assert_eq!(amount, 3);
+    let vault: TokenAccount = fetch(ptc, find_liquidation_vault(id)).await.unwrap();
+    assert_eq!(vault.amount, 3 + 2);
+
+    const CODE: u32 = 6000 + CrowdfundingError::CampaignIsActive as u32;
+    assert_matches!(
+        withdraw_chrt_deposit(ptc, ctx, supporter, id, ChrtVault::FeeExemption).await,
+        Err(BanksClientError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::Custom(CODE))
+        ))
+    );
+
+    stop_campaign(ptc, ctx, id).await.unwrap();
+    let ChrtVaults {
+        payer,
+        fee_exemption_deposits_sum,
+        liquidation_deposits_sum,
+    } = fetch(ptc, find_chrt_vaults(id)).await.unwrap();
+    assert_eq!(payer, ctx.campaign_authority.pubkey());
+    assert_eq!(fee_exemption_deposits_sum, 5);
+    assert_eq!(liquidation_deposits_sum, 3 + 2);
+
+    withdraw_chrt_deposit(ptc, ctx, supporter, id, ChrtVault::FeeExemption)
+        .await
+        .unwrap();
+    withdraw_chrt_deposit(ptc, ctx, supporter, id, ChrtVault::Liquidation)
+        .await
+        .unwrap();
+    let chrt: TokenAccount = fetch(ptc, supporter_chrt).await.unwrap();
+    assert_eq!(chrt.amount, supporter_balance);
+    assert!(ptc
+        .banks_client
+        .get_account(find_fee_exemption_vault(id))
+        .await
+        .unwrap()
+        .is_none());
+    let vault: TokenAccount = fetch(ptc, find_liquidation_vault(id)).await.unwrap();
+    assert_eq!(vault.amount, 2);
+
+    // CHRT sent to the vault directly is burnt with the last recorded deposit withdrawn, and the
+    // rent goes back to the campaign authority that paid for the vaults
+    transfer_tokens(ptc, &flagger_chrt, &find_liquidation_vault(id), flagger, 1)
+        .await
+        .unwrap();
+    let rent = ptc
+        .banks_client
+        .get_balance(find_liquidation_vault(id))
+        .await
+        .unwrap()
+        + ptc
+            .banks_client
+            .get_balance(find_chrt_vaults(id))
+            .await
+            .unwrap();
+    let authority_balance = ptc
+        .banks_client
+        .get_balance(ctx.campaign_authority.pubkey())
+        .await
+        .unwrap();
+    withdraw_chrt_deposit(ptc, ctx, flagger, id, ChrtVault::Liquidation)
+        .await
+        .unwrap();
+    let chrt: TokenAccount = fetch(ptc, flagger_chrt).await.unwrap();
+    assert_eq!(chrt.amount, flagger_balance - 1);
+    assert!(ptc
+        .banks_client
+        .get_account(find_liquidation_vault(id))
+        .await
+        .unwrap()
+        .is_none());
+    assert!(ptc
+        .banks_client
+        .get_account(find_chrt_vaults(id))
+        .await
+        .unwrap()
+        .is_none());
+    assert_eq!(
+        ptc.banks_client
+            .get_balance(ctx.campaign_authority.pubkey())
+            .await
+            .unwrap(),
+        authority_balance + rent
+    );
+    assert!(ptc
+        .banks_client
+        .get_account(find_chrt_deposit(
+            id,
+            ChrtVault::Liquidation,
+            flagger.pubkey()
+        ))
+        .await
+        .unwrap()
+        .is_none());
+}
+
+async fn test_withdraw_chrt_deposit_after_expiry(ptc: &mut ProgramTestContext, ctx: &Ctx) {
+    let Platform {
+        campaigns_count: id,
+        ..
+    } = fetch(ptc, ctx.platform).await.unwrap();
+    let clock: Clock = ptc.banks_client.get_sysvar().await.unwrap();
+    start_campaign_with_options(
+        ptc,
+        ctx,
+        None,
+        Some(clock.unix_timestamp as u32 + 100),
+        vec![],
+    )
+    .await
+    .unwrap();
+
+    let supporter = &ctx.donors[9];
+    let supporter_chrt = get_associated_token_address(&supporter.pubkey(), &ctx.chrt_mint);
+    let supporter_balance = fetch::<TokenAccount>(ptc, supporter_chrt)
+        .await
+        .unwrap()
+        .amount;
+    support_campaign(ptc, supporter, id, 5).await.unwrap();
+
+    advance_clock(ptc, 101).await;
+    refresh_blockhash(ptc).await;
+    expire_campaign(ptc, ctx, id).await.unwrap();
+
+    // the funded vault outlives the campaign, the empty one is closed
+    let vault: TokenAccount = fetch(ptc, find_fee_exemption_vault(id)).await.unwrap();
+    assert_eq!(vault.amount, 5);
+    assert!(ptc
+        .banks_client
+        .get_account(find_liquidation_vault(id))
+        .await
+        .unwrap()
+        .is_none());
+
+    withdraw_chrt_deposit(ptc, ctx, supporter, id, ChrtVault::FeeExemption)
+        .await
+        .unwrap();
+    let chrt: TokenAccount = fetch(ptc, supporter_chrt).await.unwrap();
+    assert_eq!(chrt.amount, supporter_balance);
+    assert!(ptc
+        .banks_client
+        .get_account(find_fee_exemption_vault(id))
+        .await
+        .unwrap()
+        .is_none());
+}
+
+async fn test_chrt_sent_to_vaults_directly(ptc: &mut ProgramTestContext, ctx: &Ctx) {
+    let Platform {
+        campaigns_count: id,
+        liquidation_limit,
+        ..
+    } = fetch(ptc, ctx.platform).await.unwrap();
+    start_campaign(ptc, ctx).await.unwrap();
+    update_platform_config(
+        ptc,
+        ctx,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        Some(3),
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+    )
+    .await
+    .unwrap();
+
+    // only CHRT deposited through flag_campaign votes for the liquidation
+    let flagger = &ctx.donors[9];
+    let flagger_chrt = get_associated_token_address(&flagger.pubkey(), &ctx.chrt_mint);
+    transfer_tokens(ptc, &flagger_chrt, &find_liquidation_vault(id), flagger, 3)
+        .await
+        .unwrap();
+    const CODE: u32 = 6000 + CrowdfundingError::NotEnoughCHRTInVault as u32;
+    assert_matches!(
+        liquidate_campaign(ptc, ctx, id).await,
+        Err(BanksClientError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::Custom(CODE))
+        ))
+    );
+
+    flag_campaign(ptc, flagger, id, 2).await.unwrap();
+    let Campaign {
+        liquidation_deposits_sum,
+        fee_exemption_deposits_sum,
+        ..
+    } = fetch(ptc, find_campaign(id)).await.unwrap();
+    assert_eq!(liquidation_deposits_sum, 2);
+    assert_eq!(fee_exemption_deposits_sum, 0);
+    let vault: TokenAccount = fetch(ptc, find_liquidation_vault(id)).await.unwrap();
+    assert_eq!(vault.amount, 3 + 2);
+    refresh_blockhash(ptc).await;
+    assert_matches!(
+        liquidate_campaign(ptc, ctx, id).await,
+        Err(BanksClientError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::Custom(CODE))
+        ))
+    );
+
+    update_platform_config(
+        ptc,
+        ctx,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        Some(liquidation_limit),
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+    )
+    .await
+    .unwrap();
+}
+
+async fn test_liquidation_shares(ptc: &mut ProgramTestContext, ctx: &Ctx) {
+    let Platform {
+        campaigns_count: id,
+        liquidation_limit,
+        ..
+    } = fetch(ptc, ctx.platform).await.unwrap();
+    let clock: Clock = ptc.banks_client.get_sysvar().await.unwrap();
+    start_campaign(ptc, ctx).await.unwrap();
+    start_campaign_with_options(
+        ptc,
+        ctx,
+        Some(1_000_000),
+        Some(clock.unix_timestamp as u32 + 100),
+        vec![],
+    )
+    .await
+    .unwrap();
+    donate(ptc, &ctx.donors[3], id, 1000).await.unwrap();
+    donate(ptc, &ctx.donors[3], id + 1, 1000).await.unwrap();
+
+    update_platform_config(
+        ptc,
+        ctx,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        Some(1),
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+    )
+    .await
+    .unwrap();
+    flag_campaign(ptc, &ctx.donors[9], id, 1).await.unwrap();
+    liquidate_campaign(ptc, ctx, id).await.unwrap();
+
+    // the campaign below its goal may still refund its donors, so it takes no share
+    let (active_campaigns, len) = fetch_active_campaigns(ptc, ctx).await.unwrap();
+    assert_eq!(
+        active_campaigns[..len].iter().find(|c| c.id == id + 1),
+        Some(&CampaignRecord {
+            id: id + 1,
+            donations_sum: 970,
+            withdrawn_sum: 0,
+            goal: 1_000_000,
+        })
+    );
+
+    update_platform_config(
+        ptc,
+        ctx,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        Some(liquidation_limit),
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+    )
+    .await
+    .unwrap();
+}
+
+async fn test_abort_reward_procedure(ptc: &mut ProgramTestContext, ctx: &Ctx) {
+    update_platform_config(
+        ptc,
+        ctx,
+        None,
+        Some(100),
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+    )
+    .await
+    .unwrap();
+
+    const CODE: u32 = 6000 + CrowdfundingError::RewardProcedureNotInProcess as u32;
+    assert_matches!(
+        abort_reward_procedure(ptc, ctx).await,
+        Err(BanksClientError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::Custom(CODE))
+        ))
+    );
+
+    advance_clock(ptc, 1).await;
+    record_some_donors(ptc, ctx, &ctx.donors[..1])
+        .await
+        .unwrap();
+    let Platform { season, .. } = fetch(ptc, ctx.platform).await.unwrap();
+    advance_clock(ptc, 100).await;
+
+    const CODE2: u32 = 6000 + CrowdfundingError::RewardProcedureTimedOut as u32;
+    assert_matches!(
+        drop_rewards(ptc, ctx).await,
+        Err(BanksClientError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::Custom(CODE2))
+        ))
+    );
+
+    // the stuck procedure is restarted by whoever records donors next
+    let logs = process_with_logs(
+        ptc,
+        ix::record_donors(
+            ctx.donors[0].pubkey(),
+            &ctx.donors[1..]
+                .iter()
+                .map(|d| d.pubkey())
+                .collect::<Vec<_>>(),
+        ),
+        &[&ctx.donors[0]],
+    )
+    .await
+    .unwrap();
+    let aborts: Vec<RewardProcedureAborted> = events(&logs);
+    assert_eq!(aborts.len(), 1);
+    assert_eq!(aborts[0].authority, ctx.donors[0].pubkey());
+    assert_eq!(aborts[0].donors_recorded, 1);
+
+    let Platform {
+        season: restarted_season,
+        donors_recorded,
+        ..
+    } = fetch(ptc, ctx.platform).await.unwrap();
+    assert_eq!(restarted_season, season + 1);
+    assert_eq!(donors_recorded, ctx.donors.len() as u32 - 1);
+
+    abort_reward_procedure(ptc, ctx).await.unwrap();
+
+    let Platform {
+        reward_procedure_is_in_process,
+        donors_recorded,
+        ..
+    } = fetch(ptc, ctx.platform).await.unwrap();
+    assert!(!reward_procedure_is_in_process);
+    assert_eq!(donors_recorded, 0);
+
+    let (seasonal_top, len) = fetch_seasonal_top(ptc, ctx).await.unwrap();
+    assert_eq!(seasonal_top[..len], []);
+
+    stop_campaign(ptc, ctx, 6).await.unwrap();
+    update_platform_config(
+        ptc,
+        ctx,
+        Some(10),
+        Some(0),
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+    )
+    .await
+    .unwrap();
+}
+
+async fn test_stop_campaign(ptc: &mut ProgramTestContext, ctx: &Ctx) {
+    let (active_campaigns, len) = fetch_active_campaigns(ptc, ctx).await.unwrap();
+    let record = active_campaigns[..len].iter().find(|c| c.id == 0).unwrap();
+    let amount = record.donations_sum - record.withdrawn_sum;
+    let logs = process_with_logs(
+        ptc,
+        ix::stop_campaign(
+            ctx.campaign_authority.pubkey(),
+            0,
+            ctx.campaign_beneficiary.pubkey(),
+            ctx.campaign_authority.pubkey(),
+        ),
+        &[&ctx.campaign_authority],
+    )
+    .await
+    .unwrap();
+    let stops: Vec<CampaignStopped> = events(&logs);
+    assert_eq!(stops.len(), 1);
+    assert_eq!(stops[0].campaign_id, 0);
+    assert_eq!(stops[0].beneficiary, ctx.campaign_beneficiary.pubkey());
+    assert_eq!(stops[0].amount, amount);
+    assert_eq!(stops[0].refundable_amount, 0);
+    assert!(!stops[0].expired);
+
+    const CODE: u32 = ErrorCode::AccountOwnedByWrongProgram as u32;
+    assert_matches!(
+        donate(ptc, &ctx.donors[5], 0, 1).await,
+        Err(BanksClientError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::Custom(CODE))
+        ))
+    );
+
+    let (active_campaigns, len) = fetch_active_campaigns(ptc, ctx).await.unwrap();
+    assert_eq!(
+        active_campaigns[..len],
+        [CampaignRecord {
+            id: 1,
+            donations_sum: 0,
+            withdrawn_sum: 0,
+            goal: 0,
+        }]
+    );
+
+    let Platform {
+        sum_of_active_campaign_donations,
+        ..
+    } = fetch(ptc, ctx.platform).await.unwrap();
+    assert_eq!(sum_of_active_campaign_donations, 0);
+
+    stop_campaign(ptc, ctx, 1).await.unwrap();
+
+    let (active_campaigns, len) = fetch_active_campaigns(ptc, ctx).await.unwrap();
+    assert_eq!(active_campaigns[..len], []);
+}
+
+async fn test_withdraw_fees(ptc: &mut ProgramTestContext, ctx: &Ctx) {
+    withdraw_fees(ptc, ctx).await.unwrap();
+
+    assert_eq!(get_fee_vault_balance(ptc, ctx).await.unwrap(), 0);
+}
+
+async fn test_update_platform_config(ptc: &mut ProgramTestContext, ctx: &Ctx) {
+    update_platform_config(
+        ptc,
+        ctx,
+        None,
+        None,
+        Some(2000),
+        None,
+        None,
+        Some(500),
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+    )
+    .await
+    .unwrap();
+
+    let Platform {
+        reward_amount,
+        reward_cooldown,
+        fee_basis_points,
+        fee_exemption_limit,
+        liquidation_limit,
+        ..
+    } = fetch(ptc, ctx.platform).await.unwrap();
+    assert_eq!(reward_cooldown, 10);
+    assert_eq!(reward_amount, 2000);
+    assert_eq!(fee_basis_points, 500);
+    assert_eq!(fee_exemption_limit, 1000);
+    assert_eq!(liquidation_limit, 2000);
+
+    const CODE: u32 = 6000 + CrowdfundingError::InvalidFeeBasisPoints as u32;
+    assert_matches!(
+        update_platform_config(
+            ptc,
+            ctx,
+            None,
+            None,
+            None,
+            None,
+            None,
+            Some(10001),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+        .await,
+        Err(BanksClientError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::Custom(CODE))
+        ))
+    );
+
+    const CODE2: u32 = 6000 + CrowdfundingError::InvalidLiquidationLimit as u32;
+    assert_matches!(
+        update_platform_config(
+            ptc,
+            ctx,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            Some(0),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+        .await,
+        Err(BanksClientError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::Custom(CODE2))
+        ))
+    );
+}
+
+async fn test_transfer_platform_authority(ptc: &mut ProgramTestContext, ctx: &Ctx) {
+    let new_authority = Keypair::new();
+
+    const CODE: u32 = 6000 + CrowdfundingError::NoPendingAuthority as u32;
+    assert_matches!(
+        cancel_platform_authority(ptc, ctx).await,
+        Err(BanksClientError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::Custom(CODE))
+        ))
+    );
+
+    propose_platform_authority(ptc, ctx, new_authority.pubkey())
+        .await
+        .unwrap();
+    let Platform {
+        pending_authority, ..
+    } = fetch(ptc, ctx.platform).await.unwrap();
+    assert_eq!(pending_authority, new_authority.pubkey());
+
+    cancel_platform_authority(ptc, ctx).await.unwrap();
+    const CODE2: u32 = ErrorCode::ConstraintAddress as u32;
+    assert_matches!(
+        accept_platform_authority(ptc, &new_authority).await,
+        Err(BanksClientError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::Custom(CODE2))
+        ))
+    );
+
+    refresh_blockhash(ptc).await;
+    propose_platform_authority(ptc, ctx, new_authority.pubkey())
+        .await
+        .unwrap();
+    accept_platform_authority(ptc, &new_authority)
+        .await
+        .unwrap();
+
+    let Platform {
+        authority,
+        pending_authority,
+        ..
+    } = fetch(ptc, ctx.platform).await.unwrap();
+    assert_eq!(authority, new_authority.pubkey());
+    assert_eq!(pending_authority, Pubkey::default());
+
+    assert_matches!(
+        withdraw_fees(ptc, ctx).await,
+        Err(BanksClientError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::Custom(CODE2))
+        ))
+    );
 }
@@ -1,9 +1,16 @@
 use crate::ctx::*;
 use anchor_lang::prelude::*;
-use anchor_spl::token::spl_token::instruction::transfer;
+use anchor_spl::token::spl_token::{
+    self,
+    instruction::{initialize_account, initialize_mint, mint_to, transfer},
+};
 use core::result::Result;
+use solana_program::{program_pack::Pack, system_instruction};
 use solana_program_test::*;
 use solana_sdk::{signature::Keypair, signer::Signer, transaction::Transaction};
+use spl_associated_token_account::{
+    get_associated_token_address, instruction::create_associated_token_account,
+};
 
 pub async fn get_ptc_and_ctx() -> (ProgramTestContext, Ctx) {
     let pt = ProgramTest::new("crowdfunding", crowdfunding::ID, None);
@@ -13,12 +20,145 @@ pub async fn get_ptc_and_ctx() -> (ProgramTestContext, Ctx) {
     (ptc, ctx)
 }
 
+pub async fn advance_clock(ptc: &mut ProgramTestContext, seconds: i64) {
+    let mut clock: Clock = ptc.banks_client.get_sysvar().await.unwrap();
+    clock.unix_timestamp += seconds;
+    ptc.set_sysvar(&clock);
+}
+
+pub async fn airdrop(ptc: &mut ProgramTestContext, to: &Pubkey, lamports: u64) {
+    ptc.banks_client
+        .process_transaction(Transaction::new_signed_with_payer(
+            &[system_instruction::transfer(
+                &ptc.payer.pubkey(),
+                to,
+                lamports,
+            )],
+            Some(&ptc.payer.pubkey()),
+            &[&ptc.payer],
+            ptc.last_blockhash,
+        ))
+        .await
+        .unwrap();
+}
+
+pub async fn refresh_blockhash(ptc: &mut ProgramTestContext) {
+    ptc.last_blockhash = ptc
+        .banks_client
+        .get_new_latest_blockhash(&ptc.last_blockhash)
+        .await
+        .unwrap();
+}
+
+pub async fn create_mint(
+    ptc: &mut ProgramTestContext,
+    mint: &Keypair,
+    mint_authority: &Pubkey,
+) -> Result<(), BanksClientError> {
+    let rent = ptc.banks_client.get_rent().await?;
+    ptc.banks_client
+        .process_transaction(Transaction::new_signed_with_payer(
+            &[
+                system_instruction::create_account(
+                    &ptc.payer.pubkey(),
+                    &mint.pubkey(),
+                    rent.minimum_balance(spl_token::state::Mint::LEN),
+                    spl_token::state::Mint::LEN as u64,
+                    &anchor_spl::token::ID,
+                ),
+                initialize_mint(
+                    &anchor_spl::token::ID,
+                    &mint.pubkey(),
+                    mint_authority,
+                    None,
+                    6,
+                )
+                .unwrap(),
+            ],
+            Some(&ptc.payer.pubkey()),
+            &[&ptc.payer, mint],
+            ptc.last_blockhash,
+        ))
+        .await
+}
+
+pub async fn create_token_account(
+    ptc: &mut ProgramTestContext,
+    account: &Keypair,
+    mint: &Pubkey,
+    owner: &Pubkey,
+) -> Result<(), BanksClientError> {
+    let rent = ptc.banks_client.get_rent().await?;
+    ptc.banks_client
+        .process_transaction(Transaction::new_signed_with_payer(
+            &[
+                system_instruction::create_account(
+                    &ptc.payer.pubkey(),
+                    &account.pubkey(),
+                    rent.minimum_balance(spl_token::state::Account::LEN),
+                    spl_token::state::Account::LEN as u64,
+                    &anchor_spl::token::ID,
+                ),
+                initialize_account(&anchor_spl::token::ID, &account.pubkey(), mint, owner).unwrap(),
+            ],
+            Some(&ptc.payer.pubkey()),
+            &[&ptc.payer, account],
+            ptc.last_blockhash,
+        ))
+        .await
+}
+
+pub async fn create_ata(
+    ptc: &mut ProgramTestContext,
+    wallet_address: &Pubkey,
+    mint: &Pubkey,
+) -> Result<Pubkey, BanksClientError> {
+    ptc.banks_client
+        .process_transaction(Transaction::new_signed_with_payer(
+            &[create_associated_token_account(
+                &ptc.payer.pubkey(),
+                wallet_address,
+                mint,
+            )],
+            Some(&ptc.payer.pubkey()),
+            &[&ptc.payer],
+            ptc.last_blockhash,
+        ))
+        .await?;
+    Ok(get_associated_token_address(wallet_address, mint))
+}
+
+pub async fn mint_tokens(
+    ptc: &mut ProgramTestContext,
+    mint: &Pubkey,
+    to: &Pubkey,
+    amount: u64,
+    mint_authority: &Keypair,
+) -> Result<(), BanksClientError> {
+    ptc.banks_client
+        .process_transaction(Transaction::new_signed_with_payer(
+            &[mint_to(
+                &anchor_spl::token::ID,
+                mint,
+                to,
+                &mint_authority.pubkey(),
+                &[&mint_authority.pubkey()],
+                amount,
+            )
+            .unwrap()],
+            Some(&mint_authority.pubkey()),
+            &[mint_authority],
+            ptc.last_blockhash,
+        ))
+        .await
+}
+
 pub async fn transfer_tokens(
     ptc: &mut ProgramTestContext,
     from: &Pubkey,
     to: &Pubkey,
+    authority: &Keypair,
     amount: u64,
-    signer: &Keypair,
 ) -> Result<(), BanksClientError> {
     ptc.banks_client
         .process_transaction(Transaction::new_signed_with_payer(
@@ -26,13 +166,13 @@ pub async fn transfer_tokens(
                 &anchor_spl::token::ID,
                 from,
                 to,
-                &signer.pubkey(),
-                &[&signer.pubkey()],
+                &authority.pubkey(),
+                &[&authority.pubkey()],
                 amount,
             )
             .unwrap()],
-            Some(&signer.pubkey()),
-            &[signer],
+            Some(&authority.pubkey()),
+            &[authority],
             ptc.last_blockhash,
         ))
         .await